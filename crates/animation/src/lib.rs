@@ -106,8 +106,11 @@ pub struct LayerName {
 #[derive(Component)]
 pub struct AnimatedLayer {}
 
-#[derive(Component)]
-pub struct ColoredLayer {}
+// Tint applied to the sprite of a layer, removing it restore the original colors
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+pub struct ColoredLayer {
+    pub color: Color,
+}
 
 #[derive(Component, Clone)]
 pub struct ActiveLayers {
@@ -440,6 +443,23 @@ fn character_visuals_update_system(
     }
 }
 
+// Apply the ColoredLayer tint on the layer sprite
+fn colored_layer_system(
+    mut query_colored: Query<(&ColoredLayer, &mut Sprite), Changed<ColoredLayer>>,
+    mut removed_colored: RemovedComponents<ColoredLayer>,
+    mut query_uncolored: Query<&mut Sprite, Without<ColoredLayer>>,
+) {
+    for (colored_layer, mut sprite) in query_colored.iter_mut() {
+        sprite.color = colored_layer.color;
+    }
+
+    for entity in removed_colored.read() {
+        if let Ok(mut sprite) = query_uncolored.get_mut(entity) {
+            sprite.color = Color::WHITE;
+        }
+    }
+}
+
 // SYSTEM THAT RUN ON THE BEVY SCHEDULE FOR SYNCH
 
 pub fn set_sprite_flip(
@@ -530,6 +550,7 @@ impl Plugin for D2AnimationPlugin {
                 character_visuals_update_system,
                 animate_sprite_system.after(character_visuals_update_system),
                 check_animation_config_reload_system.after(animate_sprite_system),
+                colored_layer_system,
             ),
         );
     }
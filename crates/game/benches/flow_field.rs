@@ -0,0 +1,164 @@
+//! Flow field benchmarks on a large generated map with 4 players and 200 enemies.
+//!
+//! Run with `cargo bench -p game --bench flow_field`.
+
+use std::collections::BTreeSet;
+use std::hint::black_box;
+
+use bevy::math::Vec2;
+use bevy_fixed::fixed_math;
+use criterion::{criterion_group, criterion_main, Criterion};
+use game::character::enemy::ai::{
+    update_flow_field, FlowField, FlowFieldCache, FlowFieldConfig, GridPos, NavProfile,
+};
+
+const MAP_SIZE: usize = 256;
+const ENEMY_COUNT: usize = 200;
+/// Frames re-simulated by a typical rollback
+const ROLLBACK_FRAMES: usize = 8;
+
+/// Rooms of 16x16 cells with a door in the middle of every wall
+fn generate_map() -> Vec<Vec<bool>> {
+    (0..MAP_SIZE)
+        .map(|y| {
+            (0..MAP_SIZE)
+                .map(|x| {
+                    let wall_x = x % 16 == 0 && !(6..10).contains(&(y % 16));
+                    let wall_y = y % 16 == 0 && !(6..10).contains(&(x % 16));
+                    wall_x || wall_y
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn world_pos(x: i32, y: i32) -> fixed_math::FixedVec2 {
+    GridPos::new(x, y).to_fixed()
+}
+
+fn setup() -> (FlowFieldCache, FlowFieldConfig, Vec<GridPos>, Vec<fixed_math::FixedVec2>) {
+    let mut cache = FlowFieldCache::new();
+    cache.load_intgrid_walls(&generate_map(), Vec2::ZERO, MAP_SIZE, MAP_SIZE);
+    cache.rebuild_blocked_cells(std::iter::empty());
+
+    let config = FlowFieldConfig::default();
+    let center = (MAP_SIZE / 2) as i32;
+    let players = vec![
+        GridPos::new(center - 20, center - 20),
+        GridPos::new(center + 20, center - 20),
+        GridPos::new(center - 20, center + 20),
+        GridPos::new(center + 20, center + 20),
+    ];
+
+    // Enemies spread on a ring around the players (deterministic positions)
+    let enemies = (0..ENEMY_COUNT)
+        .map(|i| {
+            let x = center - 45 + ((i * 37) % 90) as i32;
+            let y = center - 45 + ((i * 53) % 90) as i32;
+            world_pos(x, y)
+        })
+        .collect();
+
+    (cache, config, players, enemies)
+}
+
+fn build_fields(
+    cache: &FlowFieldCache,
+    config: &FlowFieldConfig,
+    players: &[GridPos],
+) -> (FlowField, Vec<FlowField>) {
+    let shared = FlowField::build(players, NavProfile::GroundBreaker, cache, config);
+    let per_player = players
+        .iter()
+        .map(|player| FlowField::build(&[*player], NavProfile::GroundBreaker, cache, config))
+        .collect();
+    (shared, per_player)
+}
+
+fn steer_enemies(fields: &[FlowField], enemies: &[fixed_math::FixedVec2]) {
+    for (i, enemy) in enemies.iter().enumerate() {
+        let field = &fields[i % fields.len()];
+        black_box(field.get_direction_vector(*enemy));
+    }
+}
+
+fn bench_flow_field(c: &mut Criterion) {
+    let (cache, config, players, enemies) = setup();
+    let no_changes = BTreeSet::new();
+
+    c.bench_function("flow_field_full_build", |b| {
+        b.iter(|| black_box(build_fields(&cache, &config, &players)))
+    });
+
+    let (shared, per_player) = build_fields(&cache, &config, &players);
+    let moved: Vec<GridPos> = players
+        .iter()
+        .map(|player| GridPos::new(player.x + 1, player.y))
+        .collect();
+
+    c.bench_function("flow_field_retarget_1_cell", |b| {
+        b.iter(|| {
+            let field = update_flow_field(
+                Some(shared.clone()),
+                &moved,
+                NavProfile::GroundBreaker,
+                &no_changes,
+                &cache,
+                &config,
+            );
+            black_box(field)
+        })
+    });
+
+    // A wall closing the door of the room of the first player
+    let mut blocked_cache = cache.clone();
+    let door_x = players[0].x - players[0].x % 16;
+    let door: BTreeSet<GridPos> = (players[0].y - 8..players[0].y + 8)
+        .map(|y| GridPos::new(door_x, y))
+        .filter(|pos| !cache.wall_cells.contains(pos))
+        .collect();
+    blocked_cache.wall_cells.extend(door.iter().copied());
+
+    c.bench_function("flow_field_obstacle_repair", |b| {
+        b.iter(|| {
+            let mut field = shared.clone();
+            black_box(field.repair(&door, &blocked_cache, &config));
+            black_box(field)
+        })
+    });
+
+    let mut fields = per_player.clone();
+    fields.push(shared.clone());
+    c.bench_function("flow_field_200_enemies_lookup", |b| {
+        b.iter(|| steer_enemies(&fields, &enemies))
+    });
+
+    // Snapshot restore, then re-simulate the frames with one field update
+    let mut full_cache = cache.clone();
+    full_cache.layers.insert(NavProfile::GroundBreaker, shared.clone());
+    for (index, field) in per_player.iter().enumerate() {
+        full_cache.player_layers.insert(index, field.clone());
+    }
+    c.bench_function("flow_field_rollback_8_frames", |b| {
+        b.iter(|| {
+            let restored = full_cache.clone();
+            for frame in 0..ROLLBACK_FRAMES {
+                if frame == ROLLBACK_FRAMES / 2 {
+                    black_box(update_flow_field(
+                        restored.layers.get(&NavProfile::GroundBreaker).cloned(),
+                        &moved,
+                        NavProfile::GroundBreaker,
+                        &no_changes,
+                        &restored,
+                        &config,
+                    ));
+                }
+                steer_enemies(&fields, &enemies);
+            }
+            black_box(restored)
+        })
+    });
+}
+
+criterion_group!(benches, bench_flow_field);
+criterion_main!(benches);
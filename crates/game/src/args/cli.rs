@@ -23,6 +23,9 @@ pub struct Opt {
     pub name: Option<String>,
     #[clap(long)]
     pub debug_ai: bool,
+    /// Game mode to play (waves, endless, timed)
+    #[clap(long)]
+    pub game_mode: Option<String>,
     #[clap(long)]
     pub telemetry: bool,
     #[clap(long, default_value = "http://localhost:5080/api/default/default/_json")]
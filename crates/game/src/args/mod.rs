@@ -30,6 +30,7 @@ pub struct GameArgs {
     pub lobby: String,
     pub cid: String,
     pub debug_ai: bool,
+    pub game_mode: String,
     pub telemetry: bool,
     pub telemetry_url: String,
     pub telemetry_auth: String,
@@ -84,6 +85,7 @@ pub fn get_args() -> GameArgs {
             lobby: args.lobby.unwrap_or(String::new()),
             cid: args.cid.unwrap_or(generate_random_correlation_id()),
             debug_ai: args.debug_ai,
+            game_mode: args.game_mode.unwrap_or(String::new()),
             telemetry: args.telemetry,
             telemetry_url: args.telemetry_url,
             telemetry_auth: args.telemetry_auth,
@@ -133,6 +135,7 @@ pub fn get_args() -> GameArgs {
             lobby: canvas_config.lobby.unwrap_or(String::new()),
             cid: generate_random_correlation_id(),
             debug_ai: false, // debug_ai not supported on WASM
+            game_mode: canvas_config.game_mode.unwrap_or(String::new()),
             telemetry: canvas_config.telemetry,
             telemetry_url: canvas_config.telemetry_url,
             telemetry_auth: canvas_config.telemetry_auth,
@@ -180,6 +183,7 @@ impl Plugin for BaseArgsPlugin {
                 udp_port: args.local_port,
             },
             players: args.players,
+            game_mode: args.game_mode,
         });
     }
 }
@@ -15,6 +15,7 @@ pub struct CanvasConfig {
     pub number_player: Option<usize>,
     pub matchbox: Option<String>,
     pub lobby: Option<String>,
+    pub game_mode: Option<String>,
     pub telemetry: bool,
     pub telemetry_url: String,
     pub telemetry_auth: String,
@@ -35,6 +36,7 @@ pub fn read_canvas_data_system() -> CanvasConfig {
 
     config.matchbox = canvas_element.get_attribute("data-matchbox");
     config.lobby = canvas_element.get_attribute("data-lobby");
+    config.game_mode = canvas_element.get_attribute("data-game-mode");
 
     // Telemetry settings
     if let Some(enabled) = canvas_element.get_attribute("data-telemetry") {
@@ -11,7 +11,10 @@ use leafwing_input_manager::prelude::*;
 use serde::{Deserialize, Serialize};
 use ui::CameraDebugUIPlugin;
 
-use crate::character::player::{control::PlayerAction, LocalPlayer, Player};
+use crate::character::player::{control::PlayerAction, respawn::Spectator, LocalPlayer, Player};
+
+/// Ambient light brightness outside of fog waves
+pub const DEFAULT_AMBIENT_BRIGHTNESS: f32 = 0.4;
 
 #[derive(Asset, TypePath, Debug, Clone, Deserialize, Serialize)]
 pub struct CameraSettingsAsset(pub CameraSettings);
@@ -106,7 +109,8 @@ pub struct PlayerIndicator {
 fn camera_input_system(
     action_query: Query<&ActionState<PlayerAction>>,
     mut camera_query: Query<&mut GameCamera>,
-    _player_query: Query<(Entity, &Player)>,
+    player_query: Query<(Entity, &Player)>,
+    spectator_query: Query<(), With<Spectator>>,
 ) {
     let action_state = if let Ok(state) = action_query.single() {
         state
@@ -135,14 +139,15 @@ fn camera_input_system(
         }
     }
 
-    // Handle player switching in PlayerLock mode
-    /*
-    if action_state.just_pressed(PlayerAction::SwitchTargetPlayer) && camera.mode == CameraMode::PlayerLock {
-        // Collect all player entities
-        let players: Vec<Entity> = player_query
-            .iter()
-            .map(|(entity, _)| entity)
-            .collect();
+    // Handle player switching in PlayerLock mode, only while spectating a teammate
+    if action_state.just_pressed(&PlayerAction::SwitchTargetPlayer)
+        && camera.mode == CameraMode::PlayerLock
+        && !spectator_query.is_empty()
+    {
+        // Collect all player entities, sorted by handle
+        let mut players: Vec<(Entity, &Player)> = player_query.iter().collect();
+        players.sort_by_key(|(_, player)| player.handle);
+        let players: Vec<Entity> = players.into_iter().map(|(entity, _)| entity).collect();
 
         if players.is_empty() {
             return;
@@ -159,7 +164,6 @@ fn camera_input_system(
         let next_index = (current_index + 1) % players.len();
         camera.target_player_id = Some(players[next_index]);
     }
-    */
 }
 
 // Main camera control system
@@ -201,6 +205,16 @@ fn camera_control_system(
             return;
         };
 
+    // Forget the target once the player is despawned (dead), or the spectated
+    // teammate once the local player respawned
+    let has_local_player = player_query.iter().any(|(_, _, _, local)| local.is_some());
+    if camera.target_player_id.is_some_and(|target| match player_query.get(target) {
+        Ok((_, _, _, local)) => has_local_player && local.is_none(),
+        Err(_) => true,
+    }) {
+        camera.target_player_id = None;
+    }
+
     // Find the local player if not already set
     if camera.target_player_id.is_none() {
         for (entity, _, _, local_player_opt) in player_query.iter() {
@@ -211,6 +225,14 @@ fn camera_control_system(
         }
     }
 
+    // Spectate the first living teammate while the local player is dead
+    if camera.target_player_id.is_none() {
+        camera.target_player_id = player_query
+            .iter()
+            .min_by_key(|(_, _, player, _)| player.handle)
+            .map(|(entity, _, _, _)| entity);
+    }
+
     // Calculate target position and zoom based on camera mode
     match camera.mode {
         CameraMode::PlayerLock => {
@@ -519,7 +541,7 @@ pub fn setup_camera(mut commands: Commands, settings: Res<CameraSettings>) {
             Camera2d::default(),
             SpatialAudioReceiver,
             AmbientLight2d{
-                brightness: 0.4,
+                brightness: DEFAULT_AMBIENT_BRIGHTNESS,
                 ..Default::default()
             },
             GameCamera {
@@ -4,7 +4,10 @@ use serde::Deserialize;
 
 use crate::{character::movement::MovementConfig, collider::ColliderConfig};
 
-use super::health::HealthConfig;
+use super::{
+    health::{modifiers::DamageModifiersConfig, HealthConfig},
+    stamina::StaminaConfig,
+};
 
 #[derive(Debug, Deserialize, Clone)]
 pub struct CharacterSkin {
@@ -19,6 +22,14 @@ pub struct CharacterConfig {
 
     pub base_health: HealthConfig,
 
+    // Characters without stamina sprint, dash and attack freely
+    #[serde(default)]
+    pub stamina: Option<StaminaConfig>,
+
+    // Critical zone and resistances, none when missing
+    #[serde(default)]
+    pub damage_modifiers: DamageModifiersConfig,
+
     pub collider: ColliderConfig,
 
     pub scale: fixed_math::Fixed,
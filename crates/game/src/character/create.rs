@@ -16,8 +16,10 @@ use bevy_ggrs::AddRollbackCommandExtension;
 use super::{
     config::CharacterConfig,
     dash::DashState,
-    health::{ui::HealthBar, Health, HealthRegen},
+    health::{modifiers::DamageModifiers, ui::{HealthBar, StaminaBar}, Health, HealthRegen},
+    stamina::Stamina,
     movement::SprintState,
+    status_effect::StatusEffects,
     Character,
 };
 
@@ -175,6 +177,7 @@ pub fn create_character(
         SprintState::default(),
         DashState::default(),
         MeleeAttackState::default(),
+        StatusEffects::default(),
         collider,
         health,
         collision_layer,
@@ -196,6 +199,14 @@ pub fn create_character(
         });
     }
 
+    // Add damage modifiers if configured
+    if !config.damage_modifiers.is_empty() {
+        commands.entity(entity).insert(DamageModifiers::from_config(
+            &config.damage_modifiers,
+            config.scale,
+        ));
+    }
+
     // Add health bar as child
     commands.entity(entity).with_children(|parent| {
         parent
@@ -211,6 +222,25 @@ pub fn create_character(
             .add_rollback();
     });
 
+    // Add stamina and its bar under the health bar if configured
+    if let Some(stamina_config) = config.stamina.clone() {
+        let stamina: Stamina = stamina_config.into();
+        commands.entity(entity).insert(stamina);
+        commands.entity(entity).with_children(|parent| {
+            parent
+                .spawn((
+                    StaminaBar,
+                    Sprite {
+                        color: Color::srgb(1.0, 0.85, 0.2),
+                        custom_size: Some(Vec2::new(30.0, 2.0)),
+                        ..default()
+                    },
+                    Transform::from_translation(Vec3::new(0.0, 7.5, 0.1)),
+                ))
+                .add_rollback();
+        });
+    }
+
     commands.entity(entity).add_rollback();
 
     entity
@@ -10,66 +10,141 @@
 use bevy::prelude::*;
 use bevy_fixed::fixed_math;
 use bevy_ggrs::Rollback;
-use utils::{frame::FrameCount, net_id::GgrsNetId, order_mut_iter};
+use utils::{
+    frame::FrameCount,
+    net_id::{GgrsNetId, GgrsNetIdFactory},
+    order_mut_iter,
+};
 
 use crate::character::enemy::Enemy;
 use crate::character::health::DamageAccumulator;
-use crate::character::movement::Velocity;
-use crate::character::player::Player;
-
+use crate::character::movement::{SprintState, Velocity};
+use crate::character::player::{downed::Downed, Player};
+use crate::character::status_effect::{StatusEffect, StatusEffectKind, StatusEffects};
+use crate::collider::CollisionSettings;
+use crate::weapons::spawn_enemy_projectile;
+
+use super::behavior_tree::{BehaviorAction, BehaviorTreeState};
+use super::lod::EnemyLod;
 use super::navigation::FlowFieldCache;
+use super::perception::{self, NoiseConfig, NoiseEvents};
 use super::obstacle::{Obstacle, ObstacleAttackEvent};
-use super::state::{AttackTarget, EnemyAiConfig, EnemyTarget, MonsterState, TargetType};
+use super::vault::WindowVault;
+use super::state::{
+    AttackTarget, EnemyAiConfig, EnemyTarget, MonsterState, RangedAttackState, TargetType,
+};
 
-/// System to select targets for enemies based on proximity
+/// System to select targets for enemies based on what they perceive
 ///
 /// NOTE: Zombies only target players here. Window/obstacle attacks are handled
 /// via collision detection in `move_enemies` (pathing.rs). This keeps the logic
 /// simple: follow flow field toward player, attack whatever physically blocks you.
+///
+/// Enemies with a `PerceptionConfig` need to see a player (or hear a noise) to
+/// react, see `perception.rs`. The noises are cleared once every enemy heard them.
 pub fn enemy_target_selection(
     frame: Res<FrameCount>,
+    flow_field_cache: Res<FlowFieldCache>,
+    noise_config: Res<NoiseConfig>,
+    mut noises: ResMut<NoiseEvents>,
     mut enemy_query: Query<
         (
             &GgrsNetId,
             &fixed_math::FixedTransform3D,
             &EnemyAiConfig,
+            &animation::FacingDirection,
             &mut EnemyTarget,
             &mut MonsterState,
+            Option<&StatusEffects>,
+            Option<&EnemyLod>,
         ),
         With<Enemy>,
     >,
+    // Downed players are ignored until they are revived
     player_query: Query<
-        (&GgrsNetId, &fixed_math::FixedTransform3D),
-        (With<Player>, Without<Enemy>),
+        (&GgrsNetId, &fixed_math::FixedTransform3D, Option<&SprintState>),
+        (With<Player>, Without<Enemy>, Without<Downed>),
     >,
 ) {
+    // Distance at which an investigated spot counts as searched
+    let investigate_reached_distance = fixed_math::new(24.0);
+
     // Collect and sort players for deterministic iteration
     let mut players: Vec<_> = player_query.iter().collect();
-    players.sort_by_key(|(net_id, _)| net_id.0);
+    players.sort_by_key(|(net_id, ..)| net_id.0);
 
-    for (_enemy_net_id, enemy_transform, ai_config, mut target, mut state) in
-        order_mut_iter!(enemy_query)
+    // Sprinting footsteps can be heard, walking is silent
+    for (_, player_transform, sprint_state) in &players {
+        if sprint_state.is_some_and(|sprint| sprint.is_sprinting) {
+            noises.emit(player_transform.translation.truncate(), noise_config.sprint_radius);
+        }
+    }
+
+    for (
+        _enemy_net_id,
+        enemy_transform,
+        ai_config,
+        facing,
+        mut target,
+        mut state,
+        status_effects,
+        lod,
+    ) in order_mut_iter!(enemy_query)
     {
         let enemy_pos = enemy_transform.translation.truncate();
 
-        // Don't retarget if attacking, stunned, or dead
+        // Don't retarget while stunned
+        if status_effects.is_some_and(|status| status.is_stunned(frame.frame)) {
+            continue;
+        }
+
+        // Don't retarget if attacking or dead
         match *state {
             MonsterState::Attacking { .. }
-            | MonsterState::Stunned { .. }
             | MonsterState::Breaching { .. }
+            | MonsterState::Vaulting { .. }
             | MonsterState::Dead => continue,
             _ => {}
         }
 
-        // Find closest player deterministically
+        // Far enemies only rethink on their LOD frames, but the noises are
+        // cleared every frame so they still listen in between
+        if EnemyLod::skips(lod) {
+            let heard = ai_config
+                .perception
+                .as_ref()
+                .filter(|_| target.target_type != TargetType::Player)
+                .and_then(|perception| noises.heard_from(enemy_pos, perception.hearing_multiplier));
+            if let Some(noise_pos) = heard {
+                target.investigate(noise_pos);
+                *state = MonsterState::Investigating {
+                    start_frame: frame.frame,
+                };
+            }
+            continue;
+        }
+
+        // Find closest seen player deterministically
         let mut closest_player: Option<(GgrsNetId, fixed_math::Fixed, fixed_math::FixedVec2)> =
             None;
 
-        for (player_net_id, player_transform) in &players {
+        for (player_net_id, player_transform, _) in &players {
             let player_pos = player_transform.translation.truncate();
             let distance = enemy_pos.distance(&player_pos);
 
-            if distance < ai_config.aggro_range {
+            let seen = match ai_config.perception.as_ref() {
+                Some(perception) => perception::can_see(
+                    perception,
+                    ai_config.aggro_range,
+                    enemy_pos,
+                    facing,
+                    player_pos,
+                    &flow_field_cache,
+                ),
+                None => distance < ai_config.aggro_range,
+            };
+
+            if seen {
                 match closest_player {
                     None => {
                         closest_player = Some(((*player_net_id).clone(), distance, player_pos));
@@ -91,13 +166,51 @@ pub fn enemy_target_selection(
             target.target_type = TargetType::Player;
             target.last_known_position = Some(player_pos);
             *state = MonsterState::Chasing;
+            continue;
+        }
+
+        let Some(perception) = ai_config.perception.as_ref() else {
+            target.clear();
+            *state = MonsterState::Idle;
+            continue;
+        };
+
+        if let Some(noise_pos) = noises.heard_from(enemy_pos, perception.hearing_multiplier) {
+            // A new noise restarts the investigation
+            target.investigate(noise_pos);
+            *state = MonsterState::Investigating {
+                start_frame: frame.frame,
+            };
+        } else if target.target_type == TargetType::Player {
+            // Lost sight of the player, search where it was last seen
+            match target.last_known_position {
+                Some(last_known_position) => {
+                    target.investigate(last_known_position);
+                    *state = MonsterState::Investigating {
+                        start_frame: frame.frame,
+                    };
+                }
+                None => {
+                    target.clear();
+                    *state = MonsterState::Idle;
+                }
+            }
+        } else if let MonsterState::Investigating { start_frame } = *state {
+            // Give up once the spot is reached or the memory fades
+            let reached = target
+                .last_known_position
+                .is_none_or(|position| enemy_pos.distance(&position) < investigate_reached_distance);
+            if reached || frame.frame >= start_frame + perception.memory_frames {
+                target.clear();
+                *state = MonsterState::Idle;
+            }
         } else {
-            target.target = None;
-            target.target_type = TargetType::None;
-            target.last_known_position = None;
+            target.clear();
             *state = MonsterState::Idle;
         }
     }
+
+    noises.noises.clear();
 }
 
 /// System to move enemies using the flow field
@@ -143,7 +256,7 @@ pub fn enemy_movement_system(
 
         // Get movement direction from flow field
         let nav_profile = ai_config.nav_profile();
-        let flow_field = match flow_field_cache.get_flow_field(nav_profile) {
+        let flow_field = match flow_field_cache.get_flow_field_to(target.target.as_ref(), nav_profile) {
             Some(ff) => ff,
             None => {
                 // Fallback: move directly toward last known position
@@ -256,6 +369,8 @@ pub fn enemy_attack_system(
             &EnemyAiConfig,
             &EnemyTarget,
             &mut MonsterState,
+            Option<&StatusEffects>,
+            Option<&BehaviorTreeState>,
         ),
         With<Enemy>,
     >,
@@ -270,10 +385,41 @@ pub fn enemy_attack_system(
     mut player_damage_query: Query<&mut DamageAccumulator>,
     mut obstacle_events: MessageWriter<ObstacleAttackEvent>,
 ) {
-    for (enemy_net_id, enemy_entity, enemy_transform, ai_config, target, mut state) in
-        order_mut_iter!(enemy_query)
+    for (
+        enemy_net_id,
+        enemy_entity,
+        enemy_transform,
+        ai_config,
+        target,
+        mut state,
+        status_effects,
+        tree_state,
+    ) in order_mut_iter!(enemy_query)
     {
+        // Stunned enemies can't attack
+        if status_effects.is_some_and(|status| status.is_stunned(frame.frame)) {
+            continue;
+        }
+
+        // Enemies with a behaviour tree only attack when it asks for it
+        let tree_allows_attack = tree_state.is_none_or(|tree_state| {
+            matches!(
+                (tree_state.action, target.target_type),
+                (BehaviorAction::Attack, TargetType::Player)
+                    | (BehaviorAction::BreachObstacle, TargetType::Obstacle)
+            )
+        });
+        if !tree_allows_attack {
+            if matches!(*state, MonsterState::Attacking { .. }) {
+                *state = MonsterState::Chasing;
+            }
+            continue;
+        }
+
         let enemy_pos = enemy_transform.translation.truncate();
+        let attack_damage = status_effects.map_or(ai_config.attack_damage, |status| {
+            ai_config.attack_damage.saturating_mul(status.damage_multiplier(frame.frame))
+        });
 
         match target.target_type {
             TargetType::Player => {
@@ -302,7 +448,7 @@ pub fn enemy_attack_system(
                             if should_attack {
                                 // Apply damage
                                 if let Ok(mut damage) = player_damage_query.get_mut(player_entity) {
-                                    damage.total_damage += ai_config.attack_damage;
+                                    damage.total_damage += attack_damage;
                                 }
 
                                 *state = MonsterState::Attacking {
@@ -317,7 +463,7 @@ pub fn enemy_attack_system(
                                     frame.frame,
                                     enemy_net_id,
                                     player_net_id,
-                                    ai_config.attack_damage.to_num::<f32>()
+                                    attack_damage.to_num::<f32>()
                                 );
                             }
                         } else {
@@ -389,6 +535,12 @@ pub fn enemy_attack_system(
                     }
                 }
             }
+            TargetType::Lure | TargetType::Investigate => {
+                // Lured and investigating enemies only walk to the spot, nothing to attack there
+                if matches!(*state, MonsterState::Attacking { .. }) {
+                    *state = MonsterState::Chasing;
+                }
+            }
             TargetType::None => {
                 // No target, return to idle
                 if *state == MonsterState::Chasing {
@@ -399,23 +551,116 @@ pub fn enemy_attack_system(
     }
 }
 
-/// System to handle stunned state recovery
-pub fn enemy_stun_recovery_system(
+/// System to fire the projectiles of ranged enemies
+///
+/// The enemy stands still for `windup_frames` once its target is in range (and
+/// in sight when required), then fires toward the target position. Moving out
+/// of range or sight, or a stun, cancels the windup.
+pub fn enemy_ranged_attack_system(
+    mut commands: Commands,
     frame: Res<FrameCount>,
-    mut enemy_query: Query<(&GgrsNetId, &mut MonsterState), With<Enemy>>,
+    collision_settings: Res<CollisionSettings>,
+    flow_field_cache: Res<FlowFieldCache>,
+    mut id_factory: ResMut<GgrsNetIdFactory>,
+    mut enemy_query: Query<
+        (
+            &GgrsNetId,
+            &fixed_math::FixedTransform3D,
+            &EnemyAiConfig,
+            &EnemyTarget,
+            &mut RangedAttackState,
+            Option<&StatusEffects>,
+        ),
+        // Climbing enemies can't shoot
+        (With<Enemy>, With<Rollback>, Without<WindowVault>),
+    >,
+    player_query: Query<
+        (&GgrsNetId, &fixed_math::FixedTransform3D),
+        (With<Player>, Without<Enemy>, Without<Downed>),
+    >,
 ) {
-    for (_net_id, mut state) in order_mut_iter!(enemy_query) {
-        if let MonsterState::Stunned { recover_at_frame } = *state {
-            if frame.frame >= recover_at_frame {
-                *state = MonsterState::Idle;
+    for (enemy_net_id, enemy_transform, ai_config, target, mut ranged_state, status_effects) in
+        order_mut_iter!(enemy_query)
+    {
+        let Some(ranged) = ai_config.ranged_attack.as_ref() else {
+            continue;
+        };
+
+        if status_effects.is_some_and(|status| status.is_stunned(frame.frame)) {
+            ranged_state.windup_started_frame = None;
+            continue;
+        }
+
+        // Only players are shot at, obstacles are clawed at in `enemy_attack_system`
+        let target_pos = match (&target.target_type, &target.target) {
+            (TargetType::Player, Some(target_net_id)) => player_query
+                .iter()
+                .find(|(player_net_id, _)| *player_net_id == target_net_id)
+                .map(|(_, player_transform)| player_transform.translation.truncate()),
+            _ => None,
+        };
+        let Some(target_pos) = target_pos else {
+            ranged_state.windup_started_frame = None;
+            continue;
+        };
+
+        let enemy_pos = enemy_transform.translation.truncate();
+        let can_fire = enemy_pos.distance(&target_pos) <= ranged.projectile_range
+            && (!ranged.requires_line_of_sight
+                || flow_field_cache.has_line_of_sight(enemy_pos, target_pos));
+
+        match ranged_state.windup_started_frame {
+            Some(_) if !can_fire => {
+                ranged_state.windup_started_frame = None;
+            }
+            Some(start_frame) if frame.frame >= start_frame + ranged.windup_frames => {
+                let projectile = status_effects.map_or(ranged.projectile, |status| {
+                    ranged
+                        .projectile
+                        .with_damage_multiplier(status.damage_multiplier(frame.frame))
+                });
+
+                spawn_enemy_projectile(
+                    &mut commands,
+                    enemy_net_id,
+                    enemy_pos,
+                    (target_pos - enemy_pos).normalize_or_zero(),
+                    projectile,
+                    ranged.projectile_range,
+                    frame.frame,
+                    &collision_settings,
+                    &mut id_factory,
+                );
+
+                ranged_state.windup_started_frame = None;
+                ranged_state.last_fire_frame = Some(frame.frame);
+
+                info!(
+                    "[{}] Enemy {} fired a {} projectile",
+                    frame.frame, enemy_net_id, ranged.projectile
+                );
+            }
+            Some(_) => {}
+            None => {
+                if can_fire
+                    && ranged_state.can_start_windup(frame.frame, ai_config.attack_cooldown_frames)
+                {
+                    ranged_state.windup_started_frame = Some(frame.frame);
+                }
             }
         }
     }
 }
 
-/// Apply stun to an enemy
-pub fn apply_stun(state: &mut MonsterState, current_frame: u32, stun_duration: u32) {
-    *state = MonsterState::Stunned {
-        recover_at_frame: current_frame + stun_duration,
-    };
+/// Apply stun to an enemy, it recovers once the status effect expires
+pub fn apply_stun(status_effects: &mut StatusEffects, current_frame: u32, stun_duration: u32) {
+    status_effects.apply(
+        current_frame,
+        StatusEffect::new(
+            StatusEffectKind::Stunned,
+            fixed_math::FIXED_ZERO,
+            current_frame,
+            stun_duration,
+        ),
+    );
 }
@@ -0,0 +1,581 @@
+//! Enemy Behaviour Trees
+//!
+//! An archetype can describe its decisions with a behaviour tree in its RON
+//! file (`behavior` field) instead of the default chase and attack loop:
+//!
+//! - **Composites**: `Selector` (first child not failing) and `Sequence`
+//!   (children in order until one doesn't succeed)
+//! - **Conditions**: health, distance to the target, line of sight
+//! - **Actions**: `MoveTo`, `Attack`, `Flee`, `BreachObstacle`, `WaitFrames`
+//!
+//! The tree is ticked from the root every frame, after the target selection,
+//! so conditions react right away. Actions only pick a `BehaviorAction`,
+//! `move_enemies` and `enemy_attack_system` carry it out.
+//!
+//! GGRS CRITICAL: the frame each running node started is kept in the
+//! `BehaviorTreeState` rollback component, never in the tree itself.
+
+use std::sync::Arc;
+
+use bevy::prelude::*;
+use bevy_fixed::fixed_math;
+use bevy_ggrs::Rollback;
+use serde::{Deserialize, Serialize};
+use utils::{frame::FrameCount, net_id::GgrsNetId, order_iter, order_mut_iter};
+
+use crate::character::enemy::Enemy;
+use crate::character::health::Health;
+
+use super::lod::EnemyLod;
+use super::navigation::FlowFieldCache;
+use super::obstacle::Obstacle;
+use super::state::{EnemyAiConfig, EnemyTarget, TargetType};
+
+/// Node of a behaviour tree, as authored in the archetype RON
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum BehaviorNode {
+    Selector(Vec<BehaviorNode>),
+    Sequence(Vec<BehaviorNode>),
+    Condition(BehaviorCondition),
+    /// Walk toward the target until within the distance
+    MoveTo {
+        within: fixed_math::Fixed,
+    },
+    /// Attack the target while it is in attack range
+    Attack,
+    /// Walk away from the target until at the distance
+    Flee {
+        distance: fixed_math::Fixed,
+    },
+    /// Attack the closest breakable obstacle in attack range
+    BreachObstacle,
+    /// Stand still for a number of frames
+    WaitFrames(u32),
+}
+
+/// Check of a `Condition` node
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum BehaviorCondition {
+    /// Health below a fraction of the max (0.0 - 1.0)
+    HealthBelow(fixed_math::Fixed),
+    HealthAbove(fixed_math::Fixed),
+    HasTarget,
+    TargetWithin(fixed_math::Fixed),
+    TargetBeyond(fixed_math::Fixed),
+    /// No wall between the enemy and its target
+    LineOfSight,
+    Not(Box<BehaviorCondition>),
+}
+
+/// What the tree asks the enemy to do this frame
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default, Reflect, Serialize, Deserialize)]
+pub enum BehaviorAction {
+    /// Stand still
+    #[default]
+    Idle,
+    MoveTo,
+    Attack,
+    Flee,
+    BreachObstacle,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Status {
+    Success,
+    Failure,
+    Running,
+}
+
+#[derive(Clone, Debug)]
+enum FlatNode {
+    Selector(Vec<usize>),
+    Sequence(Vec<usize>),
+    /// Condition or action, never a composite
+    Leaf(BehaviorNode),
+}
+
+/// Behaviour tree of an enemy, flattened in depth-first order so a node
+/// index keys its state in `BehaviorTreeState`
+///
+/// The nodes never change, rollback snapshots share them instead of copying.
+///
+/// GGRS CRITICAL: Must be registered with `.rollback_component_with_clone::<BehaviorTree>()`
+#[derive(Component, Clone, Debug)]
+pub struct BehaviorTree {
+    nodes: Arc<[FlatNode]>,
+}
+
+impl BehaviorTree {
+    pub fn new(root: &BehaviorNode) -> Self {
+        let mut nodes = Vec::new();
+        flatten(root, &mut nodes);
+        Self {
+            nodes: nodes.into(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+}
+
+fn flatten(node: &BehaviorNode, nodes: &mut Vec<FlatNode>) -> usize {
+    let index = nodes.len();
+    match node {
+        BehaviorNode::Selector(children) | BehaviorNode::Sequence(children) => {
+            // Reserve the slot, the children follow it
+            nodes.push(FlatNode::Selector(Vec::new()));
+            let child_indices = children.iter().map(|child| flatten(child, nodes)).collect();
+            nodes[index] = if matches!(node, BehaviorNode::Selector(_)) {
+                FlatNode::Selector(child_indices)
+            } else {
+                FlatNode::Sequence(child_indices)
+            };
+        }
+        leaf => nodes.push(FlatNode::Leaf(leaf.clone())),
+    }
+    index
+}
+
+/// Running state of an enemy behaviour tree
+///
+/// GGRS CRITICAL: Must be registered with `.rollback_component_with_clone::<BehaviorTreeState>()`
+#[derive(Component, Clone, Debug, Default, Serialize, Deserialize)]
+pub struct BehaviorTreeState {
+    /// Frame each node started running, indexed like the `BehaviorTree` nodes
+    pub started_frames: Vec<Option<u32>>,
+    pub action: BehaviorAction,
+}
+
+impl BehaviorTreeState {
+    pub fn new(tree: &BehaviorTree) -> Self {
+        Self {
+            started_frames: vec![None; tree.len()],
+            action: BehaviorAction::Idle,
+        }
+    }
+}
+
+/// What the nodes can look at during a tick
+struct TickContext<'a> {
+    frame: u32,
+    enemy_pos: fixed_math::FixedVec2,
+    target_pos: Option<fixed_math::FixedVec2>,
+    health_fraction: fixed_math::Fixed,
+    attack_range: fixed_math::Fixed,
+    /// Closest breakable obstacle in attack range
+    obstacle: Option<(GgrsNetId, fixed_math::FixedVec2)>,
+    flow_field_cache: &'a FlowFieldCache,
+}
+
+impl TickContext<'_> {
+    fn check(&self, condition: &BehaviorCondition) -> bool {
+        let target_distance = self
+            .target_pos
+            .map(|target_pos| self.enemy_pos.distance(&target_pos));
+        match condition {
+            BehaviorCondition::HealthBelow(fraction) => self.health_fraction < *fraction,
+            BehaviorCondition::HealthAbove(fraction) => self.health_fraction > *fraction,
+            BehaviorCondition::HasTarget => self.target_pos.is_some(),
+            BehaviorCondition::TargetWithin(distance) => {
+                target_distance.is_some_and(|target_distance| target_distance <= *distance)
+            }
+            BehaviorCondition::TargetBeyond(distance) => {
+                target_distance.is_some_and(|target_distance| target_distance > *distance)
+            }
+            BehaviorCondition::LineOfSight => self.target_pos.is_some_and(|target_pos| {
+                self.flow_field_cache
+                    .has_line_of_sight(self.enemy_pos, target_pos)
+            }),
+            BehaviorCondition::Not(condition) => !self.check(condition),
+        }
+    }
+}
+
+/// Output of a tick besides the state
+#[derive(Default)]
+struct TickOutput {
+    action: BehaviorAction,
+    breach: Option<(GgrsNetId, fixed_math::FixedVec2)>,
+    /// Nodes ticked this frame, the others forget when they started
+    ticked: Vec<bool>,
+}
+
+fn tick(
+    nodes: &[FlatNode],
+    index: usize,
+    ctx: &TickContext,
+    state: &mut BehaviorTreeState,
+    output: &mut TickOutput,
+) -> Status {
+    output.ticked[index] = true;
+
+    let status = match &nodes[index] {
+        FlatNode::Selector(children) => children
+            .iter()
+            .map(|child| tick(nodes, *child, ctx, state, output))
+            .find(|status| *status != Status::Failure)
+            .unwrap_or(Status::Failure),
+        FlatNode::Sequence(children) => children
+            .iter()
+            .map(|child| tick(nodes, *child, ctx, state, output))
+            .find(|status| *status != Status::Success)
+            .unwrap_or(Status::Success),
+        FlatNode::Leaf(node) => tick_leaf(node, index, ctx, state, output),
+    };
+
+    if status != Status::Running {
+        state.started_frames[index] = None;
+    }
+    status
+}
+
+fn tick_leaf(
+    node: &BehaviorNode,
+    index: usize,
+    ctx: &TickContext,
+    state: &mut BehaviorTreeState,
+    output: &mut TickOutput,
+) -> Status {
+    let target_distance = ctx
+        .target_pos
+        .map(|target_pos| ctx.enemy_pos.distance(&target_pos));
+
+    match node {
+        BehaviorNode::Condition(condition) => {
+            if ctx.check(condition) {
+                Status::Success
+            } else {
+                Status::Failure
+            }
+        }
+        BehaviorNode::MoveTo { within } => match target_distance {
+            None => Status::Failure,
+            Some(distance) if distance <= *within => Status::Success,
+            Some(_) => {
+                output.action = BehaviorAction::MoveTo;
+                Status::Running
+            }
+        },
+        BehaviorNode::Attack => match target_distance {
+            Some(distance) if distance < ctx.attack_range => {
+                output.action = BehaviorAction::Attack;
+                Status::Running
+            }
+            _ => Status::Failure,
+        },
+        BehaviorNode::Flee {
+            distance: flee_distance,
+        } => match target_distance {
+            Some(distance) if distance < *flee_distance => {
+                output.action = BehaviorAction::Flee;
+                Status::Running
+            }
+            _ => Status::Success,
+        },
+        BehaviorNode::BreachObstacle => match ctx.obstacle {
+            Some(obstacle) => {
+                output.action = BehaviorAction::BreachObstacle;
+                output.breach = Some(obstacle);
+                Status::Running
+            }
+            None => Status::Failure,
+        },
+        BehaviorNode::WaitFrames(frames) => {
+            let started = *state.started_frames[index].get_or_insert(ctx.frame);
+            if ctx.frame >= started + frames {
+                Status::Success
+            } else {
+                output.action = BehaviorAction::Idle;
+                Status::Running
+            }
+        }
+        // Composites are flattened into `FlatNode::Selector` / `FlatNode::Sequence`
+        BehaviorNode::Selector(_) | BehaviorNode::Sequence(_) => Status::Failure,
+    }
+}
+
+/// Position the tree moves to and attacks, lures and investigated spots are
+/// targets too, only obstacles are not
+fn target_position(target: &EnemyTarget) -> Option<fixed_math::FixedVec2> {
+    match target.target_type {
+        TargetType::Player | TargetType::Lure | TargetType::Investigate => {
+            target.last_known_position
+        }
+        TargetType::Obstacle | TargetType::None => None,
+    }
+}
+
+/// Tick the tree from its root and store the action, returns the obstacle to breach
+fn tick_tree(
+    tree: &BehaviorTree,
+    ctx: &TickContext,
+    state: &mut BehaviorTreeState,
+) -> Option<(GgrsNetId, fixed_math::FixedVec2)> {
+    let mut output = TickOutput {
+        ticked: vec![false; tree.len()],
+        ..default()
+    };
+    tick(&tree.nodes, 0, ctx, state, &mut output);
+
+    for (started, ticked) in state.started_frames.iter_mut().zip(output.ticked) {
+        if !ticked {
+            *started = None;
+        }
+    }
+    state.action = output.action;
+    output.breach
+}
+
+/// System to tick the behaviour tree of every enemy that has one
+///
+/// Runs after the target selection (and the lures), before the movement.
+/// `BreachObstacle` retargets the enemy to the obstacle so
+/// `enemy_attack_system` hits it.
+pub fn enemy_behavior_tree_system(
+    frame: Res<FrameCount>,
+    mut enemy_query: Query<
+        (
+            &GgrsNetId,
+            &fixed_math::FixedTransform3D,
+            &EnemyAiConfig,
+            &Health,
+            &BehaviorTree,
+            &mut BehaviorTreeState,
+            &mut EnemyTarget,
+            Option<&EnemyLod>,
+        ),
+        With<Enemy>,
+    >,
+    obstacle_query: Query<
+        (&GgrsNetId, &fixed_math::FixedTransform3D, &Obstacle),
+        (With<Rollback>, Without<Enemy>),
+    >,
+    flow_field_cache: Res<FlowFieldCache>,
+) {
+    let obstacles: Vec<(GgrsNetId, fixed_math::FixedVec2)> = order_iter!(obstacle_query)
+        .into_iter()
+        .filter(|(_, _, obstacle)| obstacle.is_breakable() && obstacle.is_intact())
+        .map(|(net_id, transform, _)| (net_id.clone(), transform.translation.truncate()))
+        .collect();
+
+    for (_net_id, transform, ai_config, health, tree, mut state, mut target, lod) in
+        order_mut_iter!(enemy_query)
+    {
+        // Far enemies keep their action until their next LOD frame
+        if tree.is_empty() || EnemyLod::skips(lod) {
+            continue;
+        }
+        if state.started_frames.len() != tree.len() {
+            *state = BehaviorTreeState::new(tree);
+        }
+
+        let enemy_pos = transform.translation.truncate();
+        let obstacle = obstacles
+            .iter()
+            .map(|(net_id, pos)| (enemy_pos.distance(pos), net_id, *pos))
+            .filter(|(distance, _, _)| *distance < ai_config.attack_range)
+            // Ties keep the first obstacle in net id order
+            .min_by(|(distance_a, _, _), (distance_b, _, _)| distance_a.cmp(distance_b))
+            .map(|(_, net_id, pos)| (net_id.clone(), pos));
+
+        let health_fraction = if health.max > fixed_math::FIXED_ZERO {
+            health.current / health.max
+        } else {
+            fixed_math::FIXED_ZERO
+        };
+
+        let ctx = TickContext {
+            frame: frame.frame,
+            enemy_pos,
+            target_pos: target_position(&target),
+            health_fraction,
+            attack_range: ai_config.attack_range,
+            obstacle,
+            flow_field_cache: &flow_field_cache,
+        };
+
+        let breach = tick_tree(tree, &ctx, &mut state);
+
+        if let Some((obstacle_net_id, obstacle_pos)) = breach {
+            target.target = Some(obstacle_net_id);
+            target.target_type = TargetType::Obstacle;
+            target.last_known_position = Some(obstacle_pos);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ctx(
+        flow_field_cache: &FlowFieldCache,
+        frame: u32,
+        target_x: Option<f32>,
+        health_fraction: f32,
+    ) -> TickContext<'_> {
+        TickContext {
+            frame,
+            enemy_pos: fixed_math::FixedVec2::ZERO,
+            target_pos: target_x
+                .map(|x| fixed_math::FixedVec2::new(fixed_math::new(x), fixed_math::FIXED_ZERO)),
+            health_fraction: fixed_math::new(health_fraction),
+            attack_range: fixed_math::new(20.0),
+            obstacle: None,
+            flow_field_cache,
+        }
+    }
+
+    #[test]
+    fn test_selector_picks_first_non_failing_child() {
+        let tree = BehaviorTree::new(&BehaviorNode::Selector(vec![
+            BehaviorNode::Sequence(vec![
+                BehaviorNode::Condition(BehaviorCondition::HealthBelow(fixed_math::new(0.5))),
+                BehaviorNode::Flee {
+                    distance: fixed_math::new(100.0),
+                },
+            ]),
+            BehaviorNode::MoveTo {
+                within: fixed_math::new(10.0),
+            },
+        ]));
+        let cache = FlowFieldCache::new();
+        let mut state = BehaviorTreeState::new(&tree);
+
+        // Healthy: the flee branch fails on its condition
+        tick_tree(&tree, &ctx(&cache, 0, Some(50.0), 1.0), &mut state);
+        assert_eq!(state.action, BehaviorAction::MoveTo);
+
+        tick_tree(&tree, &ctx(&cache, 1, Some(50.0), 0.25), &mut state);
+        assert_eq!(state.action, BehaviorAction::Flee);
+
+        // Far enough, the flee branch succeeds and the move is never ticked
+        tick_tree(&tree, &ctx(&cache, 2, Some(200.0), 0.25), &mut state);
+        assert_eq!(state.action, BehaviorAction::Idle);
+
+        // No target, every branch fails
+        tick_tree(&tree, &ctx(&cache, 3, None, 1.0), &mut state);
+        assert_eq!(state.action, BehaviorAction::Idle);
+    }
+
+    #[test]
+    fn test_sequence_stops_at_first_non_success() {
+        // 0: Sequence, 1: MoveTo, 2: WaitFrames
+        let tree = BehaviorTree::new(&BehaviorNode::Sequence(vec![
+            BehaviorNode::MoveTo {
+                within: fixed_math::new(10.0),
+            },
+            BehaviorNode::WaitFrames(5),
+        ]));
+        let cache = FlowFieldCache::new();
+        let mut state = BehaviorTreeState::new(&tree);
+
+        // Still walking, the wait doesn't start
+        tick_tree(&tree, &ctx(&cache, 0, Some(50.0), 1.0), &mut state);
+        assert_eq!(state.action, BehaviorAction::MoveTo);
+        assert_eq!(state.started_frames[2], None);
+
+        // Arrived, the wait runs
+        tick_tree(&tree, &ctx(&cache, 1, Some(5.0), 1.0), &mut state);
+        assert_eq!(state.action, BehaviorAction::Idle);
+        assert_eq!(state.started_frames[2], Some(1));
+
+        // No target, the move fails before the wait
+        tick_tree(&tree, &ctx(&cache, 2, None, 1.0), &mut state);
+        assert_eq!(state.started_frames[2], None);
+    }
+
+    #[test]
+    fn test_lure_and_investigate_targets_are_followed() {
+        let tree = BehaviorTree::new(&BehaviorNode::Selector(vec![
+            BehaviorNode::Sequence(vec![
+                BehaviorNode::Condition(BehaviorCondition::TargetWithin(fixed_math::new(10.0))),
+                BehaviorNode::Attack,
+            ]),
+            BehaviorNode::MoveTo {
+                within: fixed_math::new(10.0),
+            },
+        ]));
+        let cache = FlowFieldCache::new();
+        let spot = fixed_math::FixedVec2::new(fixed_math::new(50.0), fixed_math::FIXED_ZERO);
+
+        let lure = EnemyTarget {
+            target: Some(GgrsNetId(1, "lure".to_string())),
+            target_type: TargetType::Lure,
+            last_known_position: Some(spot),
+        };
+        let mut investigate = EnemyTarget::default();
+        investigate.investigate(spot);
+
+        for target in [lure, investigate] {
+            // The player the enemy was chasing is elsewhere, the tree walks to the spot
+            assert_eq!(target_position(&target), Some(spot));
+
+            let mut state = BehaviorTreeState::new(&tree);
+            let walking = TickContext {
+                target_pos: target_position(&target),
+                ..ctx(&cache, 0, None, 1.0)
+            };
+            tick_tree(&tree, &walking, &mut state);
+            assert_eq!(state.action, BehaviorAction::MoveTo);
+
+            // Standing on the spot, nothing left to walk to
+            let arrived = TickContext {
+                enemy_pos: spot,
+                target_pos: target_position(&target),
+                ..ctx(&cache, 1, None, 1.0)
+            };
+            tick_tree(&tree, &arrived, &mut state);
+            assert_ne!(state.action, BehaviorAction::MoveTo);
+        }
+
+        // Obstacles are breached, not walked to
+        let obstacle = EnemyTarget {
+            target: Some(GgrsNetId(2, "window".to_string())),
+            target_type: TargetType::Obstacle,
+            last_known_position: Some(spot),
+        };
+        assert_eq!(target_position(&obstacle), None);
+    }
+
+    #[test]
+    fn test_wait_frames_resets() {
+        // 0: Sequence, 1: Condition, 2: WaitFrames, 3: Attack
+        let tree = BehaviorTree::new(&BehaviorNode::Sequence(vec![
+            BehaviorNode::Condition(BehaviorCondition::TargetWithin(fixed_math::new(10.0))),
+            BehaviorNode::WaitFrames(3),
+            BehaviorNode::Attack,
+        ]));
+        let cache = FlowFieldCache::new();
+        let mut state = BehaviorTreeState::new(&tree);
+
+        for frame in 0..3 {
+            tick_tree(&tree, &ctx(&cache, frame, Some(5.0), 1.0), &mut state);
+            assert_eq!(state.action, BehaviorAction::Idle);
+            assert_eq!(state.started_frames[2], Some(0));
+        }
+
+        // Done waiting, the sequence moves on and the wait forgets its start
+        tick_tree(&tree, &ctx(&cache, 3, Some(5.0), 1.0), &mut state);
+        assert_eq!(state.action, BehaviorAction::Attack);
+        assert_eq!(state.started_frames[2], None);
+
+        // Ticked again after succeeding, it waits from scratch
+        tick_tree(&tree, &ctx(&cache, 4, Some(5.0), 1.0), &mut state);
+        assert_eq!(state.action, BehaviorAction::Idle);
+        assert_eq!(state.started_frames[2], Some(4));
+
+        // Not ticked while the condition fails, it restarts when ticked again
+        tick_tree(&tree, &ctx(&cache, 5, Some(50.0), 1.0), &mut state);
+        assert_eq!(state.started_frames[2], None);
+        tick_tree(&tree, &ctx(&cache, 6, Some(5.0), 1.0), &mut state);
+        tick_tree(&tree, &ctx(&cache, 8, Some(5.0), 1.0), &mut state);
+        assert_eq!(state.action, BehaviorAction::Idle);
+        assert_eq!(state.started_frames[2], Some(6));
+    }
+}
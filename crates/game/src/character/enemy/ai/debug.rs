@@ -4,10 +4,12 @@
 //! Toggle with F3 key (configurable).
 
 use bevy::prelude::*;
+use utils::frame::FrameCount;
 
-use super::navigation::{FlowFieldCache, NavProfile, GRID_CELL_SIZE};
+use super::navigation::{FlowFieldCache, GridPos, NavProfile, GRID_CELL_SIZE};
 use super::state::{EnemyAiConfig, EnemyTarget, MonsterState};
 use crate::character::enemy::Enemy;
+use crate::character::status_effect::StatusEffects;
 
 /// Resource to control flow field debug visualization
 #[derive(Resource, Default)]
@@ -97,27 +99,28 @@ pub fn draw_flow_field_debug(
     let cell_size = GRID_CELL_SIZE as f32;
     let half_cell = cell_size / 2.0;
 
-    // Only render cells within render_radius of the target (player)
-    let target = &cache.target_pos;
+    // Only render cells within render_radius of a target (player)
     let render_radius = ff_debug.render_radius;
+    let near_target = |pos: &GridPos| {
+        cache.target_positions.values().any(|target| {
+            (pos.x - target.x).abs() <= render_radius && (pos.y - target.y).abs() <= render_radius
+        })
+    };
 
     // Get max cost for color gradient (only within visible range)
     let max_cost = flow_field
-        .costs
         .iter()
-        .filter(|(pos, _)| {
-            (pos.x - target.x).abs() <= render_radius && (pos.y - target.y).abs() <= render_radius
-        })
-        .map(|(_, cost)| *cost)
+        .filter(|(pos, _, _)| near_target(pos))
+        .map(|(_, _, cost)| cost)
         .max()
         .unwrap_or(1)
         .max(1) as f32;
 
     let mut cell_count = 0;
 
-    for (pos, next_pos) in flow_field.directions.iter() {
+    for (pos, next_pos, cost) in flow_field.iter() {
         // Skip cells outside render radius
-        if (pos.x - target.x).abs() > render_radius || (pos.y - target.y).abs() > render_radius {
+        if !near_target(&pos) {
             continue;
         }
 
@@ -131,8 +134,7 @@ pub fn draw_flow_field_debug(
         let center = Vec2::new(world_x, world_y);
 
         // Get cost for color
-        let cost = flow_field.costs.get(pos).copied().unwrap_or(0) as f32;
-        let t = (cost / max_cost).clamp(0.0, 1.0);
+        let t = (cost as f32 / max_cost).clamp(0.0, 1.0);
 
         // Color gradient: green (close) -> yellow -> red (far)
         let color = if t < 0.5 {
@@ -173,7 +175,7 @@ pub fn draw_flow_field_debug(
     // Draw blocked cells (only within render radius)
     for pos in cache.wall_cells.iter() {
         // Skip cells outside render radius
-        if (pos.x - target.x).abs() > render_radius || (pos.y - target.y).abs() > render_radius {
+        if !near_target(pos) {
             continue;
         }
 
@@ -205,14 +207,16 @@ pub fn draw_flow_field_debug(
         );
     }
 
-    // Draw target
-    let target_x = cache.target_pos.x as f32 * cell_size + half_cell;
-    let target_y = cache.target_pos.y as f32 * cell_size + half_cell;
-    gizmos.circle_2d(
-        Isometry2d::from_translation(Vec2::new(target_x, target_y)),
-        cell_size * 0.6,
-        Color::srgb(0.0, 1.0, 1.0),
-    );
+    // Draw targets
+    for target in cache.target_positions.values() {
+        let target_x = target.x as f32 * cell_size + half_cell;
+        let target_y = target.y as f32 * cell_size + half_cell;
+        gizmos.circle_2d(
+            Isometry2d::from_translation(Vec2::new(target_x, target_y)),
+            cell_size * 0.6,
+            Color::srgb(0.0, 1.0, 1.0),
+        );
+    }
 }
 
 /// Resource for enemy state debug visualization
@@ -260,16 +264,18 @@ pub fn draw_enemy_state_debug(
             &EnemyAiConfig,
             &EnemyTarget,
             &MonsterState,
+            Option<&StatusEffects>,
         ),
         With<Enemy>,
     >,
     mut gizmos: Gizmos,
+    frame: Res<FrameCount>,
 ) {
     if !state_debug.enabled {
         return;
     }
 
-    for (transform, ai_config, target, state) in enemy_query.iter() {
+    for (transform, ai_config, target, state, status_effects) in enemy_query.iter() {
         let pos = Vec2::new(
             transform.translation.x.to_num::<f32>(),
             transform.translation.y.to_num::<f32>(),
@@ -304,6 +310,8 @@ pub fn draw_enemy_state_debug(
                 let color = match target.target_type {
                     super::state::TargetType::Player => Color::srgb(1.0, 0.0, 0.0),
                     super::state::TargetType::Obstacle => Color::srgb(1.0, 0.5, 0.0),
+                    super::state::TargetType::Lure => Color::srgb(1.0, 0.0, 1.0),
+                    super::state::TargetType::Investigate => Color::srgb(0.0, 1.0, 1.0),
                     super::state::TargetType::None => Color::srgb(0.5, 0.5, 0.5),
                 };
 
@@ -314,12 +322,15 @@ pub fn draw_enemy_state_debug(
         // State indicator above enemy
         if state_debug.show_state {
             let indicator_pos = pos + Vec2::new(0.0, 30.0);
+            let stunned = status_effects.is_some_and(|status| status.is_stunned(frame.frame));
             let (color, radius) = match state {
+                _ if stunned => (Color::srgb(0.0, 0.0, 1.0), 7.0),
                 MonsterState::Idle => (Color::srgb(0.5, 0.5, 0.5), 5.0),
                 MonsterState::Chasing => (Color::srgb(1.0, 1.0, 0.0), 6.0),
                 MonsterState::Attacking { .. } => (Color::srgb(1.0, 0.0, 0.0), 8.0),
-                MonsterState::Stunned { .. } => (Color::srgb(0.0, 0.0, 1.0), 7.0),
                 MonsterState::Breaching { .. } => (Color::srgb(1.0, 0.5, 0.0), 7.0),
+                MonsterState::Investigating { .. } => (Color::srgb(0.0, 1.0, 0.5), 6.0),
+                MonsterState::Vaulting { .. } => (Color::srgb(1.0, 0.0, 1.0), 7.0),
                 MonsterState::Fleeing => (Color::srgb(0.0, 1.0, 1.0), 6.0),
                 MonsterState::Dead => (Color::srgb(0.0, 0.0, 0.0), 5.0),
             };
@@ -0,0 +1,150 @@
+//! AI Level of Detail
+//!
+//! Enemies far from every player, or in a locked room away from them, don't
+//! need the full AI every frame:
+//!
+//! - **Full**: target selection, behaviour tree and movement with collisions
+//!   and steering every frame
+//! - **Reduced**: the same once every `update_interval` frames, staggered by
+//!   net id so the work is spread over the frames. In between, the enemy
+//!   keeps its velocity, only checks the cell it moves into and still
+//!   hears the noises (they only last one frame)
+//!
+//! Attacks are not reduced, a reduced enemy is too far to hit anyone.
+//!
+//! GGRS CRITICAL: the LOD only depends on rollback state (positions, unlocked
+//! rooms, frame, net ids), `EnemyLod` is rolled back so a re-simulation picks
+//! the same enemies on the same frames.
+
+use bevy::prelude::*;
+use bevy_fixed::fixed_math;
+use serde::{Deserialize, Serialize};
+use utils::{frame::FrameCount, net_id::GgrsNetId, order_mut_iter};
+
+use crate::character::enemy::Enemy;
+use crate::character::movement::Velocity;
+use crate::character::player::{input::FIXED_TIMESTEP, Player};
+use crate::room::UnlockedRooms;
+
+use super::navigation::{FlowFieldCache, GridPos, NavProfile};
+use super::room_graph::RoomGraph;
+
+/// Distances and rate of the reduced AI
+///
+/// Note: AiLodConfig is not rolled back (static configuration)
+#[derive(Resource, Clone, Debug)]
+pub struct AiLodConfig {
+    /// Enemies closer to a player keep the full AI
+    pub full_detail_distance: fixed_math::Fixed,
+    /// Same for enemies in a room not unlocked yet
+    pub locked_room_full_detail_distance: fixed_math::Fixed,
+    /// Frames between two AI updates of a reduced enemy
+    pub update_interval: u32,
+}
+
+impl Default for AiLodConfig {
+    fn default() -> Self {
+        Self {
+            full_detail_distance: fixed_math::new(640.0),
+            locked_room_full_detail_distance: fixed_math::new(200.0),
+            update_interval: 4,
+        }
+    }
+}
+
+/// Level of detail of an enemy AI, updated at the start of `RollbackSystemSet::EnemyAI`
+///
+/// GGRS CRITICAL: Must be registered with `.rollback_component_with_clone::<EnemyLod>()`
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EnemyLod {
+    pub reduced: bool,
+    /// Whether the AI runs this frame, always for full enemies
+    pub update_this_frame: bool,
+}
+
+impl Default for EnemyLod {
+    fn default() -> Self {
+        Self {
+            reduced: false,
+            update_this_frame: true,
+        }
+    }
+}
+
+impl EnemyLod {
+    /// Whether an enemy skips its AI this frame (no LOD means full AI)
+    pub fn skips(lod: Option<&EnemyLod>) -> bool {
+        lod.is_some_and(|lod| !lod.update_this_frame)
+    }
+}
+
+/// Move a reduced enemy with its current velocity, without the collisions
+///
+/// Stays in place rather than entering a wall or an intact window cell.
+pub fn integrate_reduced(
+    transform: &mut fixed_math::FixedTransform3D,
+    velocity: &Velocity,
+    flow_field_cache: &FlowFieldCache,
+) {
+    let step = (velocity.main + velocity.knockback) * fixed_math::new(FIXED_TIMESTEP);
+    if step == fixed_math::FixedVec2::ZERO {
+        return;
+    }
+
+    let next = transform.translation.truncate() + step;
+    if flow_field_cache.is_blocked(&GridPos::from_fixed(next), NavProfile::Ground) {
+        return;
+    }
+    transform.translation.x = next.x;
+    transform.translation.y = next.y;
+}
+
+/// System to pick the level of detail of every enemy
+pub fn update_enemy_lod_system(
+    frame: Res<FrameCount>,
+    config: Res<AiLodConfig>,
+    unlocked_rooms: Res<UnlockedRooms>,
+    room_graph: Res<RoomGraph>,
+    player_query: Query<&fixed_math::FixedTransform3D, (With<Player>, Without<Enemy>)>,
+    mut enemy_query: Query<(&GgrsNetId, &fixed_math::FixedTransform3D, &mut EnemyLod), With<Enemy>>,
+) {
+    let players: Vec<fixed_math::FixedVec2> = player_query
+        .iter()
+        .map(|transform| transform.translation.truncate())
+        .collect();
+    let interval = config.update_interval.max(1);
+
+    for (net_id, transform, mut lod) in order_mut_iter!(enemy_query) {
+        let enemy_pos = transform.translation.truncate();
+
+        // The minimum doesn't depend on the player order
+        let nearest_player_distance = players
+            .iter()
+            .map(|player_pos| enemy_pos.distance(player_pos))
+            .min()
+            .unwrap_or(fixed_math::Fixed::MAX);
+
+        let in_locked_room = room_graph
+            .room_at(GridPos::from_fixed(enemy_pos))
+            .is_some_and(|room| !unlocked_rooms.is_unlocked(&room_graph.rooms[room].level_id));
+
+        let full_detail_distance = if in_locked_room {
+            config.locked_room_full_detail_distance
+        } else {
+            config.full_detail_distance
+        };
+        let reduced = nearest_player_distance > full_detail_distance;
+
+        // Staggered by net id, a few reduced enemies update on every frame
+        let update_this_frame =
+            !reduced || frame.frame.wrapping_add(net_id.0 as u32) % interval == 0;
+
+        let new_lod = EnemyLod {
+            reduced,
+            update_this_frame,
+        };
+        if *lod != new_lod {
+            *lod = new_lod;
+        }
+    }
+}
@@ -7,29 +7,45 @@ pub mod pathing;
 
 // New generic AI system
 pub mod behavior;
+pub mod behavior_tree;
 pub mod debug;
+pub mod lod;
 pub mod navigation;
 pub mod obstacle;
+pub mod perception;
+pub mod room_graph;
 pub mod state;
+pub mod steering;
+pub mod vault;
 
 // Re-exports for convenience
 pub use behavior::{
-    enemy_attack_system, enemy_movement_system, enemy_target_selection, enemy_stun_recovery_system,
+    apply_stun, enemy_attack_system, enemy_movement_system, enemy_ranged_attack_system,
+    enemy_target_selection,
+};
+pub use behavior_tree::{
+    enemy_behavior_tree_system, BehaviorAction, BehaviorCondition, BehaviorNode, BehaviorTree,
+    BehaviorTreeState,
 };
 pub use debug::{
     draw_enemy_state_debug, draw_flow_field_debug, toggle_enemy_state_debug,
     toggle_flow_field_debug, EnemyStateDebug, FlowFieldDebug,
 };
+pub use lod::{update_enemy_lod_system, AiLodConfig, EnemyLod};
 pub use navigation::{
-    update_flow_field_system, FlowField, FlowFieldCache, FlowFieldConfig, GridPos, NavProfile,
-    GRID_CELL_SIZE,
+    update_flow_field, update_flow_field_system, FlowField, FlowFieldCache, FlowFieldConfig,
+    GridBounds, GridPos, NavProfile, GRID_CELL_SIZE,
 };
 pub use obstacle::{
     process_obstacle_damage, Obstacle, ObstacleAttackEvent, ObstacleConfig, ObstacleDestroyedEvent,
     ObstacleType,
 };
 pub use pathing::WallSlideTracker;
+pub use perception::{Noise, NoiseConfig, NoiseEvents};
+pub use room_graph::{update_room_graph_system, RoomGraph};
 pub use state::{
     AttackTarget, EnemyAiConfig, EnemyAiConfigRon, EnemyTarget, MonsterState, MovementType,
-    TargetType,
+    PerceptionConfig, RangedAttackConfig, RangedAttackState, TargetType,
 };
+pub use steering::{SpatialGrid, SteeringConfig, SteeringForces};
+pub use vault::{enemy_window_vault_system, VaultConfig, WindowVault};
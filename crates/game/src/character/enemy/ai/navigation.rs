@@ -4,6 +4,15 @@
 //! Uses a lightweight BFS (Breadth-First Search) with limited radius for performance.
 //! O(1) direction lookups per enemy after computation.
 //!
+//! Fields are dense arrays over the cells around their targets (clipped to the
+//! level bounds). Between full rebuilds they are updated in place: cells whose
+//! obstacle changed are repaired, and targets that moved a few cells only
+//! recompute a window around them.
+//!
+//! The shared field is seeded from every living player at once (multi-source
+//! BFS, every cell leads to its nearest player). With `per_player_fields`, one
+//! field per player is also built so an enemy walks toward its own `EnemyTarget`.
+//!
 //! IMPORTANT: Uses BTreeMap/BTreeSet for deterministic iteration order (GGRS rollback).
 
 use bevy::prelude::*;
@@ -11,10 +20,11 @@ use bevy_fixed::fixed_math;
 use bevy_ggrs::Rollback;
 use serde::{Deserialize, Serialize};
 use std::collections::{BTreeMap, BTreeSet, VecDeque};
-use utils::{frame::FrameCount, net_id::GgrsNetId};
+use utils::{frame::FrameCount, net_id::{GgrsNetId, StableIdType}};
 
-use crate::character::player::Player;
-use crate::collider::{Collider, ColliderShape, Wall};
+use crate::character::health::Death;
+use crate::character::player::{downed::Downed, Player};
+use crate::collider::{Collider, ColliderShape};
 
 use super::obstacle::{Obstacle, ObstacleType};
 
@@ -80,6 +90,108 @@ impl GridPos {
     pub fn manhattan_distance(&self, other: &GridPos) -> i32 {
         (self.x - other.x).abs() + (self.y - other.y).abs()
     }
+
+    /// Chebyshev distance (8-directional moves) to another grid position
+    pub fn chebyshev_distance(&self, other: &GridPos) -> i32 {
+        (self.x - other.x).abs().max((self.y - other.y).abs())
+    }
+}
+
+/// Rectangle of grid cells (inclusive), indexes the dense flow field arrays
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub struct GridBounds {
+    pub min: GridPos,
+    pub max: GridPos,
+}
+
+impl GridBounds {
+    pub fn new(min: GridPos, max: GridPos) -> Self {
+        Self { min, max }
+    }
+
+    /// Square of cells within `radius` of `center`
+    pub fn around(center: GridPos, radius: i32) -> Self {
+        Self {
+            min: GridPos::new(center.x - radius, center.y - radius),
+            max: GridPos::new(center.x + radius, center.y + radius),
+        }
+    }
+
+    /// Cells covered by an LDtk level
+    pub fn from_level(info: &LevelGridInfo) -> Self {
+        let last_x = (info.width_tiles - 1).max(0) as usize;
+        let last_y = (info.height_tiles - 1).max(0) as usize;
+        // LDtk Y is flipped, the last row is the lowest one
+        let bottom_left = ldtk_grid_to_flowfield(0, last_y, info);
+        let top_right = ldtk_grid_to_flowfield(last_x, 0, info);
+        Self {
+            min: GridPos::new(bottom_left.x.min(top_right.x), bottom_left.y.min(top_right.y)),
+            max: GridPos::new(bottom_left.x.max(top_right.x), bottom_left.y.max(top_right.y)),
+        }
+    }
+
+    pub fn union(&self, other: &GridBounds) -> Self {
+        Self {
+            min: GridPos::new(self.min.x.min(other.min.x), self.min.y.min(other.min.y)),
+            max: GridPos::new(self.max.x.max(other.max.x), self.max.y.max(other.max.y)),
+        }
+    }
+
+    /// Grown by `margin` cells on every side
+    pub fn expand(&self, margin: i32) -> Self {
+        Self {
+            min: GridPos::new(self.min.x - margin, self.min.y - margin),
+            max: GridPos::new(self.max.x + margin, self.max.y + margin),
+        }
+    }
+
+    pub fn intersection(&self, other: &GridBounds) -> Option<Self> {
+        let min = GridPos::new(self.min.x.max(other.min.x), self.min.y.max(other.min.y));
+        let max = GridPos::new(self.max.x.min(other.max.x), self.max.y.min(other.max.y));
+        (min.x <= max.x && min.y <= max.y).then_some(Self { min, max })
+    }
+
+    pub fn width(&self) -> i32 {
+        self.max.x - self.min.x + 1
+    }
+
+    pub fn height(&self) -> i32 {
+        self.max.y - self.min.y + 1
+    }
+
+    /// Number of cells
+    pub fn len(&self) -> usize {
+        (self.width().max(0) * self.height().max(0)) as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn contains(&self, pos: GridPos) -> bool {
+        pos.x >= self.min.x && pos.x <= self.max.x && pos.y >= self.min.y && pos.y <= self.max.y
+    }
+
+    /// Index of a cell in the dense arrays (row major)
+    pub fn index(&self, pos: GridPos) -> Option<usize> {
+        self.contains(pos)
+            .then(|| ((pos.y - self.min.y) * self.width() + (pos.x - self.min.x)) as usize)
+    }
+
+    /// Cell at an index of the dense arrays
+    pub fn pos(&self, index: usize) -> GridPos {
+        let width = self.width() as usize;
+        GridPos::new(
+            self.min.x + (index % width) as i32,
+            self.min.y + (index / width) as i32,
+        )
+    }
+
+    /// Every cell, in index order
+    pub fn cells(&self) -> impl Iterator<Item = GridPos> + '_ {
+        (self.min.y..=self.max.y)
+            .flat_map(move |y| (self.min.x..=self.max.x).map(move |x| GridPos::new(x, y)))
+    }
 }
 
 /// Navigation profile determines which obstacles block an enemy
@@ -108,20 +220,374 @@ impl NavProfile {
     }
 }
 
+/// Cost of the cells the flow field does not reach
+const UNREACHED: u32 = u32::MAX;
+
 /// A single flow field for a specific navigation profile
-/// Uses BTreeMap for deterministic iteration (GGRS rollback compatibility)
+/// Dense arrays over `bounds`, indexed with `GridBounds::index` (deterministic
+/// for GGRS rollback, and cheap to clone in the rollback snapshots)
 #[derive(Clone, Debug, Default)]
 pub struct FlowField {
-    /// For each cell, the next cell to move to (toward target)
-    pub directions: BTreeMap<GridPos, GridPos>,
-    /// Cost to reach target from each cell
-    pub costs: BTreeMap<GridPos, u32>,
+    pub profile: NavProfile,
+    /// Cells covered by the arrays
+    pub bounds: GridBounds,
+    /// Targets the field leads to
+    pub sources: Vec<GridPos>,
+    /// Targets at the last full build, `retarget` can only drift so far from them
+    built_sources: Vec<GridPos>,
+    /// Cost to reach the nearest target, `UNREACHED` when not reached
+    costs: Vec<u32>,
+    /// Index of the next cell toward the target (itself for a target)
+    next: Vec<u32>,
+    /// Index in `sources` of the target each cell leads to
+    origin: Vec<u16>,
+    /// Whether the profile can walk each cell
+    passable: Vec<bool>,
+    reached: usize,
 }
 
 impl FlowField {
+    /// Build a flow field using simple BFS (much faster than Dijkstra for unweighted graphs)
+    ///
+    /// Every source starts in the queue at cost 0 (multi-source), so each cell
+    /// leads to its nearest source. Ties go to the first source in `sources`.
+    /// The arrays only cover `max_search_radius` around the sources, clipped to the levels.
+    pub fn build(
+        sources: &[GridPos],
+        profile: NavProfile,
+        cache: &FlowFieldCache,
+        config: &FlowFieldConfig,
+    ) -> Self {
+        let bounds = sources
+            .iter()
+            .map(|source| GridBounds::around(*source, config.max_search_radius))
+            .reduce(|a, b| a.union(&b));
+        let bounds = match (bounds, cache.level_bounds) {
+            (Some(bounds), Some(level_bounds)) => bounds.intersection(&level_bounds),
+            (bounds, _) => bounds,
+        };
+
+        match bounds {
+            Some(bounds) => Self::build_within(sources, profile, cache, config, bounds),
+            None => Self {
+                profile,
+                sources: sources.to_vec(),
+                built_sources: sources.to_vec(),
+                ..Default::default()
+            },
+        }
+    }
+
+    /// Build a flow field covering only `bounds`
+    pub fn build_within(
+        sources: &[GridPos],
+        profile: NavProfile,
+        cache: &FlowFieldCache,
+        config: &FlowFieldConfig,
+        bounds: GridBounds,
+    ) -> Self {
+        let mut field = Self {
+            profile,
+            sources: sources.to_vec(),
+            built_sources: sources.to_vec(),
+            ..Default::default()
+        };
+
+        let len = bounds.len();
+        field.bounds = bounds;
+        field.costs = vec![UNREACHED; len];
+        field.next = vec![0; len];
+        field.origin = vec![0; len];
+        field.passable = cache.passable_grid(&bounds, profile);
+
+        // Start BFS from every source
+        let mut queue = VecDeque::new();
+        for (source_index, source) in sources.iter().enumerate() {
+            let Some(index) = bounds.index(*source) else {
+                continue;
+            };
+            if field.costs[index] == UNREACHED {
+                field.set(index, index, 0, source_index as u16);
+                queue.push_back(index);
+            }
+        }
+        field.propagate(queue, config, None);
+
+        field
+    }
+
+    fn set(&mut self, index: usize, next: usize, cost: u32, origin: u16) {
+        if self.costs[index] == UNREACHED {
+            self.reached += 1;
+        }
+        self.costs[index] = cost;
+        self.next[index] = next as u32;
+        self.origin[index] = origin;
+    }
+
+    fn clear(&mut self, index: usize) {
+        if self.costs[index] != UNREACHED {
+            self.reached -= 1;
+            self.costs[index] = UNREACHED;
+        }
+    }
+
+    /// Relax the neighbors of the queued cells until no cost improves
+    ///
+    /// A cell is queued again whenever its cost improves (label-correcting),
+    /// so the same loop builds, repairs and retargets the field. With `limit`,
+    /// only the cells inside one of the bounds are updated.
+    fn propagate(
+        &mut self,
+        mut queue: VecDeque<usize>,
+        config: &FlowFieldConfig,
+        limit: Option<&[GridBounds]>,
+    ) {
+        while let Some(current) = queue.pop_front() {
+            let cost = self.costs[current];
+            if cost == UNREACHED {
+                continue;
+            }
+            let current_pos = self.bounds.pos(current);
+            let origin = self.origin[current];
+            let source = self.sources[origin as usize];
+
+            // Get neighbors (4 or 8 directions, the first 4 are the orthogonal ones)
+            let neighbors_8 = current_pos.neighbors_8();
+            let neighbors: &[GridPos] = if config.use_8_directions {
+                &neighbors_8
+            } else {
+                &neighbors_8[..4]
+            };
+
+            for neighbor in neighbors {
+                let Some(index) = self.bounds.index(*neighbor) else {
+                    continue;
+                };
+                // Blocked for this profile, or already as close
+                if !self.passable[index] || cost + 1 >= self.costs[index] {
+                    continue;
+                }
+                // Check bounds (manhattan distance from the source)
+                if neighbor.manhattan_distance(&source) > config.max_search_radius {
+                    continue;
+                }
+                if limit.is_some_and(|limit| !limit.iter().any(|b| b.contains(*neighbor))) {
+                    continue;
+                }
+
+                // Direction points TOWARD target (so we store 'current' as the next step)
+                self.set(index, current, cost + 1, origin);
+                queue.push_back(index);
+            }
+        }
+    }
+
+    /// Repair the field after obstacles changed in `cells` (placed, broken,
+    /// repaired). Returns whether the field changed.
+    ///
+    /// Cells whose path crosses a newly blocked cell are cleared, then refilled
+    /// from their valid neighbors together with the newly opened cells.
+    pub fn repair(
+        &mut self,
+        cells: &BTreeSet<GridPos>,
+        cache: &FlowFieldCache,
+        config: &FlowFieldConfig,
+    ) -> bool {
+        let mut changed = Vec::new();
+        for pos in cells {
+            let Some(index) = self.bounds.index(*pos) else {
+                continue;
+            };
+            if let Some(passable) = self.passable.get_mut(index) {
+                let now_passable = !cache.is_blocked(pos, self.profile);
+                if *passable != now_passable {
+                    *passable = now_passable;
+                    changed.push(index);
+                }
+            }
+        }
+        if changed.is_empty() {
+            return false;
+        }
+
+        // The next cell always has a lower cost, so it is decided first
+        let mut order: Vec<usize> = (0..self.costs.len())
+            .filter(|index| self.costs[*index] != UNREACHED)
+            .collect();
+        order.sort_by_key(|index| (self.costs[*index], *index));
+
+        let mut invalid = vec![false; self.costs.len()];
+        for index in order {
+            // Targets stay, even when an obstacle appears on them
+            if self.costs[index] == 0 {
+                continue;
+            }
+            invalid[index] = !self.passable[index] || invalid[self.next[index] as usize];
+        }
+
+        let mut seeds: Vec<usize> = changed
+            .into_iter()
+            .filter(|index| self.passable[*index])
+            .collect();
+        for (index, is_invalid) in invalid.iter().enumerate() {
+            if *is_invalid {
+                self.clear(index);
+                seeds.push(index);
+            }
+        }
+
+        // Refill from the reached neighbors of the cleared and opened cells
+        let mut queue = VecDeque::new();
+        for index in seeds {
+            for neighbor in self.bounds.pos(index).neighbors_8() {
+                if let Some(neighbor_index) = self.bounds.index(neighbor) {
+                    if self.costs[neighbor_index] != UNREACHED {
+                        queue.push_back(neighbor_index);
+                    }
+                }
+            }
+        }
+        self.propagate(queue, config, None);
+
+        true
+    }
+
+    /// Move the targets by a few cells without a full rebuild
+    ///
+    /// Only the cells within `retarget_window` of the new targets are
+    /// recomputed. The cells further away keep their direction: it leads into
+    /// the window, then to the new targets, and their costs are updated along
+    /// it. Returns `false` when the field must be rebuilt instead.
+    pub fn retarget(&mut self, sources: &[GridPos], config: &FlowFieldConfig) -> bool {
+        if sources.len() != self.built_sources.len() || self.costs.is_empty() {
+            return false;
+        }
+
+        // Limit the drift from the last full build, costs outside the windows
+        // are still relative to the old targets
+        let windows: Vec<GridBounds> = sources
+            .iter()
+            .map(|source| GridBounds::around(*source, config.retarget_window))
+            .collect();
+        for ((source, built), (old, window)) in sources
+            .iter()
+            .zip(self.built_sources.iter())
+            .zip(self.sources.iter().zip(windows.iter()))
+        {
+            if source.chebyshev_distance(built) > config.retarget_max_cells
+                || !window.contains(*old)
+                || !self.bounds.contains(*source)
+            {
+                return false;
+            }
+        }
+
+        // Clear the windows, every cell reached before must be reached again
+        let mut previously_reached = Vec::new();
+        let mut in_window = vec![false; self.costs.len()];
+        for window in windows.iter() {
+            let Some(window) = window.intersection(&self.bounds) else {
+                continue;
+            };
+            for pos in window.cells() {
+                let index = self.bounds.index(pos).unwrap_or_default();
+                in_window[index] = true;
+                if self.costs[index] != UNREACHED {
+                    previously_reached.push(index);
+                    self.clear(index);
+                }
+            }
+        }
+
+        self.sources = sources.to_vec();
+        let mut queue = VecDeque::new();
+        for (source_index, source) in sources.iter().enumerate() {
+            let index = self.bounds.index(*source).unwrap_or_default();
+            if self.costs[index] == UNREACHED {
+                self.set(index, index, 0, source_index as u16);
+                queue.push_back(index);
+            }
+        }
+        self.propagate(queue, config, Some(windows.as_slice()));
+
+        // A cell only reachable through the outside of the windows would lead
+        // back to the old targets
+        if !previously_reached
+            .into_iter()
+            .all(|index| self.costs[index] != UNREACHED)
+        {
+            return false;
+        }
+
+        self.follow_chains(in_window)
+    }
+
+    /// Recompute the cost and target of the cells outside the windows from
+    /// the window cell their `next` chain leads to
+    ///
+    /// Their costs are still toward the old targets, `repair` and
+    /// `get_neighbor_directions` need every cell to cost more than its next
+    /// cell. Returns `false` when a chain never enters a window.
+    fn follow_chains(&mut self, mut up_to_date: Vec<bool>) -> bool {
+        let mut chain = Vec::new();
+        for start in 0..self.costs.len() {
+            if self.costs[start] == UNREACHED || up_to_date[start] {
+                continue;
+            }
+
+            let mut index = start;
+            while !up_to_date[index] {
+                if self.costs[index] == UNREACHED || chain.len() > self.costs.len() {
+                    return false;
+                }
+                chain.push(index);
+                index = self.next[index] as usize;
+            }
+
+            let (mut cost, origin) = (self.costs[index], self.origin[index]);
+            for index in chain.drain(..).rev() {
+                cost += 1;
+                self.costs[index] = cost;
+                self.origin[index] = origin;
+                up_to_date[index] = true;
+            }
+        }
+        true
+    }
+
     /// Get the direction to move from a given position
     pub fn get_direction(&self, pos: GridPos) -> Option<GridPos> {
-        self.directions.get(&pos).copied()
+        let index = self.bounds.index(pos)?;
+        let cost = *self.costs.get(index)?;
+        (cost != UNREACHED).then(|| self.bounds.pos(self.next[index] as usize))
+    }
+
+    /// Cost to reach the target from a given position
+    pub fn get_cost(&self, pos: GridPos) -> Option<u32> {
+        let cost = *self.costs.get(self.bounds.index(pos)?)?;
+        (cost != UNREACHED).then_some(cost)
+    }
+
+    /// Whether the field reaches a given position
+    pub fn is_reached(&self, pos: GridPos) -> bool {
+        self.get_cost(pos).is_some()
+    }
+
+    /// Number of cells reached
+    pub fn reachable_count(&self) -> usize {
+        self.reached
+    }
+
+    /// Reached cells in index order: (cell, next cell, cost)
+    pub fn iter(&self) -> impl Iterator<Item = (GridPos, GridPos, u32)> + '_ {
+        self.costs
+            .iter()
+            .enumerate()
+            .filter(|(_, cost)| **cost != UNREACHED)
+            .map(|(index, cost)| {
+                (self.bounds.pos(index), self.bounds.pos(self.next[index] as usize), *cost)
+            })
     }
 
     /// Get the world-space direction vector from a given position
@@ -159,7 +625,7 @@ impl FlowField {
         for neighbor in grid_pos.neighbors_8() {
             if let Some(next_pos) = self.get_direction(neighbor) {
                 // Get the cost of this neighbor's path
-                let cost = self.costs.get(&neighbor).copied().unwrap_or(u32::MAX);
+                let cost = self.get_cost(neighbor).unwrap_or(UNREACHED);
                 let neighbor_world = neighbor.to_fixed();
                 let dir = (neighbor_world - pos).normalize_or_zero();
                 if dir.length_squared() > fixed_math::FixedWide::ZERO {
@@ -181,7 +647,7 @@ impl FlowField {
 
         // First check immediate neighbors (most common case)
         for neighbor in grid_pos.neighbors_8() {
-            if self.directions.contains_key(&neighbor) {
+            if self.is_reached(neighbor) {
                 let neighbor_world = neighbor.to_fixed();
                 let dir = (neighbor_world - pos).normalize_or_zero();
                 if dir.length_squared() > fixed_math::FixedWide::ZERO {
@@ -204,7 +670,7 @@ impl FlowField {
 
                     let check_pos = GridPos::new(grid_pos.x + dx, grid_pos.y + dy);
 
-                    if let Some(&cost) = self.costs.get(&check_pos) {
+                    if let Some(cost) = self.get_cost(check_pos) {
                         // Found a covered cell - prefer the one with lowest cost (closest to target)
                         match best_cell {
                             None => best_cell = Some((check_pos, cost)),
@@ -244,14 +710,16 @@ pub struct LevelGridInfo {
 /// Uses BTreeMap/BTreeSet for deterministic iteration (GGRS rollback compatibility)
 #[derive(Resource, Default, Clone)]
 pub struct FlowFieldCache {
-    /// Target position (player) at last calculation
-    pub target_pos: GridPos,
+    /// Cell of each living player (by net id) at last calculation
+    pub target_positions: BTreeMap<StableIdType, GridPos>,
     /// Frame when last updated
     pub last_update_frame: u32,
     /// Update interval in frames
     pub update_interval: u32,
-    /// Cached flow fields per navigation profile
+    /// Cached flow fields per navigation profile, toward the nearest living player
     pub layers: BTreeMap<NavProfile, FlowField>,
+    /// GroundBreaker flow field toward each living player (by net id)
+    pub player_layers: BTreeMap<StableIdType, FlowField>,
     /// Blocked cells per obstacle type (for building flow fields)
     pub blocked_cells: BTreeMap<ObstacleType, BTreeSet<GridPos>>,
     /// All permanently blocked cells (walls) - computed from IntGrid + dynamic walls
@@ -260,6 +728,8 @@ pub struct FlowFieldCache {
     pub intgrid_wall_cells: BTreeSet<GridPos>,
     /// Level grid info for coordinate conversion
     pub level_info: Option<LevelGridInfo>,
+    /// Cells covered by all the loaded levels, the flow fields never go further
+    pub level_bounds: Option<GridBounds>,
     /// Number of wall entities at last rebuild (to detect when walls are added)
     pub last_wall_entity_count: usize,
 }
@@ -267,14 +737,16 @@ pub struct FlowFieldCache {
 impl FlowFieldCache {
     pub fn new() -> Self {
         Self {
-            target_pos: GridPos::default(),
+            target_positions: BTreeMap::new(),
             last_update_frame: 0,
             update_interval: 30, // Update every 30 frames (~2 times per second at 60 FPS)
             layers: BTreeMap::new(),
+            player_layers: BTreeMap::new(),
             blocked_cells: BTreeMap::new(),
             wall_cells: BTreeSet::new(),
             intgrid_wall_cells: BTreeSet::new(),
             level_info: None,
+            level_bounds: None,
             last_wall_entity_count: 0,
         }
     }
@@ -284,6 +756,35 @@ impl FlowFieldCache {
         self.layers.get(&profile)
     }
 
+    /// Get the flow field leading to `target`, falls back to the shared field
+    /// (nearest player) when the target has no field of its own
+    pub fn get_flow_field_to(
+        &self,
+        target: Option<&GgrsNetId>,
+        profile: NavProfile,
+    ) -> Option<&FlowField> {
+        if profile == NavProfile::GroundBreaker {
+            if let Some(flow_field) = target.and_then(|t| self.player_layers.get(&t.0)) {
+                return Some(flow_field);
+            }
+        }
+        self.get_flow_field(profile)
+    }
+
+    /// World position of the nearest living player at last calculation
+    /// Ties keep the lowest net id (BTreeMap order)
+    pub fn nearest_target(&self, pos: fixed_math::FixedVec2) -> Option<fixed_math::FixedVec2> {
+        let mut nearest: Option<(fixed_math::FixedWide, fixed_math::FixedVec2)> = None;
+        for cell in self.target_positions.values() {
+            let target = cell.to_fixed();
+            let distance_sq = pos.distance_squared(&target);
+            if nearest.is_none_or(|(best, _)| distance_sq < best) {
+                nearest = Some((distance_sq, target));
+            }
+        }
+        nearest.map(|(_, target)| target)
+    }
+
     /// Check if a cell is blocked for a given navigation profile
     pub fn is_blocked(&self, pos: &GridPos, profile: NavProfile) -> bool {
         // Walls always block (except for Phasing which ignores non-Wall obstacles)
@@ -301,6 +802,138 @@ impl FlowFieldCache {
         false
     }
 
+    /// Whether no wall cell is on the straight line between two positions
+    /// (windows and other obstacles don't block the view, like bullets)
+    pub fn has_line_of_sight(
+        &self,
+        from: fixed_math::FixedVec2,
+        to: fixed_math::FixedVec2,
+    ) -> bool {
+        let start = GridPos::from_fixed(from);
+        let end = GridPos::from_fixed(to);
+
+        // Bresenham walk over the cells of the segment
+        let dx = (end.x - start.x).abs();
+        let dy = -(end.y - start.y).abs();
+        let step_x = if start.x < end.x { 1 } else { -1 };
+        let step_y = if start.y < end.y { 1 } else { -1 };
+        let mut error = dx + dy;
+        let mut cell = start;
+
+        loop {
+            if self.wall_cells.contains(&cell) {
+                return false;
+            }
+            if cell == end {
+                return true;
+            }
+            let doubled = 2 * error;
+            if doubled >= dy {
+                error += dy;
+                cell.x += step_x;
+            }
+            if doubled <= dx {
+                error += dx;
+                cell.y += step_y;
+            }
+        }
+    }
+
+    /// Walkable cells of `bounds` for a profile, indexed with `GridBounds::index`
+    pub fn passable_grid(&self, bounds: &GridBounds, profile: NavProfile) -> Vec<bool> {
+        let mut passable = vec![true; bounds.len()];
+        // GridPos orders by x first, so a range selects the columns of the bounds
+        let columns = GridPos::new(bounds.min.x, i32::MIN)..=GridPos::new(bounds.max.x, i32::MAX);
+
+        let blocked = self
+            .blocked_cells
+            .iter()
+            .filter(|(obstacle_type, _)| !profile.can_pass(**obstacle_type))
+            .map(|(_, cells)| cells);
+        for cells in std::iter::once(&self.wall_cells).chain(blocked) {
+            for pos in cells.range(columns.clone()) {
+                if let Some(index) = bounds.index(*pos) {
+                    passable[index] = false;
+                }
+            }
+        }
+
+        passable
+    }
+
+    /// Rebuild the blocked cells from IntGrid data and current obstacle positions
+    ///
+    /// Returns the cells whose wall or obstacles changed since the last rebuild.
+    pub fn rebuild_blocked_cells<'a>(
+        &mut self,
+        obstacles: impl IntoIterator<Item = (fixed_math::FixedVec2, &'a Collider, &'a Obstacle)>,
+    ) -> BTreeSet<GridPos> {
+        let old_blocked_cells = std::mem::take(&mut self.blocked_cells);
+
+        // Start with IntGrid wall cells as the source of truth (perfect 1:1 LDtk tile mapping)
+        // We no longer iterate wall colliders since IntGrid has all static walls.
+        // Wall colliders are only used for physics, not pathfinding.
+        let old_wall_cells =
+            std::mem::replace(&mut self.wall_cells, self.intgrid_wall_cells.clone());
+
+        let mut window_cells_removed = 0;
+        // Process obstacles - windows create HOLES in walls
+        for (pos, collider, obstacle) in obstacles {
+            // Windows create passages through walls - remove only the CENTER cell
+            // Using center cell prevents accidentally removing adjacent wall cells
+            // when the window collider extends slightly beyond its tile
+            if obstacle.obstacle_type == ObstacleType::Window {
+                let center_cell = GridPos::from_fixed(pos);
+                if self.wall_cells.remove(&center_cell) {
+                    window_cells_removed += 1;
+                }
+                // Windows also block movement for Ground profile (until broken)
+                if obstacle.blocks_movement {
+                    self.blocked_cells
+                        .entry(obstacle.obstacle_type)
+                        .or_default()
+                        .insert(center_cell);
+                }
+                continue;
+            }
+
+            // For other obstacles, use full collider bounds
+            if !obstacle.blocks_movement {
+                continue;
+            }
+            let cells = get_collider_cells(pos, collider);
+            self.blocked_cells
+                .entry(obstacle.obstacle_type)
+                .or_default()
+                .extend(cells);
+        }
+
+        // Log only at trace level to avoid spam
+        trace!(
+            "FlowField: {} wall cells, {} window holes",
+            self.wall_cells.len(), window_cells_removed
+        );
+
+        let mut changed: BTreeSet<GridPos> = self
+            .wall_cells
+            .symmetric_difference(&old_wall_cells)
+            .copied()
+            .collect();
+        let empty = BTreeSet::new();
+        let obstacle_types: BTreeSet<ObstacleType> = self
+            .blocked_cells
+            .keys()
+            .chain(old_blocked_cells.keys())
+            .copied()
+            .collect();
+        for obstacle_type in obstacle_types {
+            let new_cells = self.blocked_cells.get(&obstacle_type).unwrap_or(&empty);
+            let old_cells = old_blocked_cells.get(&obstacle_type).unwrap_or(&empty);
+            changed.extend(new_cells.symmetric_difference(old_cells).copied());
+        }
+        changed
+    }
+
     /// Load wall cells directly from LDtk IntGrid data
     /// This provides perfect 1:1 mapping between LDtk tiles and flow field cells
     pub fn load_intgrid_walls(
@@ -327,6 +960,11 @@ impl FlowFieldCache {
             }
         }
 
+        let level_bounds = GridBounds::from_level(&level_info);
+        self.level_bounds = Some(
+            self.level_bounds
+                .map_or(level_bounds, |bounds| bounds.union(&level_bounds)),
+        );
         self.level_info = Some(level_info);
         info!(
             "FlowField: loaded {} IntGrid wall cells from {}x{} level at ({}, {})",
@@ -372,6 +1010,14 @@ pub struct FlowFieldConfig {
     pub use_8_directions: bool,
     /// Diagonal movement cost multiplier (for 8-directional)
     pub diagonal_cost: u32,
+    /// Also build one flow field per player, so enemies follow their own target
+    pub per_player_fields: bool,
+    /// Targets that moved at most this many cells since the last full build
+    /// are updated in place instead of rebuilding the field
+    pub retarget_max_cells: i32,
+    /// Cells recomputed around each target when updating in place
+    /// (must be more than twice `retarget_max_cells`)
+    pub retarget_window: i32,
 }
 
 impl Default for FlowFieldConfig {
@@ -381,6 +1027,9 @@ impl Default for FlowFieldConfig {
             max_search_radius: 50, // 50 cells * 16 units = 800 units radius
             use_8_directions: true, // 8 directions for smoother diagonal movement
             diagonal_cost: 14,
+            per_player_fields: true,
+            retarget_max_cells: 3,
+            retarget_window: 8,
         }
     }
 }
@@ -389,10 +1038,10 @@ impl Default for FlowFieldConfig {
 pub fn update_flow_field_system(
     frame: Res<FrameCount>,
     config: Res<FlowFieldConfig>,
-    player_query: Query<(&GgrsNetId, &fixed_math::FixedTransform3D), With<Player>>,
-    wall_query: Query<
-        (&fixed_math::FixedTransform3D, &Collider),
-        (With<Wall>, Without<Obstacle>),
+    // Downed and dead players are not chased
+    player_query: Query<
+        (&GgrsNetId, &fixed_math::FixedTransform3D),
+        (With<Player>, Without<Downed>, Without<Death>),
     >,
     obstacle_query: Query<
         (&fixed_math::FixedTransform3D, &Collider, &Obstacle),
@@ -412,103 +1061,93 @@ pub fn update_flow_field_system(
         return;
     }
 
-    // GGRS CRITICAL: Must select player deterministically by sorting by net_id
-    let target_pos = {
-        let mut players: Vec<_> = player_query.iter().collect();
-        if players.is_empty() {
-            return; // No players, nothing to do
-        }
-        // Sort by net_id for deterministic selection
-        players.sort_unstable_by_key(|(net_id, _)| net_id.0);
-        let (_, transform) = players[0];
-        GridPos::from_fixed(transform.translation.truncate())
-    };
+    // GGRS CRITICAL: BTreeMap keyed by net_id keeps the sources in a deterministic order
+    let target_positions: BTreeMap<StableIdType, GridPos> = player_query
+        .iter()
+        .map(|(net_id, transform)| (net_id.0, GridPos::from_fixed(transform.translation.truncate())))
+        .collect();
+    if target_positions.is_empty() {
+        return; // No living players, keep the last fields
+    }
 
-    // Skip if target hasn't moved and we have a valid flow field
-    if target_pos == cache.target_pos && !cache.layers.is_empty() {
+    // Rebuild blocked cell cache, the fields are repaired where it changed
+    let obstacles = obstacle_query
+        .iter()
+        .map(|(transform, collider, obstacle)| (transform.translation.truncate(), collider, obstacle));
+    let changed_cells = cache.rebuild_blocked_cells(obstacles);
+
+    // Skip if no player moved, no obstacle changed and we have a valid flow field
+    if target_positions == cache.target_positions
+        && changed_cells.is_empty()
+        && !cache.layers.is_empty()
+    {
         return;
     }
+    let players_changed = !target_positions.keys().eq(cache.target_positions.keys());
 
-    // Update target and frame
-    cache.target_pos = target_pos;
+    // Update targets and frame
+    cache.target_positions = target_positions;
     cache.last_update_frame = frame.frame;
 
-    // Rebuild blocked cell cache
-    rebuild_blocked_cells(&mut cache, &wall_query, &obstacle_query);
-
     // Use GroundBreaker profile so zombies can pathfind through breakable obstacles (windows)
-    let flow_field = build_flow_field(target_pos, NavProfile::GroundBreaker, &cache, &config);
+    let sources: Vec<GridPos> = cache.target_positions.values().copied().collect();
+    let shared_field = cache
+        .layers
+        .remove(&NavProfile::GroundBreaker)
+        .filter(|_| !players_changed);
+    let flow_field = update_flow_field(
+        shared_field,
+        &sources,
+        NavProfile::GroundBreaker,
+        &changed_cells,
+        &cache,
+        &config,
+    );
+
+    let mut player_layers = BTreeMap::new();
+    if config.per_player_fields && sources.len() > 1 {
+        let mut old_layers = std::mem::take(&mut cache.player_layers);
+        for (net_id, target_pos) in cache.target_positions.iter() {
+            let player_field = update_flow_field(
+                old_layers.remove(net_id),
+                &[*target_pos],
+                NavProfile::GroundBreaker,
+                &changed_cells,
+                &cache,
+                &config,
+            );
+            player_layers.insert(*net_id, player_field);
+        }
+    }
 
     // Log flow field stats only on significant rebuilds
     trace!(
-        "FlowField: target=({},{}), reachable={}, walls={}",
-        target_pos.x, target_pos.y,
-        flow_field.directions.len(), cache.wall_cells.len()
+        "FlowField: targets={}, reachable={}, player_fields={}, changed_cells={}, walls={}",
+        sources.len(), flow_field.reachable_count(),
+        player_layers.len(), changed_cells.len(), cache.wall_cells.len()
     );
 
     cache.layers.insert(NavProfile::GroundBreaker, flow_field);
+    cache.player_layers = player_layers;
 }
 
-/// Rebuild the blocked cell cache from IntGrid data and current obstacle positions
-fn rebuild_blocked_cells(
-    cache: &mut FlowFieldCache,
-    _wall_query: &Query<
-        (&fixed_math::FixedTransform3D, &Collider),
-        (With<Wall>, Without<Obstacle>),
-    >,
-    obstacle_query: &Query<
-        (&fixed_math::FixedTransform3D, &Collider, &Obstacle),
-        With<Rollback>,
-    >,
-) {
-    cache.blocked_cells.clear();
-
-    // Start with IntGrid wall cells as the source of truth (perfect 1:1 LDtk tile mapping)
-    // We no longer iterate wall colliders since IntGrid has all static walls.
-    // Wall colliders are only used for physics, not pathfinding.
-    cache.wall_cells = cache.intgrid_wall_cells.clone();
-
-    let mut window_cells_removed = 0;
-    // Process obstacles - windows create HOLES in walls
-    for (transform, collider, obstacle) in obstacle_query.iter() {
-        let pos = transform.translation.truncate();
-
-        // Windows create passages through walls - remove only the CENTER cell
-        // Using center cell prevents accidentally removing adjacent wall cells
-        // when the window collider extends slightly beyond its tile
-        if obstacle.obstacle_type == ObstacleType::Window {
-            let center_cell = GridPos::from_fixed(pos);
-            if cache.wall_cells.remove(&center_cell) {
-                window_cells_removed += 1;
-            }
-            // Windows also block movement for Ground profile (until broken)
-            if obstacle.blocks_movement {
-                cache
-                    .blocked_cells
-                    .entry(obstacle.obstacle_type)
-                    .or_default()
-                    .insert(center_cell);
-            }
-            continue;
-        }
-
-        // For other obstacles, use full collider bounds
-        let cells = get_collider_cells(pos, collider);
-        if !obstacle.blocks_movement {
-            continue;
-        }
-        cache
-            .blocked_cells
-            .entry(obstacle.obstacle_type)
-            .or_default()
-            .extend(cells);
+/// Bring a cached flow field up to date with the obstacles and the targets,
+/// in place when possible, rebuilt otherwise
+pub fn update_flow_field(
+    flow_field: Option<FlowField>,
+    sources: &[GridPos],
+    profile: NavProfile,
+    changed_cells: &BTreeSet<GridPos>,
+    cache: &FlowFieldCache,
+    config: &FlowFieldConfig,
+) -> FlowField {
+    if let Some(mut flow_field) = flow_field {
+        flow_field.repair(changed_cells, cache, config);
+        if flow_field.sources == sources || flow_field.retarget(sources, config) {
+            return flow_field;
+        }
     }
-
-    // Log only at trace level to avoid spam
-    trace!(
-        "FlowField: {} wall cells, {} window holes",
-        cache.wall_cells.len(), window_cells_removed
-    );
+    FlowField::build(sources, profile, cache, config)
 }
 
 /// Get all grid cells occupied by a collider (precise, no padding)
@@ -565,71 +1204,6 @@ fn get_collider_cells(
     cells
 }
 
-/// Build a flow field using simple BFS (much faster than Dijkstra for unweighted graphs)
-fn build_flow_field(
-    target: GridPos,
-    profile: NavProfile,
-    cache: &FlowFieldCache,
-    config: &FlowFieldConfig,
-) -> FlowField {
-    let mut flow_field = FlowField::default();
-    let mut visited: BTreeSet<GridPos> = BTreeSet::new();
-    let mut queue: VecDeque<GridPos> = VecDeque::new();
-
-    // Start BFS from target
-    queue.push_back(target);
-    visited.insert(target);
-    flow_field.directions.insert(target, target);
-    flow_field.costs.insert(target, 0);
-
-    let max_cells = (config.max_search_radius * config.max_search_radius * 4) as usize;
-    let mut cells_processed = 0;
-
-    while let Some(current) = queue.pop_front() {
-        // Safety limit to prevent infinite loops
-        cells_processed += 1;
-        if cells_processed > max_cells {
-            break;
-        }
-
-        let current_cost = *flow_field.costs.get(&current).unwrap_or(&0);
-
-        // Get neighbors (4 or 8 directions)
-        let neighbors = if config.use_8_directions {
-            current.neighbors_8().to_vec()
-        } else {
-            current.neighbors_4().to_vec()
-        };
-
-        for neighbor in neighbors {
-            // Skip if already visited
-            if visited.contains(&neighbor) {
-                continue;
-            }
-
-            // Check bounds (manhattan distance from target)
-            if neighbor.manhattan_distance(&target) > config.max_search_radius {
-                continue;
-            }
-
-            // Check if blocked for this profile
-            if cache.is_blocked(&neighbor, profile) {
-                continue;
-            }
-
-            // Mark as visited and add to queue
-            visited.insert(neighbor);
-            queue.push_back(neighbor);
-
-            // Direction points TOWARD target (so we store 'current' as the next step)
-            flow_field.directions.insert(neighbor, current);
-            flow_field.costs.insert(neighbor, current_cost + 1);
-        }
-    }
-
-    flow_field
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -654,4 +1228,86 @@ mod tests {
         assert!(NavProfile::Phasing.can_pass(ObstacleType::Window));
         assert!(!NavProfile::Phasing.can_pass(ObstacleType::Wall));
     }
+
+    /// Every reached cell leads to a target through cells of lower cost
+    fn assert_chains_reach_sources(field: &FlowField) {
+        for (start, _, _) in field.iter() {
+            let mut pos = start;
+            let mut steps = 0;
+            loop {
+                let cost = field.get_cost(pos).expect("chain leaves the reached cells");
+                if cost == 0 {
+                    assert!(
+                        field.sources.contains(&pos),
+                        "chain from {start:?} ends at {pos:?}"
+                    );
+                    break;
+                }
+                let next = field.get_direction(pos).unwrap();
+                let next_cost = field.get_cost(next).expect("next cell not reached");
+                assert!(next_cost < cost, "cost goes up from {pos:?} to {next:?}");
+                steps += 1;
+                assert!(
+                    steps <= field.reachable_count(),
+                    "chain from {start:?} loops"
+                );
+                pos = next;
+            }
+        }
+    }
+
+    #[test]
+    fn test_incremental_updates_keep_chains_valid() {
+        let config = FlowFieldConfig::default();
+        let mut cache = FlowFieldCache::new();
+        let level_bounds = GridBounds::new(GridPos::new(0, 0), GridPos::new(39, 39));
+        cache.level_bounds = Some(level_bounds);
+
+        // Deterministic pseudo random sequence (LCG)
+        let mut seed: u64 = 0x2545_f491_4f6c_dd1d;
+        let mut random = move |max: i32| {
+            seed = seed
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            ((seed >> 33) % max as u64) as i32
+        };
+
+        let mut sources = vec![GridPos::new(12, 12), GridPos::new(28, 26)];
+        let mut field = FlowField::build(&sources, NavProfile::GroundBreaker, &cache, &config);
+        assert_chains_reach_sources(&field);
+
+        for _ in 0..500 {
+            let mut changed = BTreeSet::new();
+            if random(2) == 0 {
+                // Move a target by a cell or two
+                let source = &mut sources[random(2) as usize];
+                let moved = GridPos::new(
+                    (source.x + random(5) - 2).clamp(0, 39),
+                    (source.y + random(5) - 2).clamp(0, 39),
+                );
+                if !cache.wall_cells.contains(&moved) {
+                    *source = moved;
+                }
+            } else {
+                // Place or remove a wall off the targets
+                let cell = GridPos::new(random(40), random(40));
+                if !sources.contains(&cell) {
+                    if !cache.wall_cells.remove(&cell) {
+                        cache.wall_cells.insert(cell);
+                    }
+                    changed.insert(cell);
+                }
+            }
+
+            field = update_flow_field(
+                Some(field),
+                &sources,
+                NavProfile::GroundBreaker,
+                &changed,
+                &cache,
+                &config,
+            );
+            assert_chains_reach_sources(&field);
+        }
+    }
 }
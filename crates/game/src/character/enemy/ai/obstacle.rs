@@ -4,6 +4,7 @@
 //! that supports different types of blocking entities with various properties.
 
 use bevy::prelude::*;
+use bevy_fixed::fixed_math;
 use serde::{Deserialize, Serialize};
 
 /// Type of obstacle - determines default behavior and appearance
@@ -228,12 +229,21 @@ pub struct ObstacleDestroyedEvent {
 /// Also syncs with WindowHealth for legacy compatibility
 pub fn process_obstacle_damage(
     mut attack_events: MessageReader<ObstacleAttackEvent>,
-    mut obstacle_query: Query<(Entity, &mut Obstacle, Option<&mut map::game::entity::map::window::WindowHealth>)>,
+    mut obstacle_query: Query<(
+        Entity,
+        &mut Obstacle,
+        Option<&fixed_math::FixedTransform3D>,
+        Option<&mut map::game::entity::map::window::WindowHealth>,
+    )>,
     mut destroyed_events: MessageWriter<ObstacleDestroyedEvent>,
     mut commands: Commands,
+    noise_config: Res<super::perception::NoiseConfig>,
+    mut noises: ResMut<super::perception::NoiseEvents>,
 ) {
     for event in attack_events.read() {
-        if let Ok((entity, mut obstacle, window_health_opt)) = obstacle_query.get_mut(event.obstacle) {
+        if let Ok((entity, mut obstacle, transform, window_health_opt)) =
+            obstacle_query.get_mut(event.obstacle)
+        {
             let destroyed = obstacle.take_damage(event.damage);
 
             // Sync with WindowHealth if present (legacy compatibility)
@@ -253,6 +263,14 @@ pub fn process_obstacle_damage(
                     destroyed_by: Some(event.attacker),
                 });
 
+                // Breaking through alerts the enemies around (heard next frame)
+                if let Some(transform) = transform {
+                    noises.emit(
+                        transform.translation.truncate(),
+                        noise_config.obstacle_break_radius,
+                    );
+                }
+
                 info!("Obstacle {:?} destroyed by {:?}", entity, event.attacker);
             }
         }
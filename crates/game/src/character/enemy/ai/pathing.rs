@@ -2,7 +2,8 @@ use crate::character::config::{CharacterConfig, CharacterConfigHandles};
 use crate::character::enemy::Enemy;
 use crate::character::movement::Velocity;
 use crate::character::player::input::FIXED_TIMESTEP;
-use crate::character::player::Player;
+use crate::character::player::{downed::Downed, Player};
+use crate::character::status_effect::StatusEffects;
 use crate::collider::{is_colliding, Collider, Wall, Window};
 use animation::FacingDirection;
 use bevy::prelude::*;
@@ -13,7 +14,12 @@ use serde::{Deserialize, Serialize};
 use std::collections::VecDeque;
 use utils::{frame::FrameCount, net_id::GgrsNetId, order_iter, order_mut_iter};
 
+use super::behavior_tree::BehaviorAction;
+use super::lod::{integrate_reduced, EnemyLod};
 use super::obstacle::{Obstacle, ObstacleAttackEvent};
+use super::steering::{
+    avoid_blocked_cells, blend_direction, compute_forces, Neighbor, SpatialGrid, SteeringConfig,
+};
 
 #[derive(Component, Debug, Clone, Default)]
 pub struct EnemyPath {
@@ -37,6 +43,9 @@ pub enum PathStatus {
     Blocked,
 }
 
+/// Frames of wall-sliding before the stuck recovery kicks in (~170ms at 60fps)
+pub const MAX_SLIDE_FRAMES: u8 = 10;
+
 /// Tracks consecutive frames of wall-sliding for stuck recovery
 #[derive(Component, Clone, Debug, Default, Serialize, Deserialize)]
 pub struct WallSlideTracker {
@@ -91,7 +100,7 @@ impl Default for PathfindingConfig {
 // System to find closest player and set as target
 // Uses EnemyTarget from the new AI state system
 pub fn update_enemy_targets(
-    player_query: Query<(&GgrsNetId, &fixed_math::FixedTransform3D, &Player)>,
+    player_query: Query<(&GgrsNetId, &fixed_math::FixedTransform3D, &Player), Without<Downed>>,
     mut enemy_query: Query<
         (
             &fixed_math::FixedTransform3D,
@@ -447,58 +456,62 @@ pub fn move_enemies(
             &mut WallSlideTracker,
             Option<&super::state::EnemyTarget>,
             Option<&super::state::EnemyAiConfig>,
+            Option<&StatusEffects>,
+            Option<&super::state::RangedAttackState>,
+            Option<&super::behavior_tree::BehaviorTreeState>,
         ),
-        With<Enemy>,
+        (With<Enemy>, Without<super::vault::WindowVault>),
     >,
     player_query: Query<&fixed_math::FixedTransform3D, (With<Player>, Without<Enemy>)>,
     character_configs: Res<Assets<CharacterConfig>>,
     config: Res<PathfindingConfig>,
+    steering_config: Res<SteeringConfig>,
     collision_settings: Res<crate::collider::CollisionSettings>,
     wall_collider_query: Query<
         (&fixed_math::FixedTransform3D, &Collider, &crate::collider::CollisionLayer),
         (With<Wall>, Without<Enemy>, Without<Player>),
     >,
-    // Query for windows (obstacles we can attack when blocked)
+    // Query for windows (obstacles we can attack when blocked, climbed once broken)
     window_query: Query<
-        (Entity, &fixed_math::FixedTransform3D, &Obstacle),
+        (
+            Entity,
+            &fixed_math::FixedTransform3D,
+            &Obstacle,
+            &Collider,
+            &crate::collider::CollisionLayer,
+        ),
         (With<Window>, With<Rollback>, Without<Enemy>, Without<Player>),
     >,
     flow_field_cache: Res<super::navigation::FlowFieldCache>,
+    room_graph: Res<super::room_graph::RoomGraph>,
+    // Separate query, the enemy one is full
+    lod_query: Query<&EnemyLod>,
     mut obstacle_events: MessageWriter<ObstacleAttackEvent>,
 ) {
     // --- Optimization 1: Cache walls ---
     // Collect walls into a Vec for faster iteration (cache locality)
     let walls: Vec<_> = wall_collider_query.iter().collect();
 
-    // --- Optimization 2: Spatial Grid for Separation ---
+    // --- Optimization 2: Spatial Grid for Steering ---
     // GGRS CRITICAL: Use order_iter! for deterministic iteration order
-    // We collect (Entity, Position)
-    let enemy_positions: Vec<(Entity, fixed_math::FixedVec2)> = order_iter!(enemy_query)
+    let neighbors: Vec<Neighbor> = order_iter!(enemy_query)
         .iter()
-        .map(|(_, entity, transform, ..)| (*entity, transform.translation.truncate()))
+        .map(|(_, entity, transform, velocity, ..)| Neighbor {
+            entity: *entity,
+            position: transform.translation.truncate(),
+            velocity: velocity.main,
+        })
         .collect();
-
-    // Build spatial grid
-    let cell_size = config.enemy_separation_distance;
-    let mut spatial_grid: std::collections::HashMap<(i32, i32), Vec<usize>> =
-        std::collections::HashMap::new();
-
-    for (index, (_, pos)) in enemy_positions.iter().enumerate() {
-        let grid_x = (pos.x / cell_size).floor().to_num::<i32>();
-        let grid_y = (pos.y / cell_size).floor().to_num::<i32>();
-        spatial_grid
-            .entry((grid_x, grid_y))
-            .or_default()
-            .push(index);
-    }
+    let spatial_grid = SpatialGrid::new(
+        config
+            .enemy_separation_distance
+            .max(steering_config.neighbor_radius),
+        neighbors,
+    );
 
     // Cache windows for collision checking
     let windows: Vec<_> = window_query.iter().collect();
 
-    // Obstacle avoidance constants
-    let lookahead_distance = fixed_math::new(30.0); // How far ahead to check for obstacles
-    let avoidance_strength = fixed_math::new(0.7); // How strongly to steer away from obstacles
-
     // Second pass - calculate and apply movement in deterministic order
     for (
         net_id,
@@ -513,8 +526,17 @@ pub fn move_enemies(
         mut wall_slide_tracker,
         enemy_target_opt,
         enemy_ai_config_opt,
+        status_effects,
+        ranged_state_opt,
+        tree_state_opt,
     ) in order_mut_iter!(enemy_query)
     {
+        // Far enemies keep their velocity between their LOD frames
+        if EnemyLod::skips(lod_query.get(entity).ok()) {
+            integrate_reduced(&mut fixed_transform, &velocity_component, &flow_field_cache);
+            continue;
+        }
+
         let enemy_pos_v2 = fixed_transform.translation.truncate();
 
         // Get character movement config
@@ -525,8 +547,20 @@ pub fn move_enemies(
                 config.movement_speed // Fallback is also Fixed
             };
 
-        // Get the player position from flow field cache
-        let player_pos = flow_field_cache.target_pos.to_fixed();
+        // Slowed or stunned enemies move slower (or not at all), knockback still applies
+        let movement_speed = status_effects
+            .map_or(movement_speed, |status| movement_speed * status.speed_multiplier(frame.frame));
+
+        // Get the nearest player position from flow field cache
+        let player_pos = flow_field_cache
+            .nearest_target(enemy_pos_v2)
+            .unwrap_or(enemy_pos_v2);
+
+        // Follow the field of the targeted player, the shared one (nearest player) otherwise
+        let target_flow_field = flow_field_cache.get_flow_field_to(
+            enemy_target_opt.and_then(|target| target.target.as_ref()),
+            super::navigation::NavProfile::GroundBreaker,
+        );
 
         // Get the enemy's actual target position (could be window, player, etc.)
         let actual_target = if let Some(enemy_target) = enemy_target_opt {
@@ -542,18 +576,40 @@ pub fn move_enemies(
             player_pos
         };
 
+        // The flow field leads to the players, lured enemies walk straight to the lure
+        let is_lured = enemy_target_opt
+            .is_some_and(|target| target.target_type == super::state::TargetType::Lure);
+
+        // Perceiving enemies only move toward what they noticed, they stand when idle
+        let is_investigating = enemy_target_opt
+            .is_some_and(|target| target.target_type == super::state::TargetType::Investigate);
+        let is_unaware = enemy_ai_config_opt.is_some_and(|ai_config| ai_config.perception.is_some())
+            && enemy_target_opt
+                .is_none_or(|target| target.target_type == super::state::TargetType::None);
+
         // Calculate direction to actual target using flow field
-        let direction_to_target_v2 = if let Some(flow_field) =
-            flow_field_cache.get_flow_field(super::navigation::NavProfile::GroundBreaker)
-        {
+        let direction_to_target_v2 = if is_lured {
+            (actual_target - enemy_pos_v2).normalize_or_zero()
+        } else if is_investigating {
+            // Investigated spots are not covered by the flow fields
+            room_graph
+                .get_direction_vector(enemy_pos_v2, actual_target)
+                .unwrap_or_else(|| (actual_target - enemy_pos_v2).normalize_or_zero())
+        } else if is_unaware {
+            fixed_math::FixedVec2::ZERO
+        } else if let Some(flow_field) = target_flow_field {
             // Always use flow field for navigation - it handles pathfinding around walls
             match flow_field.get_direction_vector(enemy_pos_v2) {
                 Some(dir) => dir,
                 None => {
-                    // Outside flow field coverage - find nearest covered cell
-                    // and move toward it instead of directly toward player
-                    // (moving directly toward player often pushes into walls)
-                    match flow_field.find_nearest_covered_cell(enemy_pos_v2, 10) {
+                    // Outside flow field coverage - route through the rooms first,
+                    // then find nearest covered cell and move toward it instead of
+                    // directly toward player (moving directly toward player often
+                    // pushes into walls)
+                    let covered_dir = room_graph
+                        .get_direction_vector(enemy_pos_v2, actual_target)
+                        .or_else(|| flow_field.find_nearest_covered_cell(enemy_pos_v2, 10));
+                    match covered_dir {
                         Some(dir) => dir,
                         None => {
                             // No flow field nearby at all - try neighbor directions as fallback
@@ -567,62 +623,77 @@ pub fn move_enemies(
                 }
             }
         } else {
-            // No flow field yet, move directly toward target
-            (actual_target - enemy_pos_v2).normalize_or_zero()
+            // No flow field yet, route through the rooms or move directly toward target
+            room_graph
+                .get_direction_vector(enemy_pos_v2, actual_target)
+                .unwrap_or_else(|| (actual_target - enemy_pos_v2).normalize_or_zero())
         };
 
-        // --- General Obstacle Avoidance Steering ---
-        // Use FlowField's blocked cells for O(1) lookups instead of O(walls) collision checks
-        let direction_to_target_v2 = {
-            use super::navigation::{GridPos, NavProfile};
-
-            // Fast grid-based check using FlowField's precomputed blocked cells
-            let is_cell_blocked = |test_pos: fixed_math::FixedVec2| -> bool {
-                let grid_pos = GridPos::from_fixed(test_pos);
-                flow_field_cache.is_blocked(&grid_pos, NavProfile::GroundBreaker)
-            };
-
-            // Check if moving forward would hit a blocked cell
-            let forward_pos = enemy_pos_v2 + direction_to_target_v2 * lookahead_distance;
-
-            if is_cell_blocked(forward_pos) {
-                // Forward is blocked - check left and right to find clear path
-                let perpendicular = fixed_math::FixedVec2::new(
-                    -direction_to_target_v2.y,
-                    direction_to_target_v2.x,
-                );
-
-                let left_pos = enemy_pos_v2 + (direction_to_target_v2 + perpendicular).normalize_or_zero() * lookahead_distance;
-                let right_pos = enemy_pos_v2 + (direction_to_target_v2 - perpendicular).normalize_or_zero() * lookahead_distance;
-
-                let left_clear = !is_cell_blocked(left_pos);
-                let right_clear = !is_cell_blocked(right_pos);
-
-                if left_clear && !right_clear {
-                    // Steer left
-                    let blended = direction_to_target_v2 * (fixed_math::FIXED_ONE - avoidance_strength)
-                        + perpendicular * avoidance_strength;
-                    blended.normalize_or_zero()
-                } else if right_clear && !left_clear {
-                    // Steer right
-                    let blended = direction_to_target_v2 * (fixed_math::FIXED_ONE - avoidance_strength)
-                        - perpendicular * avoidance_strength;
-                    blended.normalize_or_zero()
-                } else if left_clear && right_clear {
-                    // Both clear - pick left (deterministic for GGRS)
-                    let blended = direction_to_target_v2 * (fixed_math::FIXED_ONE - avoidance_strength)
-                        + perpendicular * avoidance_strength;
-                    blended.normalize_or_zero()
+        // Ranged enemies kite at their preferred distance instead of closing to the
+        // attack range: back off when too close, hold the position while winding
+        // up or when in range with a clear shot
+        let ranged_attack = enemy_ai_config_opt
+            .and_then(|ai_config| ai_config.ranged_attack.as_ref())
+            .filter(|_| !is_lured && !is_investigating && !is_unaware);
+        let mut holds_position = false;
+        let direction_to_target_v2 = match ranged_attack {
+            Some(ranged) => {
+                let distance_to_player = enemy_pos_v2.distance(&player_pos);
+                if ranged_state_opt.is_some_and(|ranged_state| ranged_state.is_winding_up()) {
+                    holds_position = true;
+                    direction_to_target_v2
+                } else if distance_to_player < ranged.preferred_distance - ranged.distance_tolerance {
+                    (enemy_pos_v2 - player_pos).normalize_or_zero()
                 } else {
-                    // Both blocked - keep original direction, wall sliding will handle it
+                    holds_position = distance_to_player
+                        <= ranged.preferred_distance + ranged.distance_tolerance
+                        && (!ranged.requires_line_of_sight
+                            || flow_field_cache.has_line_of_sight(enemy_pos_v2, player_pos));
                     direction_to_target_v2
                 }
-            } else {
-                // Forward is clear, no steering needed
+            }
+            None => direction_to_target_v2,
+        };
+
+        // Enemies with a behaviour tree only walk when it asks for it, lures
+        // pull them in whatever the tree says
+        let tree_state_opt = tree_state_opt.filter(|_| !is_lured);
+        let is_fleeing = tree_state_opt
+            .is_some_and(|tree_state| tree_state.action == BehaviorAction::Flee);
+        let direction_to_target_v2 = match tree_state_opt.map(|tree_state| tree_state.action) {
+            Some(BehaviorAction::MoveTo) | None => direction_to_target_v2,
+            Some(BehaviorAction::Flee) => (enemy_pos_v2 - actual_target).normalize_or_zero(),
+            Some(BehaviorAction::Idle | BehaviorAction::Attack | BehaviorAction::BreachObstacle) => {
+                holds_position = true;
                 direction_to_target_v2
             }
         };
 
+        // Flow field direction bent by the horde, then around the blocked cells
+        let steering_forces = compute_forces(
+            entity,
+            enemy_pos_v2,
+            &spatial_grid,
+            config.enemy_separation_distance,
+            &steering_config,
+        );
+        let direction_to_target_v2 = if holds_position {
+            direction_to_target_v2
+        } else {
+            blend_direction(
+                direction_to_target_v2,
+                &steering_forces,
+                wall_slide_tracker.consecutive_slide_frames,
+                &steering_config,
+            )
+        };
+        let direction_to_target_v2 = avoid_blocked_cells(
+            enemy_pos_v2,
+            direction_to_target_v2,
+            &flow_field_cache,
+            &steering_config,
+        );
+
         // Calculate distance to nearest player (for attack range check)
         // Initialize with FixedWide::MAX because distance_squared now returns FixedWide
         let mut min_dist_sq_to_player_fw = fixed_math::FixedWide::MAX;
@@ -655,50 +726,21 @@ pub fn move_enemies(
             distance_to_nearest_player = fixed_math::Fixed::MAX;
         }
 
-        // Calculate separation force (avoid other enemies) using Spatial Grid
-        let mut separation_v2 = fixed_math::FixedVec2::ZERO;
-        let mut separation_count: u32 = 0; // Use u32 for count
-
-        let grid_x = (enemy_pos_v2.x / cell_size).floor().to_num::<i32>();
-        let grid_y = (enemy_pos_v2.y / cell_size).floor().to_num::<i32>();
-
-        // Check current cell and neighbors (3x3 area)
-        for dx in -1..=1 {
-            for dy in -1..=1 {
-                if let Some(indices) = spatial_grid.get(&(grid_x + dx, grid_y + dy)) {
-                    for &idx in indices {
-                        let (other_entity, other_pos_v2) = &enemy_positions[idx];
-                        if *other_entity == entity {
-                            continue;
-                        }
-
-                        let dist_to_other = enemy_pos_v2.distance(other_pos_v2);
-                        // Use small epsilon for distance > 0 check
-                        if dist_to_other < config.enemy_separation_distance
-                            && dist_to_other > fixed_math::new(0.1)
-                        {
-                            let repulsion_v2 = (enemy_pos_v2 - *other_pos_v2).normalize_or_zero()
-                                / dist_to_other.max(fixed_math::FIXED_ONE);
-                            separation_v2 += repulsion_v2;
-                            separation_count += 1;
-                        }
-                    }
-                }
-            }
-        }
-
-        if separation_count > 0 {
-            // Convert count to Fixed for division
-            separation_v2 = (separation_v2 / fixed_math::Fixed::from_num(separation_count))
-                * config.enemy_separation_force;
-        }
+        // Separation pushes even enemies holding their position
+        let separation_v2 = steering_forces.separation * config.enemy_separation_force;
+        let separation_count = steering_forces.separation_count;
 
         // Calculate base velocity using flow field direction
         let base_velocity_v2 = direction_to_target_v2 * movement_speed;
 
         // Slow down when near player (for attack positioning)
         let speed_factor_fixed =
-            if distance_to_nearest_player < config.optimal_attack_distance {
+            if holds_position {
+                fixed_math::FIXED_ZERO
+            } else if ranged_attack.is_some() || is_fleeing {
+                // Ranged enemies never slow down to melee, they hold at their distance
+                fixed_math::FIXED_ONE
+            } else if distance_to_nearest_player < config.optimal_attack_distance {
                 fixed_math::FIXED_ZERO // Stop when in melee range
             } else if distance_to_nearest_player < config.slow_down_distance {
                 let range = config.slow_down_distance - config.optimal_attack_distance;
@@ -758,6 +800,16 @@ pub fn move_enemies(
                         return true;
                     }
                 }
+                // Windows stop the enemies too, broken ones are climbed through
+                // in `enemy_window_vault_system`
+                for (_, window_transform, _, window_collider, window_layer) in &windows {
+                    if !collision_settings.layer_matrix[enemy_collision_layer.0][window_layer.0] {
+                        continue;
+                    }
+                    if is_colliding(pos, enemy_collider, &window_transform.translation, window_collider) {
+                        return true;
+                    }
+                }
                 false
             };
 
@@ -817,7 +869,6 @@ pub fn move_enemies(
                 }
 
                 // If wall-sliding too long, trigger escape logic
-                const MAX_SLIDE_FRAMES: u8 = 10; // ~170ms at 60fps
                 if wall_slide_tracker.consecutive_slide_frames >= MAX_SLIDE_FRAMES {
                     // Reset tracker
                     wall_slide_tracker.consecutive_slide_frames = 0;
@@ -827,7 +878,7 @@ pub fn move_enemies(
                     let speed = velocity_component.main.length();
 
                     // Try flow field neighbor directions first
-                    if let Some(flow_field) = flow_field_cache.get_flow_field(super::navigation::NavProfile::GroundBreaker) {
+                    if let Some(flow_field) = target_flow_field {
                         let neighbor_dirs = flow_field.get_neighbor_directions(enemy_pos_v2);
                         for dir in neighbor_dirs {
                             // Determine slide axis (which axis succeeded)
@@ -869,7 +920,7 @@ pub fn move_enemies(
                         .map(|c| c.attack_range)
                         .unwrap_or(fixed_math::new(50.0));
 
-                    for (window_entity, window_transform, window_obstacle) in &windows {
+                    for (window_entity, window_transform, window_obstacle, ..) in &windows {
                         // Skip destroyed windows
                         if !window_obstacle.is_intact() {
                             continue;
@@ -902,7 +953,7 @@ pub fn move_enemies(
                     let mut escaped = false;
 
                     // First, try directions from neighboring flow field cells (sorted by cost)
-                    if let Some(flow_field) = flow_field_cache.get_flow_field(super::navigation::NavProfile::GroundBreaker) {
+                    if let Some(flow_field) = target_flow_field {
                         let neighbor_dirs = flow_field.get_neighbor_directions(enemy_pos_v2);
                         for dir in neighbor_dirs {
                             let dx = dir.x * move_magnitude;
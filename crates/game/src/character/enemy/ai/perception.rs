@@ -0,0 +1,138 @@
+//! Enemy Perception
+//!
+//! Enemies with a `PerceptionConfig` notice the players instead of always
+//! knowing where they are:
+//!
+//! - **Sight**: players in the vision cone (up to `aggro_range`) with no wall
+//!   in between, or closer than `awareness_range` in any direction
+//! - **Hearing**: gunshots, explosions, broken obstacles and sprinting players
+//!   make noises, every enemy within their radius investigates the spot
+//! - **Memory**: a player lost from sight is searched for at its last known
+//!   position (`EnemyTarget::last_known_position`) for `memory_frames`
+//!
+//! Enemies without perception see every player in `aggro_range`, like before.
+//!
+//! GGRS CRITICAL: `NoiseEvents` is a rollback resource, noises must only be
+//! emitted inside the `GgrsSchedule` (before `RollbackSystemSet::EnemyAI` to
+//! be heard on the same frame).
+
+use animation::FacingDirection;
+use bevy::prelude::*;
+use bevy_fixed::fixed_math;
+
+use super::navigation::FlowFieldCache;
+use super::state::PerceptionConfig;
+
+/// A noise enemies can hear within its radius
+#[derive(Clone, Copy, Debug)]
+pub struct Noise {
+    pub position: fixed_math::FixedVec2,
+    pub radius: fixed_math::Fixed,
+}
+
+/// Noises made since the last target selection, which clears them
+///
+/// GGRS CRITICAL: Must be registered with `.rollback_resource_with_clone::<NoiseEvents>()`
+#[derive(Resource, Clone, Debug, Default)]
+pub struct NoiseEvents {
+    pub noises: Vec<Noise>,
+}
+
+impl NoiseEvents {
+    pub fn emit(&mut self, position: fixed_math::FixedVec2, radius: fixed_math::Fixed) {
+        if radius > fixed_math::FIXED_ZERO {
+            self.noises.push(Noise { position, radius });
+        }
+    }
+
+    /// Position of the closest noise heard from `pos`
+    ///
+    /// Doesn't depend on the emission order, ties keep the lowest position.
+    pub fn heard_from(
+        &self,
+        pos: fixed_math::FixedVec2,
+        hearing_multiplier: fixed_math::Fixed,
+    ) -> Option<fixed_math::FixedVec2> {
+        self.noises
+            .iter()
+            .filter(|noise| {
+                pos.distance(&noise.position) <= noise.radius.saturating_mul(hearing_multiplier)
+            })
+            .map(|noise| (pos.distance_squared(&noise.position), noise.position))
+            .min_by(|(distance_a, pos_a), (distance_b, pos_b)| {
+                distance_a
+                    .cmp(distance_b)
+                    .then(pos_a.x.cmp(&pos_b.x))
+                    .then(pos_a.y.cmp(&pos_b.y))
+            })
+            .map(|(_, position)| position)
+    }
+}
+
+/// Radius of the noises made by each source
+///
+/// Static configuration, not rolled back.
+#[derive(Resource, Clone, Debug)]
+pub struct NoiseConfig {
+    pub gunshot_radius: fixed_math::Fixed,
+    pub explosion_radius: fixed_math::Fixed,
+    /// Window or barricade broken by an enemy
+    pub obstacle_break_radius: fixed_math::Fixed,
+    /// Footsteps of a sprinting player, walking is silent
+    pub sprint_radius: fixed_math::Fixed,
+}
+
+impl Default for NoiseConfig {
+    fn default() -> Self {
+        Self {
+            gunshot_radius: fixed_math::new(450.0),
+            explosion_radius: fixed_math::new(700.0),
+            obstacle_break_radius: fixed_math::new(250.0),
+            sprint_radius: fixed_math::new(120.0),
+        }
+    }
+}
+
+/// Unit vector of a facing direction (fixed point, unlike `FacingDirection::to_vector`)
+pub fn facing_vector(facing: &FacingDirection) -> fixed_math::FixedVec2 {
+    let diagonal = fixed_math::new(std::f32::consts::FRAC_1_SQRT_2);
+    let (x, y) = match facing {
+        FacingDirection::Right => (fixed_math::FIXED_ONE, fixed_math::FIXED_ZERO),
+        FacingDirection::UpRight => (diagonal, diagonal),
+        FacingDirection::Up => (fixed_math::FIXED_ZERO, fixed_math::FIXED_ONE),
+        FacingDirection::UpLeft => (-diagonal, diagonal),
+        FacingDirection::Left => (-fixed_math::FIXED_ONE, fixed_math::FIXED_ZERO),
+        FacingDirection::DownLeft => (-diagonal, -diagonal),
+        FacingDirection::Down => (fixed_math::FIXED_ZERO, -fixed_math::FIXED_ONE),
+        FacingDirection::DownRight => (diagonal, -diagonal),
+    };
+    fixed_math::FixedVec2::new(x, y)
+}
+
+/// Whether an enemy sees a player
+pub fn can_see(
+    perception: &PerceptionConfig,
+    aggro_range: fixed_math::Fixed,
+    enemy_pos: fixed_math::FixedVec2,
+    facing: &FacingDirection,
+    player_pos: fixed_math::FixedVec2,
+    flow_field_cache: &FlowFieldCache,
+) -> bool {
+    let distance = enemy_pos.distance(&player_pos);
+    if distance < perception.awareness_range {
+        return true;
+    }
+    if distance >= aggro_range {
+        return false;
+    }
+
+    // Inside the cone when the angle to the player is at most half the cone
+    let half_cone = perception.vision_cone_degrees / fixed_math::new(2.0) * fixed_math::FIXED_PI
+        / fixed_math::new(180.0);
+    let to_player = (player_pos - enemy_pos).normalize_or_zero();
+    if to_player.dot(&facing_vector(facing)) < fixed_math::cos_fixed(half_cone) {
+        return false;
+    }
+
+    flow_field_cache.has_line_of_sight(enemy_pos, player_pos)
+}
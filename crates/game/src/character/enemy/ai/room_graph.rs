@@ -0,0 +1,356 @@
+//! Hierarchical Room Navigation
+//!
+//! The flow fields only reach `max_search_radius` cells around the players.
+//! Further away, enemies plan a coarse route through the room graph and
+//! refine it locally:
+//!
+//! - **Rooms**: every LDtk level (`RoomBounds`), the rooms placed by
+//!   `map::generation`
+//! - **Portals**: the openings (doors, windows) in the edge shared by two
+//!   touching rooms. The generated `RoomConnection`s are not kept once the
+//!   map is loaded, the openings are found again in the walls
+//! - **Route**: the portal to take first from every room toward every other
+//!   room, precomputed with a BFS over the graph
+//! - **Local**: each portal has a small flow field covering its two rooms,
+//!   followed until the enemy enters the range of the players' flow field
+//!
+//! GGRS CRITICAL: the graph is derived from the rooms and the blocked cells of
+//! the rolled back `FlowFieldCache`. It is rebuilt on the frame they change
+//! (compared by hash), so after a rollback it always matches the restored
+//! cache and doesn't need rollback itself.
+
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+use bevy::prelude::*;
+use bevy_fixed::fixed_math;
+use map::game::entity::map::{level_id::LevelId, room::RoomBounds};
+
+use super::navigation::{
+    FlowField, FlowFieldCache, FlowFieldConfig, GridBounds, GridPos, NavProfile,
+};
+
+/// A room of the graph
+#[derive(Clone, Debug)]
+pub struct RoomNode {
+    pub level_id: String,
+    pub bounds: GridBounds,
+}
+
+/// An opening between two touching rooms
+#[derive(Clone, Debug)]
+pub struct RoomPortal {
+    /// Rooms on both sides (indices in `RoomGraph::rooms`, lowest first)
+    pub rooms: (usize, usize),
+    /// Middle of the opening, the facing cells of both rooms
+    pub cells: (GridPos, GridPos),
+    /// Leads to the opening from both rooms
+    pub field: FlowField,
+}
+
+impl RoomPortal {
+    /// Room on the other side of the portal
+    pub fn other_room(&self, room: usize) -> usize {
+        if self.rooms.0 == room {
+            self.rooms.1
+        } else {
+            self.rooms.0
+        }
+    }
+}
+
+/// Room graph used to route enemies outside of the flow field range
+#[derive(Resource, Clone, Debug, Default)]
+pub struct RoomGraph {
+    /// Sorted by level id
+    pub rooms: Vec<RoomNode>,
+    pub portals: Vec<RoomPortal>,
+    /// First portal to take, indexed by `from * rooms.len() + to`
+    next_portal: Vec<Option<usize>>,
+    /// `source_hash` of the rooms and cells the graph was built from
+    built_from: Option<u64>,
+}
+
+impl RoomGraph {
+    /// Build the graph from the rooms and the walls of the flow field cache
+    pub fn build(
+        mut rooms: Vec<RoomNode>,
+        cache: &FlowFieldCache,
+        config: &FlowFieldConfig,
+    ) -> Self {
+        // GGRS CRITICAL: sort by level id so every peer numbers the rooms the same way
+        rooms.sort_by(|a, b| a.level_id.cmp(&b.level_id));
+
+        let mut portals = Vec::new();
+        for a in 0..rooms.len() {
+            for b in (a + 1)..rooms.len() {
+                for cells in find_openings(&rooms[a].bounds, &rooms[b].bounds, cache) {
+                    // Both rooms plus one cell, so the field still leads out of
+                    // the opening cells of the neighbors
+                    let area = rooms[a].bounds.union(&rooms[b].bounds).expand(1);
+                    let portal_config = FlowFieldConfig {
+                        max_search_radius: area.width() + area.height(),
+                        ..config.clone()
+                    };
+                    let field = FlowField::build_within(
+                        &[cells.0, cells.1],
+                        NavProfile::GroundBreaker,
+                        cache,
+                        &portal_config,
+                        area,
+                    );
+                    portals.push(RoomPortal {
+                        rooms: (a, b),
+                        cells,
+                        field,
+                    });
+                }
+            }
+        }
+
+        let next_portal = compute_routes(rooms.len(), &portals);
+
+        Self {
+            built_from: Some(source_hash(&rooms, cache)),
+            rooms,
+            portals,
+            next_portal,
+        }
+    }
+
+    /// Room containing a cell, lowest level id first where rooms overlap
+    pub fn room_at(&self, pos: GridPos) -> Option<usize> {
+        self.rooms.iter().position(|room| room.bounds.contains(pos))
+    }
+
+    /// First portal to take from a room toward another one
+    pub fn next_portal(&self, from: usize, to: usize) -> Option<&RoomPortal> {
+        let index = self
+            .next_portal
+            .get(from * self.rooms.len() + to)
+            .copied()??;
+        self.portals.get(index)
+    }
+
+    /// Direction to follow from `pos` toward `target` through the rooms.
+    ///
+    /// `None` when both are in the same room (or outside of every room), the
+    /// local navigation handles it.
+    pub fn get_direction_vector(
+        &self,
+        pos: fixed_math::FixedVec2,
+        target: fixed_math::FixedVec2,
+    ) -> Option<fixed_math::FixedVec2> {
+        let cell = GridPos::from_fixed(pos);
+        let from = self.room_at(cell)?;
+        let to = self.room_at(GridPos::from_fixed(target))?;
+        if from == to {
+            return None;
+        }
+
+        let mut portal = self.next_portal(from, to)?;
+
+        // Standing in the opening: cross it and head to the next one
+        if portal.cells.0 == cell || portal.cells.1 == cell {
+            let next_room = portal.other_room(from);
+            if next_room == to {
+                return Some((target - pos).normalize_or_zero());
+            }
+            portal = self.next_portal(next_room, to)?;
+        }
+
+        portal.field.get_direction_vector(pos)
+    }
+}
+
+/// Hash of everything the graph is built from: the rooms (sorted by level id)
+/// and the cells blocking `NavProfile::GroundBreaker`
+fn source_hash(rooms: &[RoomNode], cache: &FlowFieldCache) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    for room in rooms {
+        room.level_id.hash(&mut hasher);
+        room.bounds.min.hash(&mut hasher);
+        room.bounds.max.hash(&mut hasher);
+    }
+    cache.wall_cells.hash(&mut hasher);
+    for (obstacle_type, cells) in &cache.blocked_cells {
+        if !NavProfile::GroundBreaker.can_pass(*obstacle_type) {
+            obstacle_type.hash(&mut hasher);
+            cells.hash(&mut hasher);
+        }
+    }
+    hasher.finish()
+}
+
+/// Facing cells of the openings between two touching rooms, the middle cell
+/// pair of every run of walkable pairs along their shared edge
+fn find_openings(
+    a: &GridBounds,
+    b: &GridBounds,
+    cache: &FlowFieldCache,
+) -> Vec<(GridPos, GridPos)> {
+    let y_range = a.min.y.max(b.min.y)..=a.max.y.min(b.max.y);
+    let x_range = a.min.x.max(b.min.x)..=a.max.x.min(b.max.x);
+
+    let edge: Vec<(GridPos, GridPos)> = if a.max.x + 1 == b.min.x {
+        y_range
+            .map(|y| (GridPos::new(a.max.x, y), GridPos::new(b.min.x, y)))
+            .collect()
+    } else if b.max.x + 1 == a.min.x {
+        y_range
+            .map(|y| (GridPos::new(a.min.x, y), GridPos::new(b.max.x, y)))
+            .collect()
+    } else if a.max.y + 1 == b.min.y {
+        x_range
+            .map(|x| (GridPos::new(x, a.max.y), GridPos::new(x, b.min.y)))
+            .collect()
+    } else if b.max.y + 1 == a.min.y {
+        x_range
+            .map(|x| (GridPos::new(x, a.min.y), GridPos::new(x, b.max.y)))
+            .collect()
+    } else {
+        return Vec::new();
+    };
+
+    let is_open = |(cell_a, cell_b): &(GridPos, GridPos)| {
+        !cache.is_blocked(cell_a, NavProfile::GroundBreaker)
+            && !cache.is_blocked(cell_b, NavProfile::GroundBreaker)
+    };
+
+    edge.split(|pair| !is_open(pair))
+        .filter(|run| !run.is_empty())
+        .map(|run| run[run.len() / 2])
+        .collect()
+}
+
+/// First portal from every room toward every other room (fewest rooms crossed)
+fn compute_routes(room_count: usize, portals: &[RoomPortal]) -> Vec<Option<usize>> {
+    // Adjacency in portal order, so ties resolve the same way on every peer
+    let mut adjacency: Vec<Vec<usize>> = vec![Vec::new(); room_count];
+    for (index, portal) in portals.iter().enumerate() {
+        adjacency[portal.rooms.0].push(index);
+        adjacency[portal.rooms.1].push(index);
+    }
+
+    let mut next_portal = vec![None; room_count * room_count];
+    for to in 0..room_count {
+        // BFS from the destination, the portal a room is discovered through
+        // is the first step of its route
+        let mut visited = vec![false; room_count];
+        let mut queue = std::collections::VecDeque::from([to]);
+        visited[to] = true;
+
+        while let Some(room) = queue.pop_front() {
+            for &portal_index in &adjacency[room] {
+                let neighbor = portals[portal_index].other_room(room);
+                if visited[neighbor] {
+                    continue;
+                }
+                visited[neighbor] = true;
+                next_portal[neighbor * room_count + to] = Some(portal_index);
+                queue.push_back(neighbor);
+            }
+        }
+    }
+
+    next_portal
+}
+
+/// System to rebuild the room graph when the rooms or the blocked cells changed
+///
+/// Runs after `update_flow_field_system`, which adds the window openings to the walls.
+pub fn update_room_graph_system(
+    room_query: Query<(&RoomBounds, &LevelId)>,
+    cache: Res<FlowFieldCache>,
+    config: Res<FlowFieldConfig>,
+    mut graph: ResMut<RoomGraph>,
+) {
+    if cache.wall_cells.is_empty() {
+        return;
+    }
+
+    let mut rooms: Vec<RoomNode> = room_query
+        .iter()
+        .map(|(bounds, level_id)| {
+            // Last cell inside the room, the far edges belong to the next room
+            let max = bounds.position + bounds.size;
+            let max_cell = GridPos::from_fixed(max);
+            RoomNode {
+                level_id: level_id.0.clone(),
+                bounds: GridBounds::new(
+                    GridPos::from_fixed(bounds.position),
+                    GridPos::new(max_cell.x - 1, max_cell.y - 1),
+                ),
+            }
+        })
+        .collect();
+    rooms.sort_by(|a, b| a.level_id.cmp(&b.level_id));
+
+    if graph.built_from == Some(source_hash(&rooms, &cache)) {
+        return;
+    }
+
+    *graph = RoomGraph::build(rooms, &cache, &config);
+
+    info!(
+        "RoomGraph: {} rooms, {} portals",
+        graph.rooms.len(),
+        graph.portals.len()
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn portal(rooms: (usize, usize)) -> RoomPortal {
+        RoomPortal {
+            rooms,
+            cells: (GridPos::default(), GridPos::default()),
+            field: FlowField::default(),
+        }
+    }
+
+    #[test]
+    fn test_compute_routes() {
+        // 0 - 1 - 2, and 3 on its own
+        let portals = vec![portal((0, 1)), portal((1, 2))];
+        let routes = compute_routes(4, &portals);
+        let route = |from: usize, to: usize| routes[from * 4 + to];
+
+        assert_eq!(route(0, 2), Some(0));
+        assert_eq!(route(1, 2), Some(1));
+        assert_eq!(route(2, 0), Some(1));
+        assert_eq!(route(1, 0), Some(0));
+        assert_eq!(route(0, 0), None);
+        assert_eq!(route(0, 3), None);
+        assert_eq!(route(3, 0), None);
+    }
+
+    #[test]
+    fn test_find_openings() {
+        let a = GridBounds::new(GridPos::new(0, 0), GridPos::new(9, 9));
+        let b = GridBounds::new(GridPos::new(10, 0), GridPos::new(19, 9));
+
+        // Wall along the shared edge, open on rows 3 to 5 and on row 8
+        let mut cache = FlowFieldCache::new();
+        for y in (0..=9).filter(|y| !(3..=5).contains(y) && *y != 8) {
+            cache.wall_cells.insert(GridPos::new(9, y));
+            cache.wall_cells.insert(GridPos::new(10, y));
+        }
+
+        let openings = find_openings(&a, &b, &cache);
+        assert_eq!(
+            openings,
+            vec![
+                (GridPos::new(9, 4), GridPos::new(10, 4)),
+                (GridPos::new(9, 8), GridPos::new(10, 8)),
+            ]
+        );
+        // Same openings seen from the other room
+        assert_eq!(find_openings(&b, &a, &cache).len(), 2);
+
+        // Rooms that don't touch have no opening
+        let far = GridBounds::new(GridPos::new(30, 0), GridPos::new(39, 9));
+        assert!(find_openings(&a, &far, &cache).is_empty());
+    }
+}
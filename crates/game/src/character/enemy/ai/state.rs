@@ -9,6 +9,8 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 use utils::net_id::GgrsNetId;
 
+use crate::weapons::BulletType;
+
 use super::navigation::NavProfile;
 use super::obstacle::ObstacleType;
 
@@ -25,15 +27,15 @@ pub enum MonsterState {
         target: AttackTarget,
         last_attack_frame: u32,
     },
-    /// Stunned or knocked back (recovering)
-    Stunned {
-        recover_at_frame: u32,
-    },
     /// Special state for breaking through obstacles
     Breaching {
         obstacle: GgrsNetId,
         start_frame: u32,
     },
+    /// Walking to a noise or to where a player was last seen
+    Investigating { start_frame: u32 },
+    /// Climbing through a broken window (see `vault::WindowVault`)
+    Vaulting { window: GgrsNetId, start_frame: u32 },
     /// Fleeing from target (low health behavior)
     Fleeing,
     /// Dead but not yet despawned
@@ -73,6 +75,68 @@ impl From<MovementType> for NavProfile {
     }
 }
 
+/// Projectile attack of a ranged enemy (spitter)
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RangedAttackConfig {
+    /// Distance the enemy keeps from its target, backing off when closer
+    pub preferred_distance: fixed_math::Fixed,
+    /// How far from `preferred_distance` the enemy holds its position
+    #[serde(default = "default_distance_tolerance")]
+    pub distance_tolerance: fixed_math::Fixed,
+    /// Only fire when no wall is between the enemy and its target
+    pub requires_line_of_sight: bool,
+    pub projectile: BulletType,
+    /// Distance the projectile travels before despawning
+    pub projectile_range: fixed_math::Fixed,
+    /// Frames standing still before the projectile is fired
+    pub windup_frames: u32,
+}
+
+fn default_distance_tolerance() -> fixed_math::Fixed {
+    fixed_math::new(30.0)
+}
+
+/// Sight and hearing of an enemy, see `perception.rs`
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PerceptionConfig {
+    /// Full angle of the vision cone in front of the enemy, in degrees
+    pub vision_cone_degrees: fixed_math::Fixed,
+    /// Players this close are noticed in any direction, even behind a wall
+    pub awareness_range: fixed_math::Fixed,
+    /// Multiplier of the noise radius (deaf < 1 < keen)
+    #[serde(default = "default_hearing_multiplier")]
+    pub hearing_multiplier: fixed_math::Fixed,
+    /// Frames spent investigating a noise or a lost player before giving up
+    pub memory_frames: u32,
+}
+
+fn default_hearing_multiplier() -> fixed_math::Fixed {
+    fixed_math::FIXED_ONE
+}
+
+/// Windup and cooldown of a ranged attack, only on enemies with a `RangedAttackConfig`
+///
+/// GGRS CRITICAL: Must be registered with `.rollback_component_with_clone::<RangedAttackState>()`
+#[derive(Component, Clone, Debug, Default, Serialize, Deserialize)]
+pub struct RangedAttackState {
+    /// Frame the current windup started, `None` when not winding up
+    pub windup_started_frame: Option<u32>,
+    pub last_fire_frame: Option<u32>,
+}
+
+impl RangedAttackState {
+    pub fn is_winding_up(&self) -> bool {
+        self.windup_started_frame.is_some()
+    }
+
+    pub fn can_start_windup(&self, current_frame: u32, cooldown_frames: u32) -> bool {
+        !self.is_winding_up()
+            && self
+                .last_fire_frame
+                .is_none_or(|last| current_frame >= last + cooldown_frames)
+    }
+}
+
 /// AI configuration for an enemy - loaded from RON
 #[derive(Component, Clone, Debug, Serialize, Deserialize)]
 pub struct EnemyAiConfig {
@@ -96,6 +160,10 @@ pub struct EnemyAiConfig {
     pub flee_threshold: Option<fixed_math::Fixed>,
     /// Damage dealt per attack
     pub attack_damage: fixed_math::Fixed,
+    /// Projectile attack, kites at the preferred distance instead of closing to `attack_range`
+    pub ranged_attack: Option<RangedAttackConfig>,
+    /// Sight cone and hearing, aggro on distance alone when missing
+    pub perception: Option<PerceptionConfig>,
 }
 
 impl Default for EnemyAiConfig {
@@ -111,6 +179,8 @@ impl Default for EnemyAiConfig {
             path_through_breakables: false,
             flee_threshold: None,
             attack_damage: fixed_math::new(10.0),
+            ranged_attack: None,
+            perception: None,
         }
     }
 }
@@ -153,6 +223,8 @@ impl EnemyAiConfig {
             path_through_breakables: true, // Zombies path through breakables (windows)
             flee_threshold: None,
             attack_damage: fixed_math::new(10.0),
+            ranged_attack: None,
+            perception: None,
         }
     }
 
@@ -169,6 +241,8 @@ impl EnemyAiConfig {
             path_through_breakables: false,
             flee_threshold: None,
             attack_damage: fixed_math::new(8.0),
+            ranged_attack: None,
+            perception: None,
         }
     }
 
@@ -195,6 +269,8 @@ impl EnemyAiConfig {
             path_through_breakables: false,
             flee_threshold: None,
             attack_damage: fixed_math::new(15.0),
+            ranged_attack: None,
+            perception: None,
         }
     }
 
@@ -215,6 +291,8 @@ impl EnemyAiConfig {
             path_through_breakables: true, // Uses GroundBreaker profile
             flee_threshold: None,
             attack_damage: fixed_math::new(25.0),
+            ranged_attack: None,
+            perception: None,
         }
     }
 }
@@ -232,6 +310,10 @@ pub struct EnemyAiConfigRon {
     pub path_through_breakables: Option<bool>,
     pub flee_threshold: Option<String>,
     pub attack_damage: Option<String>,
+    #[serde(default)]
+    pub ranged_attack: Option<RangedAttackConfig>,
+    #[serde(default)]
+    pub perception: Option<PerceptionConfig>,
 }
 
 impl From<&EnemyAiConfigRon> for EnemyAiConfig {
@@ -287,6 +369,12 @@ impl From<&EnemyAiConfigRon> for EnemyAiConfig {
                 warn!("Failed to parse attack_damage '{}' from RON config.", damage);
             }
         }
+        if let Some(ref ranged_attack) = ron.ranged_attack {
+            config.ranged_attack = Some(ranged_attack.clone());
+        }
+        if let Some(ref perception) = ron.perception {
+            config.perception = Some(perception.clone());
+        }
 
         config
     }
@@ -303,6 +391,21 @@ pub struct EnemyTarget {
     pub last_known_position: Option<fixed_math::FixedVec2>,
 }
 
+impl EnemyTarget {
+    pub fn clear(&mut self) {
+        self.target = None;
+        self.target_type = TargetType::None;
+        self.last_known_position = None;
+    }
+
+    /// Walk to a position without a target to attack
+    pub fn investigate(&mut self, position: fixed_math::FixedVec2) {
+        self.target = None;
+        self.target_type = TargetType::Investigate;
+        self.last_known_position = Some(position);
+    }
+}
+
 /// Type of target being pursued
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Default, Reflect, Serialize, Deserialize)]
 pub enum TargetType {
@@ -310,4 +413,8 @@ pub enum TargetType {
     None,
     Player,
     Obstacle,
+    /// A thrown lure, followed in a straight line instead of the flow field
+    Lure,
+    /// A noise or the last known position of a player, reached through the rooms
+    Investigate,
 }
@@ -0,0 +1,226 @@
+//! Horde Steering
+//!
+//! Local steering layered on top of the flow field, in fixed math so every
+//! peer moves the horde the same way:
+//!
+//! - **Separation**: push away from the neighbours that are too close
+//! - **Alignment**: walk the way the neighbours walk
+//! - **Cohesion**: stay with the group instead of trailing behind it
+//! - **Avoidance**: bend away from the blocked cells ahead
+//!
+//! The flow field direction leads, alignment and cohesion bend it. Both fade
+//! out while the enemy slides along a wall (`WallSlideTracker`): pulled
+//! toward the group it would keep pressing into the door frame, alone it
+//! follows the field through the opening. Groups spread across doors and
+//! windows instead of queueing in single file.
+
+use bevy::{platform::collections::HashMap, prelude::*};
+use bevy_fixed::fixed_math;
+
+use super::navigation::{FlowFieldCache, GridPos, NavProfile};
+use super::pathing::MAX_SLIDE_FRAMES;
+
+/// Weights of the local steering
+///
+/// Note: SteeringConfig is not rolled back (static configuration). The
+/// separation keeps its `PathfindingConfig` force and distance.
+#[derive(Resource, Clone, Debug)]
+pub struct SteeringConfig {
+    /// Neighbours further away are ignored by alignment and cohesion
+    pub neighbor_radius: fixed_math::Fixed,
+    pub alignment_weight: fixed_math::Fixed,
+    pub cohesion_weight: fixed_math::Fixed,
+    /// How far ahead blocked cells are looked for
+    pub avoidance_lookahead: fixed_math::Fixed,
+    /// How strongly to steer away from a blocked cell ahead (0.0 - 1.0)
+    pub avoidance_strength: fixed_math::Fixed,
+}
+
+impl Default for SteeringConfig {
+    fn default() -> Self {
+        Self {
+            neighbor_radius: fixed_math::new(96.0),
+            alignment_weight: fixed_math::new(0.3),
+            cohesion_weight: fixed_math::new(0.15),
+            avoidance_lookahead: fixed_math::new(30.0),
+            avoidance_strength: fixed_math::new(0.7),
+        }
+    }
+}
+
+/// An enemy as seen by its neighbours
+#[derive(Clone, Debug)]
+pub struct Neighbor {
+    pub entity: Entity,
+    pub position: fixed_math::FixedVec2,
+    /// Movement velocity of the previous frame
+    pub velocity: fixed_math::FixedVec2,
+}
+
+/// Enemies bucketed by cell for the neighbour lookups
+pub struct SpatialGrid {
+    cell_size: fixed_math::Fixed,
+    cells: HashMap<(i32, i32), Vec<usize>>,
+    neighbors: Vec<Neighbor>,
+}
+
+impl SpatialGrid {
+    /// GGRS CRITICAL: `neighbors` must be in net id order, the forces are
+    /// summed in that order
+    pub fn new(cell_size: fixed_math::Fixed, neighbors: Vec<Neighbor>) -> Self {
+        let mut grid = Self {
+            cell_size,
+            cells: HashMap::new(),
+            neighbors,
+        };
+        for index in 0..grid.neighbors.len() {
+            let cell = grid.cell(grid.neighbors[index].position);
+            grid.cells.entry(cell).or_default().push(index);
+        }
+        grid
+    }
+
+    fn cell(&self, pos: fixed_math::FixedVec2) -> (i32, i32) {
+        (
+            (pos.x / self.cell_size).floor().to_num::<i32>(),
+            (pos.y / self.cell_size).floor().to_num::<i32>(),
+        )
+    }
+
+    /// Enemies in the 3x3 cells around a position, in a fixed order
+    pub fn around(&self, pos: fixed_math::FixedVec2) -> impl Iterator<Item = &Neighbor> {
+        let (x, y) = self.cell(pos);
+        (-1..=1)
+            .flat_map(move |dx| (-1..=1).map(move |dy| (x + dx, y + dy)))
+            .filter_map(|cell| self.cells.get(&cell))
+            .flatten()
+            .map(|index| &self.neighbors[*index])
+    }
+}
+
+/// Steering forces of an enemy, each one a direction (or zero)
+#[derive(Clone, Debug, Default)]
+pub struct SteeringForces {
+    /// Not normalized, stronger when the neighbours are closer
+    pub separation: fixed_math::FixedVec2,
+    pub alignment: fixed_math::FixedVec2,
+    pub cohesion: fixed_math::FixedVec2,
+    /// Neighbours closer than the separation distance
+    pub separation_count: u32,
+}
+
+/// Separation, alignment and cohesion of an enemy from its neighbours
+pub fn compute_forces(
+    entity: Entity,
+    pos: fixed_math::FixedVec2,
+    grid: &SpatialGrid,
+    separation_distance: fixed_math::Fixed,
+    config: &SteeringConfig,
+) -> SteeringForces {
+    let mut forces = SteeringForces::default();
+    let mut velocity_sum = fixed_math::FixedVec2::ZERO;
+    let mut position_sum = fixed_math::FixedVec2::ZERO;
+    let mut neighbor_count: u32 = 0;
+
+    for neighbor in grid.around(pos) {
+        if neighbor.entity == entity {
+            continue;
+        }
+
+        let distance = pos.distance(&neighbor.position);
+        // Use small epsilon for distance > 0 check
+        if distance < separation_distance && distance > fixed_math::new(0.1) {
+            forces.separation +=
+                (pos - neighbor.position).normalize_or_zero() / distance.max(fixed_math::FIXED_ONE);
+            forces.separation_count += 1;
+        }
+
+        if distance < config.neighbor_radius {
+            velocity_sum += neighbor.velocity.normalize_or_zero();
+            // Relative to the enemy so the sum stays small
+            position_sum += neighbor.position - pos;
+            neighbor_count += 1;
+        }
+    }
+
+    if forces.separation_count > 0 {
+        forces.separation =
+            forces.separation / fixed_math::Fixed::from_num(forces.separation_count);
+    }
+    if neighbor_count > 0 {
+        let count = fixed_math::Fixed::from_num(neighbor_count);
+        forces.alignment = (velocity_sum / count).normalize_or_zero();
+        forces.cohesion = (position_sum / count).normalize_or_zero();
+    }
+
+    forces
+}
+
+/// Bend the flow field direction with alignment and cohesion
+///
+/// A standing enemy (zero direction) stays put, and wall sliding fades the
+/// local steering out.
+pub fn blend_direction(
+    direction: fixed_math::FixedVec2,
+    forces: &SteeringForces,
+    slide_frames: u8,
+    config: &SteeringConfig,
+) -> fixed_math::FixedVec2 {
+    if direction == fixed_math::FixedVec2::ZERO {
+        return direction;
+    }
+
+    let slide = fixed_math::Fixed::from_num(slide_frames.min(MAX_SLIDE_FRAMES))
+        / fixed_math::Fixed::from_num(MAX_SLIDE_FRAMES);
+    let local_weight = fixed_math::FIXED_ONE - slide;
+
+    let local =
+        forces.alignment * config.alignment_weight + forces.cohesion * config.cohesion_weight;
+    let blended = (direction + local * local_weight).normalize_or_zero();
+
+    // Never let the group turn the enemy around
+    if blended == fixed_math::FixedVec2::ZERO {
+        direction
+    } else {
+        blended
+    }
+}
+
+/// Bend a direction away from the blocked cells ahead
+///
+/// Uses the flow field blocked cells for O(1) lookups instead of O(walls)
+/// collision checks.
+pub fn avoid_blocked_cells(
+    pos: fixed_math::FixedVec2,
+    direction: fixed_math::FixedVec2,
+    flow_field_cache: &FlowFieldCache,
+    config: &SteeringConfig,
+) -> fixed_math::FixedVec2 {
+    let is_cell_blocked = |test_pos: fixed_math::FixedVec2| -> bool {
+        flow_field_cache.is_blocked(&GridPos::from_fixed(test_pos), NavProfile::GroundBreaker)
+    };
+
+    let lookahead = config.avoidance_lookahead;
+    if !is_cell_blocked(pos + direction * lookahead) {
+        // Forward is clear, no steering needed
+        return direction;
+    }
+
+    // Forward is blocked - check left and right to find clear path
+    let perpendicular = fixed_math::FixedVec2::new(-direction.y, direction.x);
+    let left_clear =
+        !is_cell_blocked(pos + (direction + perpendicular).normalize_or_zero() * lookahead);
+    let right_clear =
+        !is_cell_blocked(pos + (direction - perpendicular).normalize_or_zero() * lookahead);
+
+    let forward = direction * (fixed_math::FIXED_ONE - config.avoidance_strength);
+    if left_clear {
+        // Steer left, also when both are clear (deterministic for GGRS)
+        (forward + perpendicular * config.avoidance_strength).normalize_or_zero()
+    } else if right_clear {
+        (forward - perpendicular * config.avoidance_strength).normalize_or_zero()
+    } else {
+        // Both blocked - keep original direction, wall sliding will handle it
+        direction
+    }
+}
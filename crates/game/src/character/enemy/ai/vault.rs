@@ -0,0 +1,224 @@
+//! Window Vaulting
+//!
+//! Windows block the enemies like walls (see `move_enemies`). Once a window
+//! is broken (`WindowHealth.current == 0`), an enemy heading through it
+//! climbs instead of walking:
+//!
+//! 1. It snaps to the entry point, on its side of the window
+//! 2. It climbs for `vault_frames` (`Climb` animation), ignoring knockback
+//! 3. It lands on the exit point, on the other side, and chases again
+//!
+//! Only one enemy climbs through a window at a time, the others wait behind
+//! it, so every window is a single-file choke point.
+//!
+//! GGRS CRITICAL: `WindowVault` is a rollback component, the enemies are
+//! processed in net id order so the same enemy gets the window on every peer.
+
+use std::collections::BTreeSet;
+
+use animation::AnimationState;
+use bevy::prelude::*;
+use bevy_fixed::fixed_math;
+use map::game::entity::map::window::WindowHealth;
+use serde::{Deserialize, Serialize};
+use utils::{
+    frame::FrameCount,
+    net_id::{GgrsNetId, StableIdType},
+    order_iter, order_mut_iter,
+};
+
+use crate::character::enemy::Enemy;
+use crate::character::movement::Velocity;
+use crate::collider::Window;
+
+use super::navigation::{FlowFieldCache, GridPos};
+use super::state::{EnemyTarget, MonsterState};
+
+/// Animation played while climbing
+pub const VAULT_ANIMATION: &str = "Climb";
+
+/// Timing and reach of the vaults
+///
+/// Note: VaultConfig is not rolled back (static configuration)
+#[derive(Resource, Clone, Debug)]
+pub struct VaultConfig {
+    /// Frames to climb through a window
+    pub vault_frames: u32,
+    /// Distance from the window center at which a blocked enemy starts climbing
+    pub trigger_distance: fixed_math::Fixed,
+    /// Distance from the window center of the entry and exit points
+    pub entry_offset: fixed_math::Fixed,
+    pub exit_offset: fixed_math::Fixed,
+}
+
+impl Default for VaultConfig {
+    fn default() -> Self {
+        Self {
+            vault_frames: 45,
+            trigger_distance: fixed_math::new(34.0),
+            entry_offset: fixed_math::new(16.0),
+            // Far enough for the enemy collider to clear the window
+            exit_offset: fixed_math::new(30.0),
+        }
+    }
+}
+
+/// Climb of an enemy through a broken window
+///
+/// GGRS CRITICAL: Must be registered with `.rollback_component_with_clone::<WindowVault>()`
+#[derive(Component, Clone, Debug, Serialize, Deserialize)]
+pub struct WindowVault {
+    pub window: GgrsNetId,
+    pub start_frame: u32,
+    pub entry: fixed_math::FixedVec2,
+    pub exit: fixed_math::FixedVec2,
+}
+
+/// Axis crossing a window, across the wall it sits in
+pub fn crossing_axis(
+    window_pos: fixed_math::FixedVec2,
+    flow_field_cache: &FlowFieldCache,
+) -> fixed_math::FixedVec2 {
+    let cell = GridPos::from_fixed(window_pos);
+    let wall_runs_horizontally = flow_field_cache
+        .wall_cells
+        .contains(&GridPos::new(cell.x - 1, cell.y))
+        || flow_field_cache
+            .wall_cells
+            .contains(&GridPos::new(cell.x + 1, cell.y));
+
+    if wall_runs_horizontally {
+        fixed_math::FixedVec2::new(fixed_math::FIXED_ZERO, fixed_math::FIXED_ONE)
+    } else {
+        fixed_math::FixedVec2::new(fixed_math::FIXED_ONE, fixed_math::FIXED_ZERO)
+    }
+}
+
+/// System to start and advance the window vaults
+///
+/// Runs before `move_enemies`, which skips the climbing enemies. A moving
+/// enemy next to a broken window climbs when its target is on the other side.
+pub fn enemy_window_vault_system(
+    mut commands: Commands,
+    frame: Res<FrameCount>,
+    config: Res<VaultConfig>,
+    flow_field_cache: Res<FlowFieldCache>,
+    mut enemy_query: Query<
+        (
+            &GgrsNetId,
+            Entity,
+            &mut fixed_math::FixedTransform3D,
+            &mut Velocity,
+            &mut MonsterState,
+            &mut AnimationState,
+            Option<&EnemyTarget>,
+            Option<&WindowVault>,
+        ),
+        With<Enemy>,
+    >,
+    window_query: Query<
+        (&GgrsNetId, &fixed_math::FixedTransform3D, &WindowHealth),
+        (With<Window>, Without<Enemy>),
+    >,
+) {
+    let broken_windows: Vec<(GgrsNetId, fixed_math::FixedVec2)> = order_iter!(window_query)
+        .into_iter()
+        .filter(|(_, _, health)| health.current == 0)
+        .map(|(net_id, transform, _)| (net_id.clone(), transform.translation.truncate()))
+        .collect();
+
+    // Net ids of the windows someone is climbing through
+    let mut occupied_windows: BTreeSet<StableIdType> = enemy_query
+        .iter()
+        .filter_map(|(.., vault)| vault.map(|vault| vault.window.0))
+        .collect();
+
+    for (net_id, entity, mut transform, mut velocity, mut state, mut animation, target, vault) in
+        order_mut_iter!(enemy_query)
+    {
+        if *state == MonsterState::Dead {
+            continue;
+        }
+
+        if let Some(vault) = vault {
+            // Climbing enemies can't be pushed around
+            velocity.main = fixed_math::FixedVec2::ZERO;
+            velocity.knockback = fixed_math::FixedVec2::ZERO;
+
+            let elapsed = frame.frame.saturating_sub(vault.start_frame);
+            let position = if elapsed >= config.vault_frames {
+                commands.entity(entity).remove::<WindowVault>();
+                *state = MonsterState::Chasing;
+                vault.exit
+            } else {
+                let progress = fixed_math::Fixed::from_num(elapsed)
+                    / fixed_math::Fixed::from_num(config.vault_frames);
+                vault.entry + (vault.exit - vault.entry) * progress
+            };
+            transform.translation.x = position.x;
+            transform.translation.y = position.y;
+
+            if elapsed < config.vault_frames && animation.0 != VAULT_ANIMATION {
+                animation.0 = VAULT_ANIMATION.to_string();
+            }
+            continue;
+        }
+
+        // Standing enemies don't climb anywhere
+        if velocity.main == fixed_math::FixedVec2::ZERO {
+            continue;
+        }
+
+        let enemy_pos = transform.translation.truncate();
+        let Some(target_pos) = target
+            .and_then(|target| target.last_known_position)
+            .or_else(|| flow_field_cache.nearest_target(enemy_pos))
+        else {
+            continue;
+        };
+        for (window_net_id, window_pos) in broken_windows.iter() {
+            if occupied_windows.contains(&window_net_id.0)
+                || enemy_pos.distance(window_pos) > config.trigger_distance
+            {
+                continue;
+            }
+
+            // Climb toward the other side, only when the target is there
+            let axis = crossing_axis(*window_pos, &flow_field_cache);
+            let direction = if (*window_pos - enemy_pos).dot(&axis) >= fixed_math::FIXED_ZERO {
+                axis
+            } else {
+                -axis
+            };
+            if (target_pos - *window_pos).dot(&direction) <= fixed_math::FIXED_ZERO {
+                continue;
+            }
+
+            let entry = *window_pos - direction * config.entry_offset;
+            let exit = *window_pos + direction * config.exit_offset;
+            transform.translation.x = entry.x;
+            transform.translation.y = entry.y;
+            velocity.main = fixed_math::FixedVec2::ZERO;
+            velocity.knockback = fixed_math::FixedVec2::ZERO;
+            animation.0 = VAULT_ANIMATION.to_string();
+
+            *state = MonsterState::Vaulting {
+                window: window_net_id.clone(),
+                start_frame: frame.frame,
+            };
+            commands.entity(entity).insert(WindowVault {
+                window: window_net_id.clone(),
+                start_frame: frame.frame,
+                entry,
+                exit,
+            });
+            occupied_windows.insert(window_net_id.0);
+
+            info!(
+                "[{}] Enemy {} vaulting through window {}",
+                frame.frame, net_id, window_net_id
+            );
+            break;
+        }
+    }
+}
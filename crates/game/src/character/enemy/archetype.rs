@@ -0,0 +1,118 @@
+//! Enemy archetypes loaded from RON files.
+//!
+//! Every enemy type is described by `assets/enemies/<id>.ron`: its character
+//! config, sprites, animations, AI, weapons and loot. The ids are listed in
+//! `assets/enemies/registry.ron` (folders can't be loaded on wasm), so a new
+//! type only needs a new file and a registry entry before the wave tiers can
+//! reference it.
+//!
+//! The archetype id is also the key of the character config, sprites and
+//! animations in `GlobalAsset`, see `global_asset::load_enemy_archetypes`.
+
+use bevy::{platform::collections::HashMap, prelude::*, reflect::TypePath};
+use serde::{Deserialize, Serialize};
+
+use crate::powerup::PowerUpKind;
+
+use super::ai::{behavior_tree::BehaviorNode, state::EnemyAiConfigRon};
+
+pub const ENEMY_REGISTRY_PATH: &str = "enemies/registry.ron";
+
+/// Path of the archetype file of an enemy type
+pub fn archetype_path(id: &str) -> String {
+    format!("enemies/{}.ron", id)
+}
+
+/// List of the enemy types to load
+#[derive(Asset, TypePath, Debug, Clone, Serialize, Deserialize)]
+pub struct EnemyRegistry {
+    pub archetypes: Vec<String>,
+}
+
+/// Power-up drops of an enemy type, overrides the power-up config
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EnemyDropTable {
+    /// Chance to drop a power-up in percent, the power-up config one when missing
+    #[serde(default)]
+    pub drop_chance_percent: Option<u32>,
+    /// Power-up kind -> weight, the power-up config weights when empty
+    #[serde(default)]
+    pub drop_weights: HashMap<PowerUpKind, u32>,
+}
+
+/// Definition of an enemy type
+#[derive(Asset, TypePath, Debug, Clone, Serialize, Deserialize)]
+pub struct EnemyArchetype {
+    /// Path of the `CharacterConfig`
+    pub character_config: String,
+    /// Sprite layer name -> spritesheet config path
+    pub sprite_layers: HashMap<String, String>,
+    /// Path of the `AnimationMapConfig`
+    pub animations: String,
+    /// AI overrides, `EnemyAiConfig::zombie()` when missing
+    #[serde(default)]
+    pub ai: Option<EnemyAiConfigRon>,
+    /// Behaviour tree deciding when to move, attack or flee, the default
+    /// chase and attack loop when missing
+    #[serde(default)]
+    pub behavior: Option<BehaviorNode>,
+    /// Melee weapon id, `zombie_claws` when missing
+    #[serde(default)]
+    pub melee_weapon: Option<String>,
+    /// Ranged weapon id, none when missing
+    #[serde(default)]
+    pub ranged_weapon: Option<String>,
+    #[serde(default)]
+    pub drop_table: EnemyDropTable,
+    /// Points for the kill, the economy config reward when missing
+    #[serde(default)]
+    pub point_value: Option<u32>,
+}
+
+/// Kill reward and power-up drops of an enemy, copied from its archetype
+///
+/// GGRS CRITICAL: Must be registered with `.rollback_component_with_clone::<EnemyLoot>()`
+#[derive(Component, Clone, Debug, Default)]
+pub struct EnemyLoot {
+    pub point_value: Option<u32>,
+    pub drop_chance_percent: Option<u32>,
+    /// Sorted by kind, empty to use the power-up config weights
+    pub drop_weights: Vec<(PowerUpKind, u32)>,
+}
+
+impl From<&EnemyArchetype> for EnemyLoot {
+    fn from(archetype: &EnemyArchetype) -> Self {
+        let mut drop_weights: Vec<_> = archetype
+            .drop_table
+            .drop_weights
+            .iter()
+            .filter(|(_, weight)| **weight > 0)
+            .map(|(kind, weight)| (*kind, *weight))
+            .collect();
+        drop_weights.sort_by_key(|(kind, _)| *kind);
+
+        Self {
+            point_value: archetype.point_value,
+            drop_chance_percent: archetype.drop_table.drop_chance_percent,
+            drop_weights,
+        }
+    }
+}
+
+impl EnemyLoot {
+    pub fn total_drop_weight(&self) -> u32 {
+        self.drop_weights.iter().map(|(_, weight)| weight).sum()
+    }
+
+    /// Pick a power-up kind given a roll in `0..total_drop_weight()`
+    pub fn pick_drop(&self, roll: u32) -> Option<PowerUpKind> {
+        let mut cumulative = 0u32;
+        for (kind, weight) in self.drop_weights.iter() {
+            cumulative += weight;
+            if roll < cumulative {
+                return Some(*kind);
+            }
+        }
+        None
+    }
+}
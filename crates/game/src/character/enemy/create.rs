@@ -1,5 +1,5 @@
 use animation::SpriteSheetConfig;
-use bevy::prelude::*;
+use bevy::{ecs::system::SystemParam, prelude::*};
 use bevy_fixed::fixed_math;
 use utils::net_id::GgrsNetIdFactory;
 use harmonium_bevy::components::HarmoniumTag;
@@ -8,18 +8,37 @@ use crate::{
     character::{config::CharacterConfig, create::create_character},
     collider::{CollisionLayer, CollisionSettings},
     global_asset::GlobalAsset,
-    weapons::{melee::{spawn_melee_weapon_for_character, MeleeWeaponsConfig}, WeaponInventory, WeaponsConfig},
+    weapons::{
+        melee::{spawn_melee_weapon_for_character, MeleeWeaponsConfig},
+        spawn_weapon_for_player, WeaponInventory, WeaponsConfig,
+    },
 };
 
 use super::{
     ai::{
+        behavior_tree::{BehaviorTree, BehaviorTreeState},
+        lod::EnemyLod,
         pathing::{EnemyPath, WallSlideTracker},
-        state::{EnemyAiConfig, EnemyTarget, MonsterState},
+        state::{EnemyAiConfig, EnemyTarget, MonsterState, RangedAttackState},
     },
+    archetype::{EnemyArchetype, EnemyLoot},
     Enemy,
 };
 
-/// Spawns an enemy entity and returns it.
+/// Bundled parameters for enemy spawning (reduces parameter count)
+#[derive(SystemParam)]
+pub struct SpawnAssets<'w> {
+    pub collision_settings: Res<'w, CollisionSettings>,
+    pub weapons_asset: Res<'w, Assets<WeaponsConfig>>,
+    pub melee_weapons_asset: Res<'w, Assets<MeleeWeaponsConfig>>,
+    pub characters_asset: Res<'w, Assets<CharacterConfig>>,
+    pub archetypes_asset: Res<'w, Assets<EnemyArchetype>>,
+    pub asset_server: Res<'w, AssetServer>,
+    pub texture_atlas_layouts: ResMut<'w, Assets<TextureAtlasLayout>>,
+    pub spritesheet_assets: Res<'w, Assets<SpriteSheetConfig>>,
+}
+
+/// Spawns an enemy entity from its archetype and returns it.
 ///
 /// Returns the spawned Entity so callers can attach additional components
 /// (e.g., WaveEnemy for wave tracking).
@@ -27,38 +46,43 @@ pub fn spawn_enemy(
     enemy_type_name: String,
     position: fixed_math::FixedVec3,
     commands: &mut Commands,
-    _weapons_asset: &Res<Assets<WeaponsConfig>>,
-    melee_weapons_asset: &Res<Assets<MeleeWeaponsConfig>>,
-    characters_asset: &Res<Assets<CharacterConfig>>,
-    asset_server: &Res<AssetServer>,
-    texture_atlas_layouts: &mut ResMut<Assets<TextureAtlasLayout>>,
-    spritesheet_assets: &Res<Assets<SpriteSheetConfig>>,
-
+    spawn_assets: &mut SpawnAssets,
     global_assets: &Res<GlobalAsset>,
-    collision_settings: &Res<CollisionSettings>,
-
     id_factory: &mut ResMut<GgrsNetIdFactory>,
 ) -> Entity {
+    let archetype = global_assets
+        .enemy_archetypes
+        .get(&enemy_type_name)
+        .and_then(|h| spawn_assets.archetypes_asset.get(h))
+        .cloned();
+    if archetype.is_none() {
+        warn!("no archetype loaded for enemy type {}", enemy_type_name);
+    }
+
     let entity = create_character(
         commands,
         global_assets,
-        characters_asset,
-        asset_server,
-        texture_atlas_layouts,
-        spritesheet_assets,
+        &spawn_assets.characters_asset,
+        &spawn_assets.asset_server,
+        &mut spawn_assets.texture_atlas_layouts,
+        &spawn_assets.spritesheet_assets,
         enemy_type_name,
         None,
         (LinearRgba::RED).into(),
         position,
-        CollisionLayer(collision_settings.enemy_layer),
+        CollisionLayer(spawn_assets.collision_settings.enemy_layer),
         id_factory,
     );
 
-    let inventory = WeaponInventory::default();
+    let mut inventory = WeaponInventory::default();
 
-    // Give the enemy a melee weapon (zombie claws, fallback to bare hands)
-    if let Some(melee_weapons_config) = melee_weapons_asset.get(&global_assets.melee_weapons) {
-        if let Some(weapon) = melee_weapons_config.0.get("zombie_claws").or_else(|| melee_weapons_config.0.get("bare_hands")) {
+    // Give the enemy its melee weapon (zombie claws by default, fallback to bare hands)
+    let melee_weapon = archetype
+        .as_ref()
+        .and_then(|a| a.melee_weapon.as_deref())
+        .unwrap_or("zombie_claws");
+    if let Some(melee_weapons_config) = spawn_assets.melee_weapons_asset.get(&global_assets.melee_weapons) {
+        if let Some(weapon) = melee_weapons_config.0.get(melee_weapon).or_else(|| melee_weapons_config.0.get("bare_hands")) {
             spawn_melee_weapon_for_character(
                 commands,
                 entity,
@@ -68,6 +92,52 @@ pub fn spawn_enemy(
         }
     }
 
+    // Ranged weapon, only for the archetypes that have one
+    if let Some(ranged_weapon) = archetype.as_ref().and_then(|a| a.ranged_weapon.as_ref()) {
+        match spawn_assets
+            .weapons_asset
+            .get(&global_assets.weapons)
+            .and_then(|config| config.0.get(ranged_weapon))
+        {
+            Some(weapon) => {
+                spawn_weapon_for_player(
+                    commands,
+                    global_assets,
+                    &spawn_assets.asset_server,
+                    &mut spawn_assets.texture_atlas_layouts,
+                    &spawn_assets.spritesheet_assets,
+                    true,
+                    entity,
+                    weapon.clone(),
+                    &mut inventory,
+                    id_factory,
+                );
+            }
+            None => warn!("unknown ranged weapon {} for enemy", ranged_weapon),
+        }
+    }
+
+    let (ai_config, loot) = match archetype.as_ref() {
+        Some(archetype) => (
+            archetype.ai.as_ref().map_or_else(EnemyAiConfig::zombie, EnemyAiConfig::from),
+            EnemyLoot::from(archetype),
+        ),
+        None => (EnemyAiConfig::zombie(), EnemyLoot::default()),
+    };
+
+    // Ranged enemies track the windup and cooldown of their projectile attack
+    if ai_config.ranged_attack.is_some() {
+        commands.entity(entity).insert(RangedAttackState::default());
+    }
+
+    // Data-driven enemies decide through their behaviour tree
+    if let Some(root) = archetype.as_ref().and_then(|archetype| archetype.behavior.as_ref()) {
+        let tree = BehaviorTree::new(root);
+        commands
+            .entity(entity)
+            .insert((BehaviorTreeState::new(&tree), tree));
+    }
+
     commands
         .entity(entity)
         .insert((
@@ -76,9 +146,11 @@ pub fn spawn_enemy(
             WallSlideTracker::default(),
             Enemy::default(),
             // AI components for flow field navigation and combat
-            EnemyAiConfig::zombie(),
+            ai_config,
             EnemyTarget::default(),
             MonsterState::default(),
+            EnemyLod::default(),
+            loot,
             HarmoniumTag::new(&["danger", "combat", "monster"], 1.0),
         ));
 
@@ -1,4 +1,5 @@
 pub mod ai;
+pub mod archetype;
 pub mod create;
 pub mod spawning;
 use bevy::prelude::*;
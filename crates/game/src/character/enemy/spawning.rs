@@ -1,17 +1,15 @@
-use animation::SpriteSheetConfig;
 use bevy::prelude::*;
 use bevy_fixed::{fixed_math, rng::RollbackRng};
-use map::game::entity::map::{enemy_spawn::EnemySpawnerComponent, level_id::LevelId, room::RoomBounds};
+use map::game::entity::map::{enemy_spawn::EnemySpawnerComponent, level_id::LevelId};
 
 use crate::{
-    character::{config::CharacterConfig, player::Player},
-    collider::CollisionSettings,
+    character::player::Player,
     global_asset::GlobalAsset,
-    weapons::{melee::MeleeWeaponsConfig, WeaponsConfig},
+    room::SpawnerRooms,
 };
 use utils::{frame::FrameCount, net_id::{GgrsNetId, GgrsNetIdFactory}, order_iter};
 
-use super::{create::spawn_enemy, Enemy};
+use super::{create::{spawn_enemy, SpawnAssets}, Enemy};
 
 /// Maximum distance from player for spawners to activate
 /// Should be within flow field range (50 cells * 16 = 800 units)
@@ -47,17 +45,11 @@ pub fn enemy_spawn_from_spawners_system(
         &fixed_math::FixedTransform3D,
         Option<&LevelId>,
     )>,
-    room_query: Query<(&RoomBounds, &LevelId)>,
+    spawner_rooms: SpawnerRooms,
     enemy_query: Query<&fixed_math::FixedTransform3D, With<Enemy>>,
     player_query: Query<(&GgrsNetId, &fixed_math::FixedTransform3D), With<Player>>,
     global_assets: Res<GlobalAsset>,
-    collision_settings: Res<CollisionSettings>,
-    weapons_asset: Res<Assets<WeaponsConfig>>,
-    melee_weapons_asset: Res<Assets<MeleeWeaponsConfig>>,
-    characters_asset: Res<Assets<CharacterConfig>>,
-    asset_server: Res<AssetServer>,
-    mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
-    sprint_sheet_assets: Res<Assets<SpriteSheetConfig>>,
+    mut spawn_assets: SpawnAssets,
 
     mut id_factory: ResMut<GgrsNetIdFactory>,
 ) {
@@ -79,7 +71,7 @@ pub fn enemy_spawn_from_spawners_system(
         let player_pos = transform.translation.truncate();
 
         // Find which room this player is in
-        for (room_bounds, level_id) in room_query.iter() {
+        for (room_bounds, level_id) in spawner_rooms.rooms.iter() {
             if room_bounds.contains(player_pos) {
                 players_with_rooms.push((net_id.0, player_pos, level_id.0.clone()));
                 break; // Player can only be in one room
@@ -139,12 +131,22 @@ pub fn enemy_spawn_from_spawners_system(
             }
         };
 
-        // Find the closest player that is in the SAME room as this spawner
+        // Only spawners in an unlocked room or on its perimeter are used
+        let Some(active_room) = level_id.and_then(|id| spawner_rooms.active_room(id, spawner_pos)) else {
+            if frame.frame % 60 == 0 {
+                debug!("Frame {}: Spawner {} (Level: {}) is in a locked room, skipped",
+                       frame.frame, net_id.0, spawner_level);
+            }
+            continue;
+        };
+
+        // Find the closest player that is in the SAME room as this spawner,
+        // or in the room it is on the perimeter of
         let mut min_distance_to_same_room_player: Option<fixed_math::Fixed> = None;
 
         for (player_net_id, player_pos, player_room) in &players_with_rooms {
             // Only consider players in the same room
-            if player_room != spawner_level {
+            if *player_room != active_room {
                 continue;
             }
 
@@ -274,14 +276,8 @@ pub fn enemy_spawn_from_spawners_system(
             enemy_type_name,
             final_spawn_pos,
             &mut commands,
-            &weapons_asset,
-            &melee_weapons_asset,
-            &characters_asset,
-            &asset_server,
-            &mut texture_atlas_layouts,
-            &sprint_sheet_assets,
+            &mut spawn_assets,
             &global_assets,
-            &collision_settings,
             &mut id_factory,
         );
 
@@ -1,4 +1,5 @@
 
+pub mod modifiers;
 pub mod ui;
 
 use bevy::{log::{tracing::span, Level}, prelude::*};
@@ -8,7 +9,7 @@ use ggrs::PlayerHandle;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 use utils::{frame::FrameCount, net_id::GgrsNetId, order_iter, order_mut_iter};
-use crate::character::player::Player;
+use crate::{character::{player::{downed::Downed, Player}, status_effect::StatusEffects}, perk::Perks};
 
 #[derive(Event, Message)]
 pub struct PlayerDiedEvent(pub GgrsNetId);
@@ -17,6 +18,8 @@ pub struct PlayerDiedEvent(pub GgrsNetId);
 pub enum HitBy {
     Entity(GgrsNetId),
     Player(PlayerHandle),
+    // Enemy that fired the projectile
+    Enemy(GgrsNetId),
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -32,7 +35,6 @@ pub struct HealthConfig {
 pub struct Health {
     pub current: fixed_math::Fixed,
     pub max: fixed_math::Fixed,
-    pub invulnerable_until_frame: Option<u32>, // Optional invulnerability window
 }
 
 #[derive(Component, Clone, Debug, Serialize, Default, Deserialize)]
@@ -59,17 +61,14 @@ impl fmt::Display for HitBy {
         match self {
             HitBy::Entity(net_id) => write!(f, "NetId({})", net_id.0),
             HitBy::Player(player_handle) => write!(f, "Player({})", player_handle),
+            HitBy::Enemy(net_id) => write!(f, "Enemy({})", net_id.0),
         }
     }
 }
 
 impl fmt::Display for Health {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "HP: {}/{}", self.current, self.max)?;
-        if let Some(frame) = self.invulnerable_until_frame {
-            write!(f, " (Invulnerable until frame {})", frame)?;
-        }
-        Ok(())
+        write!(f, "HP: {}/{}", self.current, self.max)
     }
 }
 
@@ -97,7 +96,6 @@ impl From<HealthConfig> for Health {
         Self {
             current: value.max,
             max: value.max,
-            invulnerable_until_frame: None,
         }
     }
 }
@@ -106,12 +104,24 @@ impl From<HealthConfig> for Health {
 pub fn rollback_apply_accumulated_damage(
     frame: Res<FrameCount>,
     mut commands: Commands,
-    mut query: Query<(&GgrsNetId, Entity, &DamageAccumulator, &mut Health, Option<&mut HealthRegen>), With<Rollback>>,
+    mut query: Query<(&GgrsNetId, Entity, &DamageAccumulator, &mut Health, Option<&mut HealthRegen>, Option<&Downed>, Option<&StatusEffects>), With<Rollback>>,
 ) {
     let system_span = span!(Level::INFO, "ggrs", f = frame.frame, s = "apply_damage");
     let _enter = system_span.enter();
 
-    for (g_id, entity, accumulator, mut health, opt_regen) in order_mut_iter!(query) {
+    for (g_id, entity, accumulator, mut health, opt_regen, downed, status_effects) in order_mut_iter!(query) {
+        // Downed players only die by bleeding out
+        if downed.is_some() {
+            commands.entity(entity).remove::<DamageAccumulator>();
+            continue;
+        }
+
+        if status_effects.is_some_and(|status| status.is_invulnerable(frame.frame)) {
+            info!("{} invulnerable, ignore {} dmg", g_id, accumulator.total_damage);
+            commands.entity(entity).remove::<DamageAccumulator>();
+            continue;
+        }
+
         if accumulator.total_damage > fixed_math::FIXED_ZERO {
             health.current = health.current.saturating_sub(accumulator.total_damage);
 
@@ -160,26 +170,32 @@ pub fn rollback_apply_death(
 // SYSTEM: HEALTH REGENERATION
 pub fn rollback_health_regeneration(
     frame: Res<FrameCount>,
-    mut query: Query<(&GgrsNetId, &mut Health, &HealthRegen), With<Rollback>>,
+    mut query: Query<(&GgrsNetId, &mut Health, &HealthRegen, Option<&Perks>), (With<Rollback>, Without<Downed>)>,
 ) {
     let system_span = span!(Level::INFO, "ggrs", f = frame.frame, s = "health_regen");
     let _enter = system_span.enter();
 
-    for (g_id, mut health, regen) in order_mut_iter!(query) {
+    for (g_id, mut health, regen, perks) in order_mut_iter!(query) {
+        // Perks speed up the regen
+        let (regen_rate, regen_delay_frames) = match perks {
+            Some(perks) => (perks.regen_rate(regen.regen_rate), perks.regen_delay_frames(regen.regen_delay_frames)),
+            None => (regen.regen_rate, regen.regen_delay_frames),
+        };
+
         // Check if enough time has passed since last damage
         let frames_since_damage = frame.frame.saturating_sub(regen.last_damage_frame);
         
-        if frames_since_damage >= regen.regen_delay_frames && health.current < health.max {
+        if frames_since_damage >= regen_delay_frames && health.current < health.max {
             let health_before = health.current;
             // Regenerate health (60 frames per second)
-            let regen_per_frame = regen.regen_rate / fixed_math::new(60.0);
+            let regen_per_frame = regen_rate / fixed_math::new(60.0);
             health.current = (health.current + regen_per_frame).min(health.max);
             
             // Log every 60 frames (once per second) or when reaching max health
             if frame.frame % 60 == 0 || health.current >= health.max {
                 info!(
                     "{} regen {} -> {} (+{}/s, {}f since dmg)",
-                    g_id, health_before, health.current, regen.regen_rate, frames_since_damage
+                    g_id, health_before, health.current, regen_rate, frames_since_damage
                 );
             }
         }
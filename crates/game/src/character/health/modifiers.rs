@@ -0,0 +1,101 @@
+//! Incoming damage modifiers of a character: critical zone and resistances.
+//!
+//! Configured per character type in the `damage_modifiers` field of the
+//! character RON, read by the bullet collision and explosion systems.
+
+use bevy::{platform::collections::HashMap, prelude::*};
+use bevy_fixed::fixed_math;
+use serde::{Deserialize, Serialize};
+
+use crate::weapons::BulletKind;
+
+/// Circle on the character where hits are critical ("headshots")
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct CriticalZone {
+    /// Center of the zone relative to the character position
+    pub offset: fixed_math::FixedVec2,
+    pub radius: fixed_math::Fixed,
+    /// Damage multiplier of a critical hit
+    pub multiplier: fixed_math::Fixed,
+}
+
+/// `damage_modifiers` field of the character config
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct DamageModifiersConfig {
+    #[serde(default)]
+    pub critical_zone: Option<CriticalZone>,
+    /// Incoming damage multiplier per bullet kind (0.5 = half damage), 1 when missing
+    #[serde(default)]
+    pub resistances: HashMap<BulletKind, fixed_math::Fixed>,
+}
+
+impl DamageModifiersConfig {
+    pub fn is_empty(&self) -> bool {
+        self.critical_zone.is_none() && self.resistances.is_empty()
+    }
+}
+
+/// Incoming damage modifiers of a character
+///
+/// GGRS CRITICAL: Must be registered with `.rollback_component_with_clone::<DamageModifiers>()`
+#[derive(Component, Clone, Debug, Default, Serialize, Deserialize)]
+pub struct DamageModifiers {
+    pub critical_zone: Option<CriticalZone>,
+    /// Sorted by bullet kind
+    pub resistances: Vec<(BulletKind, fixed_math::Fixed)>,
+}
+
+impl DamageModifiers {
+    /// Modifiers from the config, the critical zone is scaled like the collider
+    pub fn from_config(config: &DamageModifiersConfig, scale: fixed_math::Fixed) -> Self {
+        let critical_zone = config.critical_zone.map(|zone| CriticalZone {
+            offset: zone.offset * scale,
+            radius: zone.radius.saturating_mul(scale),
+            multiplier: zone.multiplier,
+        });
+
+        let mut resistances: Vec<_> = config
+            .resistances
+            .iter()
+            .map(|(kind, multiplier)| (*kind, *multiplier))
+            .collect();
+        resistances.sort_by_key(|(kind, _)| *kind);
+
+        Self {
+            critical_zone,
+            resistances,
+        }
+    }
+
+    /// Whether a hit at `hit_position` lands in the critical zone
+    pub fn is_critical_hit(
+        &self,
+        hit_position: fixed_math::FixedVec2,
+        position: fixed_math::FixedVec2,
+    ) -> bool {
+        self.critical_zone.is_some_and(|zone| {
+            hit_position.distance(&(position + zone.offset)) <= zone.radius
+        })
+    }
+
+    pub fn resistance(&self, kind: BulletKind) -> fixed_math::Fixed {
+        self.resistances
+            .iter()
+            .find(|(resisted, _)| *resisted == kind)
+            .map_or(fixed_math::FIXED_ONE, |(_, multiplier)| *multiplier)
+    }
+
+    /// Damage after the resistance and the critical multiplier
+    pub fn apply(
+        &self,
+        damage: fixed_math::Fixed,
+        kind: BulletKind,
+        is_critical: bool,
+    ) -> fixed_math::Fixed {
+        let damage = damage.saturating_mul(self.resistance(kind));
+        match self.critical_zone {
+            Some(zone) if is_critical => damage.saturating_mul(zone.multiplier),
+            _ => damage,
+        }
+    }
+}
@@ -1,11 +1,16 @@
 use bevy::prelude::*;
 use bevy_fixed::fixed_math;
 
+use crate::character::stamina::Stamina;
+
 use super::Health;
 
 #[derive(Component)]
 pub struct HealthBar;
 
+#[derive(Component)]
+pub struct StaminaBar;
+
 /*
 pub fn setup_health_bars(
     mut commands: Commands,
@@ -42,3 +47,17 @@ pub fn update_health_bars(
         }
     }
 }
+
+pub fn update_stamina_bars(
+    stamina_query: Query<(&Stamina, &Children)>,
+    mut stamina_bar_query: Query<&mut Sprite, With<StaminaBar>>,
+) {
+    for (stamina, children) in stamina_query.iter() {
+        for child in children.iter() {
+            if let Ok(mut sprite) = stamina_bar_query.get_mut(child) {
+                let stamina_ratio = stamina.current / stamina.max;
+                sprite.custom_size = Some(Vec2::new(30.0 * fixed_math::to_f32(stamina_ratio), 2.0));
+            }
+        }
+    }
+}
@@ -5,6 +5,8 @@ pub mod enemy;
 pub mod health;
 pub mod movement;
 pub mod player;
+pub mod stamina;
+pub mod status_effect;
 
 use animation::set_sprite_flip;
 use bevy::prelude::*;
@@ -21,7 +23,8 @@ use crate::{
         enemy::{
             ai::{
                 // New AI behavior systems
-                behavior::{enemy_target_selection, enemy_attack_system},
+                behavior::{enemy_target_selection, enemy_attack_system, enemy_ranged_attack_system},
+                behavior_tree::{BehaviorTree, BehaviorTreeState, enemy_behavior_tree_system},
                 combat::ZombieCombatConfig,
                 pathing::{
                     move_enemies, update_enemy_targets,
@@ -29,8 +32,13 @@ use crate::{
                 },
                 // Flow field navigation
                 navigation::{FlowFieldCache, FlowFieldConfig, update_flow_field_system},
+                room_graph::{RoomGraph, update_room_graph_system},
+                steering::SteeringConfig,
+                lod::{AiLodConfig, EnemyLod, update_enemy_lod_system},
+                vault::{VaultConfig, WindowVault, enemy_window_vault_system},
+                perception::{NoiseConfig, NoiseEvents},
                 obstacle::{Obstacle, ObstacleAttackEvent, ObstacleDestroyedEvent, process_obstacle_damage},
-                state::{EnemyAiConfig, EnemyTarget, MonsterState},
+                state::{EnemyAiConfig, EnemyTarget, MonsterState, RangedAttackState},
                 debug::{
                     FlowFieldDebug, EnemyStateDebug,
                     toggle_flow_field_debug, draw_flow_field_debug,
@@ -41,21 +49,30 @@ use crate::{
             Enemy,
         },
         health::{
+            modifiers::DamageModifiers,
             rollback_apply_accumulated_damage, rollback_apply_death, rollback_health_regeneration,
-            ui::update_health_bars, DamageAccumulator, Death, Health, HealthRegen,
+            ui::{update_health_bars, update_stamina_bars},
+            DamageAccumulator, Death, Health, HealthRegen,
         },
         movement::{apply_knockback_damping, KnockbackDampingConfig, SprintState, Velocity},
         player::{
             control::PlayerAction,
+            downed::{rollback_bleed_out, rollback_down_players, update_downed_labels, Downed, DownedConfig},
             input::{
                 apply_friction, apply_inputs, move_characters, read_local_inputs,
                 update_animation_state, PointerWorldPosition,
             },
+            respawn::{
+                rollback_record_dead_players, rollback_respawn_players, update_spectators,
+                DeadPlayers, RespawnConfig,
+            },
             Player,
         },
+        status_effect::{rollback_tick_status_effects, StatusEffects},
     },
+    game_mode::{ActiveGameMode, EnemySpawning},
     system_set::RollbackSystemSet,
-    waves::WaveModeEnabled,
+    throwable::systems::rollback_lure_enemies,
 };
 
 #[derive(Component, Clone, Copy, Default)]
@@ -65,21 +82,34 @@ pub struct BaseCharacterGamePlugin {}
 
 impl Plugin for BaseCharacterGamePlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins((RonAssetPlugin::<CharacterConfig>::new(&["ron"]),));
+        app.add_plugins((
+            RonAssetPlugin::<CharacterConfig>::new(&["ron"]),
+            RonAssetPlugin::<enemy::archetype::EnemyRegistry>::new(&["ron"]),
+            RonAssetPlugin::<enemy::archetype::EnemyArchetype>::new(&["ron"]),
+        ));
 
         app.add_plugins(InputManagerPlugin::<PlayerAction>::default());
         app.init_resource::<PointerWorldPosition>();
 
         // Resources
         app.init_resource::<PathfindingConfig>();
+        app.init_resource::<SteeringConfig>();
+        app.init_resource::<VaultConfig>();
+        app.init_resource::<AiLodConfig>();
         app.init_resource::<KnockbackDampingConfig>();
         app.init_resource::<ZombieCombatConfig>();
+        app.init_resource::<DownedConfig>();
+        app.init_resource::<RespawnConfig>();
+        app.init_resource::<DeadPlayers>();
         app.add_message::<crate::character::enemy::ai::combat::ZombieWindowAttackEvent>();
         app.add_message::<crate::character::health::PlayerDiedEvent>();
 
         // AI system resources
         app.init_resource::<FlowFieldCache>();
         app.init_resource::<FlowFieldConfig>();
+        app.init_resource::<RoomGraph>();
+        app.init_resource::<NoiseConfig>();
+        app.init_resource::<NoiseEvents>();
 
         // Initialize debug resources with --debug-ai flag if present
         let debug_ai_enabled = app.world().get_resource::<DebugAiConfig>()
@@ -99,6 +129,7 @@ impl Plugin for BaseCharacterGamePlugin {
 
         // Rollback registration
         app.rollback_resource_with_clone::<PathfindingConfig>()
+            .rollback_resource_with_clone::<DeadPlayers>()
             .rollback_resource_with_clone::<KnockbackDampingConfig>()
             .rollback_component_with_clone::<EnemySpawnerComponent>()
             .rollback_component_with_clone::<EnemySpawnerState>()
@@ -108,20 +139,35 @@ impl Plugin for BaseCharacterGamePlugin {
             .rollback_component_with_clone::<EnemyAiConfig>()
             .rollback_component_with_clone::<EnemyTarget>()
             .rollback_component_with_clone::<MonsterState>()
+            .rollback_component_with_clone::<RangedAttackState>()
+            .rollback_component_with_clone::<BehaviorTree>()
+            .rollback_component_with_clone::<BehaviorTreeState>()
+            .rollback_component_with_clone::<WindowVault>()
+            .rollback_component_with_clone::<EnemyLod>()
+            .rollback_component_with_clone::<enemy::archetype::EnemyLoot>()
             .rollback_resource_with_copy::<PointerWorldPosition>()
             .rollback_component_with_clone::<Health>()
             .rollback_component_with_clone::<HealthRegen>()
             .rollback_component_with_clone::<DamageAccumulator>()
+            .rollback_component_with_clone::<DamageModifiers>()
             .rollback_component_with_clone::<DashState>()
             .rollback_component_with_clone::<SprintState>()
             .rollback_component_with_clone::<Velocity>()
             .rollback_component_with_clone::<Death>()
+            .rollback_component_with_clone::<Downed>()
+            .rollback_component_with_clone::<StatusEffects>()
+            .rollback_component_with_clone::<stamina::Stamina>()
             .rollback_component_with_reflect::<Player>()
             .rollback_component_with_reflect::<Enemy>();
 
         // Rollback registration - Flow field cache
         app.rollback_resource_with_clone::<FlowFieldCache>();
         // Note: FlowFieldConfig is not rolled back (static configuration)
+        // Note: RoomGraph is not rolled back (rebuilt from the FlowFieldCache whenever it changes)
+
+        // Rollback registration - Noises heard by the enemies
+        app.rollback_resource_with_clone::<NoiseEvents>();
+        // Note: NoiseConfig is not rolled back (static configuration)
 
         app.add_systems(ReadInputs, read_local_inputs);
 
@@ -131,6 +177,9 @@ impl Plugin for BaseCharacterGamePlugin {
             (
                 set_sprite_flip,
                 update_health_bars,
+                update_stamina_bars,
+                update_downed_labels,
+                update_spectators,
                 // Debug toggles
                 toggle_flow_field_debug,
                 toggle_enemy_state_debug,
@@ -146,13 +195,28 @@ impl Plugin for BaseCharacterGamePlugin {
                 // HANDLE ALL PLAYERS INPUT
                 (apply_inputs,).in_set(RollbackSystemSet::Input),
                 // MOVEMENT CHARACTERS
-                (apply_friction, move_characters.after(apply_friction))
+                (
+                    apply_friction,
+                    move_characters.after(apply_friction),
+                    stamina::rollback_stamina_regeneration,
+                )
                     .in_set(RollbackSystemSet::Movement),
                 // HEALTH
                 (
+                    // Damage over time goes through the damage accumulator
+                    rollback_tick_status_effects.before(rollback_apply_accumulated_damage),
                     rollback_apply_accumulated_damage,
                     rollback_health_regeneration.after(rollback_apply_accumulated_damage),
-                    rollback_apply_death.after(rollback_health_regeneration),
+                    // Players go down instead of dying, and die once they bleed out
+                    rollback_down_players.after(rollback_apply_accumulated_damage),
+                    rollback_bleed_out.after(rollback_down_players),
+                    // Dead players are remembered before being despawned and
+                    // re-created following the game mode respawn rule
+                    rollback_record_dead_players.after(rollback_bleed_out),
+                    rollback_apply_death
+                        .after(rollback_health_regeneration)
+                        .after(rollback_record_dead_players),
+                    rollback_respawn_players.after(rollback_apply_death),
                 )
                     .in_set(RollbackSystemSet::DeathManagement),
                 // KNOCKBACK DAMPING - Apply after weapons (which apply knockback) but before animation/AI
@@ -162,21 +226,34 @@ impl Plugin for BaseCharacterGamePlugin {
                     .before(RollbackSystemSet::EnemyAI),
                 // ANIMATION CRATE
                 (update_animation_state,).in_set(RollbackSystemSet::AnimationUpdates),
-                // SPAWNING (disabled when wave mode is enabled)
+                // SPAWNING (only when the game mode uses the map spawners directly)
                 (enemy_spawn_from_spawners_system,)
-                    .run_if(|wave_mode: Res<WaveModeEnabled>| !wave_mode.0)
+                    .run_if(|game_mode: Res<ActiveGameMode>| {
+                        game_mode.rules.enemy_spawning == EnemySpawning::Spawners
+                    })
                     .in_set(RollbackSystemSet::EnemySpawning),
                 // FLOW FIELD UPDATE (runs before EnemyAI)
-                (update_flow_field_system,)
+                (
+                    update_flow_field_system,
+                    update_room_graph_system.after(update_flow_field_system),
+                )
                     .after(RollbackSystemSet::EnemySpawning)
                     .before(RollbackSystemSet::EnemyAI),
                 // ENEMY AI - Flow field navigation with collision
                 // Uses new behavior.rs systems with MonsterState/EnemyTarget
                 (
-                    enemy_target_selection,
+                    update_enemy_lod_system,
+                    enemy_target_selection.after(update_enemy_lod_system),
+                    enemy_behavior_tree_system
+                        .after(rollback_lure_enemies)
+                        .before(update_enemy_targets),
                     update_enemy_targets.after(enemy_target_selection),
-                    move_enemies.after(update_enemy_targets),
+                    enemy_window_vault_system.after(update_enemy_targets),
+                    move_enemies
+                        .after(update_enemy_targets)
+                        .after(enemy_window_vault_system),
                     enemy_attack_system.after(move_enemies),
+                    enemy_ranged_attack_system.after(enemy_attack_system),
                 )
                     .in_set(RollbackSystemSet::EnemyAI),
                 // OBSTACLE DAMAGE PROCESSING
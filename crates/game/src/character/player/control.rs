@@ -21,6 +21,7 @@ pub enum PlayerAction {
 
     Reload,
     MeleeAttack,
+    ThrowGrenade,
 
     Modifier,
 
@@ -56,6 +57,7 @@ pub fn get_input_map() -> InputMap<PlayerAction> {
         (PlayerAction::SwitchWeaponMode, KeyCode::KeyZ),
         (PlayerAction::Reload, KeyCode::KeyR),
         (PlayerAction::MeleeAttack, KeyCode::KeyF),
+        (PlayerAction::ThrowGrenade, KeyCode::KeyG),
         (PlayerAction::MoveCameraRight, KeyCode::ArrowRight),
         (PlayerAction::Sprint, KeyCode::ShiftLeft),
         (PlayerAction::Dash, KeyCode::KeyC),
@@ -69,6 +71,7 @@ pub fn get_input_map() -> InputMap<PlayerAction> {
     map.insert(PlayerAction::Interaction, GamepadButton::North);
     map.insert(PlayerAction::Reload, GamepadButton::West);
     map.insert(PlayerAction::MeleeAttack, GamepadButton::East);
+    map.insert(PlayerAction::ThrowGrenade, GamepadButton::RightTrigger);
     // Add more bindings...
     map.insert(PlayerAction::PointerClick, MouseButton::Left);
 
@@ -11,6 +11,8 @@ use harmonium_bevy::components::AiDriver;
 use crate::{
     character::{config::CharacterConfig, create::create_character},
     collider::{CollisionLayer, CollisionSettings},
+    economy::Points,
+    game_mode::GameModeRules,
     global_asset::GlobalAsset,
     weapons::{melee::{spawn_melee_weapon_for_character, MeleeWeaponsConfig}, spawn_weapon_for_player, WeaponInventory, WeaponsConfig},
 };
@@ -29,6 +31,7 @@ const PLAYER_COLORS: &[LinearRgba] = &[
     LinearRgba::BLACK,
 ];
 
+/// Create a player with the starting loadout and points of the game mode.
 pub fn create_player(
     commands: &mut Commands,
     global_assets: &Res<GlobalAsset>,
@@ -47,8 +50,58 @@ pub fn create_player(
     name: String,
     pubkey: String,
 
+    rules: &GameModeRules,
+
     id_factory: &mut ResMut<GgrsNetIdFactory>,
-) {
+) -> Entity {
+    create_player_with_loadout(
+        commands,
+        global_assets,
+        weapons_asset,
+        melee_weapons_asset,
+        character_asset,
+        collision_settings,
+        asset_server,
+        texture_atlas_layouts,
+        sprint_sheet_assets,
+        position,
+        local,
+        handle,
+        name,
+        pubkey,
+        rules.starting_loadout.as_deref(),
+        rules.starting_points,
+        id_factory,
+    )
+}
+
+/// Create a player with only the weapons named in `loadout` (all weapons if `None`)
+/// and `points` points.
+///
+/// Weapons are spawned in name order, the first one is active.
+pub fn create_player_with_loadout(
+    commands: &mut Commands,
+    global_assets: &Res<GlobalAsset>,
+    weapons_asset: &Res<Assets<WeaponsConfig>>,
+    melee_weapons_asset: &Res<Assets<MeleeWeaponsConfig>>,
+    character_asset: &Res<Assets<CharacterConfig>>,
+    collision_settings: &Res<CollisionSettings>,
+    asset_server: &Res<AssetServer>,
+    texture_atlas_layouts: &mut ResMut<Assets<TextureAtlasLayout>>,
+    sprint_sheet_assets: &Res<Assets<SpriteSheetConfig>>,
+
+    position: FixedVec3,
+
+    local: bool,
+    handle: usize,
+    name: String,
+    pubkey: String,
+
+    loadout: Option<&[String]>,
+    points: u32,
+
+    id_factory: &mut ResMut<GgrsNetIdFactory>,
+) -> Entity {
     let player_name = name;
     let player_pubkey = pubkey;
 
@@ -85,7 +138,11 @@ pub fn create_player(
     let mut inventory = WeaponInventory::default();
 
     if let Some(weapons_config) = weapons_asset.get(&global_assets.weapons) {
-        let mut keys: Vec<&String> = weapons_config.0.keys().collect();
+        let mut keys: Vec<&String> = weapons_config
+            .0
+            .keys()
+            .filter(|k| loadout.is_none_or(|loadout| loadout.contains(*k)))
+            .collect();
         keys.sort();
         for (i, k) in keys.iter().enumerate() {
             spawn_weapon_for_player(
@@ -122,6 +179,9 @@ pub fn create_player(
             CursorPosition::default(),
             super::input::InteractionInput::default(),
             crate::interaction::Interactor,
+            Points { current: points },
+            crate::perk::Perks::default(),
+            crate::throwable::Throwables::default(),
             Player {
                 handle,
                 color: PLAYER_COLORS[handle].into(),
@@ -144,6 +204,9 @@ pub fn create_player(
             CursorPosition::default(),
             super::input::InteractionInput::default(),
             crate::interaction::Interactor,
+            Points { current: points },
+            crate::perk::Perks::default(),
+            crate::throwable::Throwables::default(),
             Player {
                 handle,
                 color: PLAYER_COLORS[handle].into(),
@@ -152,4 +215,6 @@ pub fn create_player(
             },
         ));
     }
+
+    entity
 }
@@ -0,0 +1,269 @@
+//! Downed state and teammate revive.
+//!
+//! A player whose health drops to zero is not killed right away: they go down
+//! with a bleed-out timer. While downed they cannot move, can only fire the
+//! pistol and enemies ignore them. A teammate holding interaction next to them
+//! revives them, otherwise they die when the timer runs out.
+//!
+//! GGRS CRITICAL: `Downed` is a rollback component, all changes must happen
+//! inside the `GgrsSchedule`.
+
+use bevy::{log::{tracing::span, Level}, prelude::*};
+use bevy_fixed::fixed_math;
+use bevy_ggrs::Rollback;
+use serde::{Deserialize, Serialize};
+use utils::{frame::FrameCount, net_id::GgrsNetId, order_iter, order_mut_iter};
+
+use crate::{
+    character::{
+        health::{Death, Health},
+        player::Player,
+    },
+    interaction::{Interactable, InteractionEvent, InteractionType},
+    weapons::WeaponInventory,
+};
+
+/// Only weapon a downed player can fire
+pub const DOWNED_WEAPON_NAME: &str = "pistol";
+
+/// Resource that configures the downed state and revives
+#[derive(Resource, Clone, Debug, Serialize, Deserialize)]
+pub struct DownedConfig {
+    /// Frames a downed player survives without being revived
+    pub bleed_out_frames: u32,
+    /// Frames a teammate must hold interaction to revive
+    pub revive_frames: u32,
+    /// Range within which a teammate can revive
+    pub revive_range: fixed_math::Fixed,
+    /// Part of the max health restored on revive (1.0 = full)
+    pub revive_health_ratio: fixed_math::Fixed,
+}
+
+impl Default for DownedConfig {
+    fn default() -> Self {
+        Self {
+            bleed_out_frames: 1800, // 30 seconds at 60 FPS
+            revive_frames: 180,     // 3 seconds at 60 FPS
+            revive_range: fixed_math::new(30.0),
+            revive_health_ratio: fixed_math::new(0.5),
+        }
+    }
+}
+
+/// Component for a player waiting to be revived
+#[derive(Component, Clone, Debug, Serialize, Deserialize)]
+pub struct Downed {
+    /// Frame at which the player dies if not revived
+    pub bleed_out_at_frame: u32,
+    /// Weapon that was active when the player went down, restored on revive
+    pub previous_weapon_index: usize,
+    /// Teammate currently reviving
+    pub reviver: Option<GgrsNetId>,
+    /// Frames the current reviver has been holding interaction
+    pub revive_progress_frames: u32,
+    /// Last frame a revive interaction was received
+    pub last_revive_frame: u32,
+}
+
+impl Downed {
+    /// A revive is in progress if the reviver interacted this frame or the previous one
+    pub fn is_being_revived(&self, frame: u32) -> bool {
+        self.reviver.is_some() && self.last_revive_frame + 1 >= frame
+    }
+}
+
+/// Put the players that received a killing blow in the downed state.
+///
+/// Runs after damage is applied (Death added) but before the entity is despawned.
+pub fn rollback_down_players(
+    frame: Res<FrameCount>,
+    mut commands: Commands,
+    config: Res<DownedConfig>,
+    mut query: Query<
+        (&GgrsNetId, Entity, &mut WeaponInventory),
+        (With<Player>, With<Death>, Without<Downed>, With<Rollback>),
+    >,
+) {
+    let system_span = span!(Level::INFO, "ggrs", f = frame.frame, s = "down_players");
+    let _enter = system_span.enter();
+
+    for (net_id, entity, mut inventory) in order_mut_iter!(query) {
+        let previous_weapon_index = inventory.active_weapon_index;
+
+        // Switch to the pistol if the player owns one
+        if let Some(index) = inventory
+            .weapons
+            .iter()
+            .position(|(_, weapon)| weapon.config.name == DOWNED_WEAPON_NAME)
+        {
+            inventory.active_weapon_index = index;
+            inventory.frame_switched = frame.frame;
+            inventory.clear_reloading();
+        }
+
+        info!(
+            "{} {} is downed, bleeding out at frame {}",
+            frame.as_ref(), net_id, frame.frame + config.bleed_out_frames
+        );
+
+        commands
+            .entity(entity)
+            .remove::<Death>()
+            .insert((
+                Downed {
+                    bleed_out_at_frame: frame.frame + config.bleed_out_frames,
+                    previous_weapon_index,
+                    reviver: None,
+                    revive_progress_frames: 0,
+                    last_revive_frame: 0,
+                },
+                Interactable {
+                    interaction_range: config.revive_range,
+                    interaction_type: InteractionType::Revive,
+                },
+            ));
+    }
+}
+
+/// Kill the downed players whose bleed-out timer ran out.
+pub fn rollback_bleed_out(
+    frame: Res<FrameCount>,
+    mut commands: Commands,
+    query: Query<(&GgrsNetId, Entity, &Downed), (Without<Death>, With<Rollback>)>,
+) {
+    let system_span = span!(Level::INFO, "ggrs", f = frame.frame, s = "bleed_out");
+    let _enter = system_span.enter();
+
+    for (net_id, entity, downed) in order_iter!(query) {
+        if frame.frame >= downed.bleed_out_at_frame {
+            info!("{} {} bled out", frame.as_ref(), net_id);
+            commands.entity(entity).insert(Death { last_hit_by: None });
+        }
+    }
+}
+
+/// System that handles teammates holding interaction on a downed player.
+///
+/// The bleed-out timer is paused while the revive is in progress. Releasing
+/// interaction or another teammate taking over restarts the revive.
+pub fn handle_revive_interaction(
+    frame: Res<FrameCount>,
+    mut commands: Commands,
+    config: Res<DownedConfig>,
+    mut event_reader: MessageReader<InteractionEvent>,
+    mut downed_query: Query<(&GgrsNetId, &mut Downed, &mut Health, &mut WeaponInventory), With<Rollback>>,
+) {
+    let system_span = span!(Level::INFO, "ggrs", f = frame.frame, s = "handle_revive_interaction");
+    let _enter = system_span.enter();
+
+    // Events are delivered in deterministic order from interaction_detection_system
+    for event in event_reader.read() {
+        if event.interaction_type != InteractionType::Revive {
+            continue;
+        }
+
+        let Ok((downed_net_id, mut downed, mut health, mut inventory)) =
+            downed_query.get_mut(event.interactable)
+        else {
+            continue;
+        };
+
+        // Only one teammate progresses the revive per frame
+        if downed.last_revive_frame == frame.frame {
+            continue;
+        }
+
+        if !downed.is_being_revived(frame.frame)
+            || downed.reviver.as_ref() != Some(&event.interactor_net_id)
+        {
+            info!(
+                "{} {} starts reviving {}",
+                frame.as_ref(), event.interactor_net_id, downed_net_id
+            );
+            downed.reviver = Some(event.interactor_net_id.clone());
+            downed.revive_progress_frames = 0;
+        }
+
+        downed.revive_progress_frames += 1;
+        downed.last_revive_frame = frame.frame;
+        downed.bleed_out_at_frame += 1;
+
+        if downed.revive_progress_frames < config.revive_frames {
+            continue;
+        }
+
+        health.current = health.max.saturating_mul(config.revive_health_ratio).max(fixed_math::FIXED_ONE);
+        if downed.previous_weapon_index < inventory.weapons.len() {
+            inventory.active_weapon_index = downed.previous_weapon_index;
+            inventory.frame_switched = frame.frame;
+            inventory.clear_reloading();
+        }
+
+        info!(
+            "{} {} revived by {} with {} health",
+            frame.as_ref(), downed_net_id, event.interactor_net_id, health.current
+        );
+
+        commands
+            .entity(event.interactable)
+            .remove::<Downed>()
+            .remove::<Interactable>();
+    }
+}
+
+/// Component marker for the label floating above a downed player
+#[derive(Component)]
+pub struct DownedLabel(pub Entity);
+
+/// Show the bleed-out timer or the revive progress above every downed player
+pub fn update_downed_labels(
+    mut commands: Commands,
+    frame: Res<FrameCount>,
+    config: Res<DownedConfig>,
+    asset_server: Res<AssetServer>,
+    downed_query: Query<(Entity, &Downed, &fixed_math::FixedTransform3D)>,
+    mut label_query: Query<(Entity, &DownedLabel, &mut Text2d, &mut Transform)>,
+) {
+    // Remove the labels of players that are no longer downed
+    for (label_entity, label, _, _) in label_query.iter() {
+        if !downed_query.contains(label.0) {
+            commands.entity(label_entity).despawn();
+        }
+    }
+
+    for (entity, downed, transform) in downed_query.iter() {
+        let text = if downed.is_being_revived(frame.frame) {
+            format!(
+                "REVIVING {}%",
+                downed.revive_progress_frames * 100 / config.revive_frames.max(1)
+            )
+        } else {
+            format!(
+                "DOWNED {}s",
+                downed.bleed_out_at_frame.saturating_sub(frame.frame).div_ceil(60)
+            )
+        };
+        let translation = fixed_math::fixed_to_vec2(transform.translation.truncate()).extend(10.0)
+            + Vec3::new(0.0, 24.0, 0.0);
+
+        if let Some((_, _, mut label_text, mut label_transform)) = label_query
+            .iter_mut()
+            .find(|(_, label, _, _)| label.0 == entity)
+        {
+            label_text.0 = text;
+            label_transform.translation = translation;
+        } else {
+            commands.spawn((
+                DownedLabel(entity),
+                Text2d::new(text),
+                TextFont {
+                    font: asset_server.load("fonts/FiraMono-Medium.ttf"),
+                    font_size: 10.0,
+                    ..Default::default()
+                },
+                TextColor(Color::srgb(1.0, 0.3, 0.3)),
+                Transform::from_translation(translation),
+            ));
+        }
+    }
+}
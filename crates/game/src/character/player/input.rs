@@ -1,5 +1,5 @@
 use animation::AnimationState;
-use animation::{ActiveLayers, FacingDirection};
+use animation::FacingDirection;
 use bevy::window::PrimaryWindow;
 use bevy::{prelude::*, platform::collections::hash_map::HashMap};
 use bevy_fixed::fixed_math;
@@ -7,13 +7,18 @@ use bevy_ggrs::prelude::*;
 use bevy_ggrs::LocalInputs;
 use leafwing_input_manager::prelude::*;
 use serde::{Deserialize, Serialize};
-use utils::{order_mut_iter, net_id::GgrsNetId};
+use utils::{frame::FrameCount, order_mut_iter, net_id::GgrsNetId};
 
 use crate::character::config::{CharacterConfig, CharacterConfigHandles};
 use crate::character::dash::DashState;
+use crate::character::enemy::ai::vault::WindowVault;
 use crate::character::movement::{SprintState, Velocity};
-use crate::character::player::{control::PlayerAction, Player};
+use crate::character::stamina::Stamina;
+use crate::character::player::{control::PlayerAction, downed::Downed, Player};
+use crate::character::status_effect::StatusEffects;
 use crate::collider::{is_colliding, Collider, CollisionLayer, CollisionSettings};
+use crate::jjrs::GgrsSessionBuilding;
+use crate::perk::Perks;
 use crate::weapons::WeaponInventory;
 
 use super::jjrs::PeerConfig;
@@ -33,6 +38,7 @@ pub const INPUT_MODIFIER: u16 = 1 << 8;
 pub const INPUT_INTERACTION: u16 = 1 << 9;
 pub const INPUT_MELEE_ATTACK: u16 = 1 << 10;
 pub const INPUT_FORCE_CRASH: u16 = 1 << 11;
+pub const INPUT_THROW: u16 = 1 << 12;
 
 const PAN_FACING_THRESHOLD: i16 = 5;
 
@@ -61,6 +67,8 @@ pub struct CursorPosition {
 #[derive(Component, Clone, Copy, Default, Debug, Serialize, Deserialize)]
 pub struct InteractionInput {
     pub is_holding: bool,
+    /// True only on the frame the interaction button went down (for one-shot purchases)
+    pub just_pressed: bool,
 }
 
 fn get_facing_direction(input: &BoxInput) -> FacingDirection {
@@ -105,6 +113,7 @@ pub fn read_local_inputs(
 
     q_window: Query<&Window, With<PrimaryWindow>>,
     q_camera: Query<(&Camera, &GlobalTransform)>,
+    session_building: Option<Res<GgrsSessionBuilding>>,
 ) {
     let mut local_inputs = HashMap::new();
 
@@ -159,6 +168,10 @@ pub fn read_local_inputs(
             input.buttons |= INPUT_MELEE_ATTACK;
         }
 
+        if action_state.pressed(&PlayerAction::ThrowGrenade) {
+            input.buttons |= INPUT_THROW;
+        }
+
         // F12 to force crash (debug)
         if action_state.pressed(&PlayerAction::DebugForceCrash) {
             input.buttons |= INPUT_FORCE_CRASH;
@@ -189,11 +202,19 @@ pub fn read_local_inputs(
         local_inputs.insert(player.handle, input);
     }
 
+    // Dead local players have no entity until they respawn but GGRS still needs their input
+    if let Some(session_building) = session_building {
+        for ggrs_player in session_building.players.iter().filter(|p| p.is_local) {
+            local_inputs.entry(ggrs_player.handle).or_insert_with(BoxInput::default);
+        }
+    }
+
     commands.insert_resource(LocalInputs::<PeerConfig>(local_inputs));
 }
 
 pub fn apply_inputs(
     _commands: Commands,
+    frame: Res<FrameCount>,
     inputs: Res<PlayerInputs<PeerConfig>>,
     character_configs: Res<Assets<CharacterConfig>>,
     mut query: Query<
@@ -204,13 +225,15 @@ pub fn apply_inputs(
             &mut fixed_math::FixedTransform3D,
             &mut DashState,
             &mut Velocity,
-            &mut ActiveLayers,
+            Option<&mut Stamina>,
             &mut FacingDirection,
             &mut CursorPosition,
             &mut SprintState,
             &mut InteractionInput,
             &CharacterConfigHandles,
             &Player,
+            Option<&Downed>,
+            Option<&Perks>,
         ),
         With<Rollback>,
     >,
@@ -222,13 +245,15 @@ pub fn apply_inputs(
         mut transform,
         mut dash_state,
         mut velocity,
-        _active_layers,
+        mut stamina,
         mut facing_direction,
         mut cursor_position,
         mut sprint_state,
         mut interaction_input,
         config_handles,
         player,
+        downed,
+        perks,
     ) in order_mut_iter!(query)
     {
         if let Some(config) = character_configs.get(&config_handles.config) {
@@ -241,7 +266,19 @@ pub fn apply_inputs(
             dash_state.update();
 
             // Update interaction input state
-            interaction_input.is_holding = (input.buttons & INPUT_INTERACTION) != 0;
+            let is_holding = (input.buttons & INPUT_INTERACTION) != 0;
+            interaction_input.just_pressed = is_holding && !interaction_input.is_holding;
+            interaction_input.is_holding = is_holding;
+
+            // Downed players can only aim
+            if downed.is_some() {
+                *facing_direction = get_facing_direction(&input);
+                cursor_position.x = input.pan_x as i32;
+                cursor_position.y = input.pan_y as i32;
+                sprint_state.is_sprinting = false;
+                velocity.main = fixed_math::FixedVec2::ZERO;
+                continue;
+            }
 
             // If currently dashing, directly update position
             if dash_state.is_dashing {
@@ -267,7 +304,14 @@ pub fn apply_inputs(
             }
 
             // Check if player is trying to dash
-            if (input.buttons & INPUT_DASH != 0) && dash_state.can_dash() {
+            // Dashing costs stamina, no dash when it is empty
+            if (input.buttons & INPUT_DASH != 0)
+                && dash_state.can_dash()
+                && stamina.as_deref_mut().is_none_or(|stamina| {
+                    let dash_cost = stamina.dash_cost;
+                    stamina.try_consume(dash_cost, frame.frame)
+                })
+            {
                 // Get looking direction for dash
                 let look_direction = fixed_math::FixedVec2::new(
                     fixed_math::new(input.pan_x as f32),
@@ -297,14 +341,20 @@ pub fn apply_inputs(
                     config.movement.dash_distance,
                     config.movement.dash_duration_frames,
                 );
-                dash_state.set_cooldown(config.movement.dash_cooldown_frames);
+                // Perks shorten the dash cooldown
+                let dash_cooldown_frames = perks.map_or(config.movement.dash_cooldown_frames, |perks| {
+                    perks.dash_cooldown_frames(config.movement.dash_cooldown_frames)
+                });
+                dash_state.set_cooldown(dash_cooldown_frames);
 
                 // Zero out velocity to prevent normal movement physics
                 velocity.main = fixed_math::FixedVec2::ZERO;
                 continue;
             }
 
-            let is_sprinting = input.buttons & INPUT_SPRINT != 0;
+            // Sprinting needs stamina left
+            let is_sprinting = input.buttons & INPUT_SPRINT != 0
+                && stamina.as_deref().is_none_or(|stamina| !stamina.is_empty());
             sprint_state.is_sprinting = is_sprinting;
 
             if is_sprinting {
@@ -335,6 +385,14 @@ pub fn apply_inputs(
             cursor_position.y = input.pan_y as i32;
 
             if direction != fixed_math::FixedVec2::ZERO {
+                // Sprinting drains stamina while moving
+                if is_sprinting {
+                    if let Some(stamina) = stamina.as_deref_mut() {
+                        let sprint_cost = stamina.sprint_cost_per_frame;
+                        stamina.consume(sprint_cost, frame.frame);
+                    }
+                }
+
                 let sprint_multiplier = fixed_math::FIXED_ONE
                     + (config.movement.sprint_multiplier - fixed_math::FIXED_ONE)
                         * sprint_state.sprint_factor;
@@ -380,6 +438,7 @@ pub fn apply_friction(
 }
 
 pub fn move_characters(
+    frame: Res<FrameCount>,
     mut query: Query<
         (
             &GgrsNetId,
@@ -387,6 +446,7 @@ pub fn move_characters(
             &mut Velocity,
             &Collider,
             &CollisionLayer,
+            Option<&StatusEffects>,
         ),
         (With<Rollback>, With<Player>),
     >,
@@ -401,7 +461,7 @@ pub fn move_characters(
         (With<Collider>, Without<Player>, With<Rollback>),
     >,
 ) {
-    for (_net_id, mut transform, mut velocity, player_collider, collision_layer) in order_mut_iter!(query) {
+    for (_net_id, mut transform, mut velocity, player_collider, collision_layer, status_effects) in order_mut_iter!(query) {
         let total_velocity = velocity.main + velocity.knockback;
         let delta_x = total_velocity.x * fixed_math::new(FIXED_TIMESTEP);
         let delta_y = total_velocity.y * fixed_math::new(FIXED_TIMESTEP);
@@ -453,6 +513,10 @@ pub fn move_characters(
             fixed_math::FIXED_ONE
         };
 
+        // Slowed or stunned players move slower (or not at all)
+        let slowdown = status_effects
+            .map_or(slowdown, |status| slowdown * status.speed_multiplier(frame.frame));
+
         let delta_x = delta_x * slowdown;
         let delta_y = delta_y * slowdown;
 
@@ -507,7 +571,13 @@ pub fn move_characters(
     }
 }
 
-pub fn update_animation_state(mut query: Query<(&GgrsNetId, &Velocity, &mut AnimationState), With<Rollback>>) {
+// Enemies climbing through a window keep the climb animation set by the vault
+pub fn update_animation_state(
+    mut query: Query<
+        (&GgrsNetId, &Velocity, &mut AnimationState),
+        (With<Rollback>, Without<WindowVault>),
+    >,
+) {
     for (_net_id, velocity, mut state) in order_mut_iter!(query) {
         let current_state_name = state.0.clone();
         let new_state_name = if (velocity.main + velocity.knockback).length_squared() > 0.5 {
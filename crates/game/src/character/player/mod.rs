@@ -1,7 +1,9 @@
 pub mod control;
 pub mod create;
+pub mod downed;
 pub mod input;
 pub mod jjrs;
+pub mod respawn;
 
 use bevy::prelude::*;
 use ggrs::PlayerHandle;
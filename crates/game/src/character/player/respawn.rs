@@ -0,0 +1,306 @@
+//! Respawn of dead players and spectating while dead.
+//!
+//! A player who bled out is despawned and remembered in `DeadPlayers`. When
+//! the `RespawnRule` of the game mode allows it (next wave, or after a delay
+//! in modes without waves) the player is re-created at its
+//! `PlayerSpawnConfig` location with the respawn loadout and part of its
+//! points. Until then the local player spectates a living teammate.
+//!
+//! GGRS CRITICAL: `DeadPlayers` is a rollback resource and the respawned
+//! entities get their net ids from `GgrsNetIdFactory`, all changes must happen
+//! inside the `GgrsSchedule`. `Spectator` is local only and never rolled back.
+
+use animation::SpriteSheetConfig;
+use bevy::{log::{tracing::span, Level}, prelude::*};
+use bevy_fixed::fixed_math;
+use bevy_ggrs::Rollback;
+use leafwing_input_manager::prelude::ActionState;
+use map::game::entity::map::player_spawn::PlayerSpawnConfig;
+use serde::{Deserialize, Serialize};
+use utils::{frame::FrameCount, net_id::{GgrsNetId, GgrsNetIdFactory}, order_iter};
+
+use crate::{
+    camera::GameCamera,
+    character::{config::CharacterConfig, health::Death},
+    collider::CollisionSettings,
+    economy::Points,
+    game_mode::{ActiveGameMode, RespawnRule},
+    global_asset::GlobalAsset,
+    jjrs::GgrsSessionBuilding,
+    waves::WaveState,
+    weapons::{melee::MeleeWeaponsConfig, WeaponsConfig},
+};
+
+use super::{
+    control::{get_input_map, PlayerAction},
+    create::create_player_with_loadout,
+    Player,
+};
+
+/// Resource that configures respawns
+#[derive(Resource, Clone, Debug, Serialize, Deserialize)]
+pub struct RespawnConfig {
+    /// Part of the points lost on respawn, in percent
+    pub points_penalty_percent: u32,
+    /// Weapons given to a respawned player
+    pub loadout: Vec<String>,
+}
+
+impl Default for RespawnConfig {
+    fn default() -> Self {
+        Self {
+            points_penalty_percent: 10,
+            loadout: vec!["pistol".to_string()],
+        }
+    }
+}
+
+impl RespawnConfig {
+    /// Points kept by a player respawning with `points`
+    pub fn points_after_penalty(&self, points: u32) -> u32 {
+        let penalty = points as u64 * self.points_penalty_percent.min(100) as u64 / 100;
+        points - penalty as u32
+    }
+}
+
+/// A player waiting to respawn
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DeadPlayer {
+    pub handle: usize,
+    /// Points owned when the player died
+    pub points: u32,
+    pub died_at_frame: u32,
+    /// Wave being played when the player died
+    pub died_in_wave: u32,
+}
+
+impl DeadPlayer {
+    /// Whether the player can come back under `rule`
+    pub fn can_respawn(&self, rule: RespawnRule, frame: u32, wave_state: &WaveState) -> bool {
+        match rule {
+            RespawnRule::NextWave => wave_state.current_wave > self.died_in_wave,
+            RespawnRule::AfterFrames(frames) => frame >= self.died_at_frame.saturating_add(frames),
+        }
+    }
+}
+
+/// Players that died and were despawned, sorted by handle
+///
+/// GGRS CRITICAL: Must be registered with `.rollback_resource_with_clone::<DeadPlayers>()`
+#[derive(Resource, Clone, Debug, Default, Serialize, Deserialize)]
+pub struct DeadPlayers {
+    pub players: Vec<DeadPlayer>,
+}
+
+impl DeadPlayers {
+    pub fn is_dead(&self, handle: usize) -> bool {
+        self.players.iter().any(|p| p.handle == handle)
+    }
+
+    pub fn get(&self, handle: usize) -> Option<&DeadPlayer> {
+        self.players.iter().find(|p| p.handle == handle)
+    }
+}
+
+/// Remember the players that are about to be despawned by `rollback_apply_death`.
+pub fn rollback_record_dead_players(
+    frame: Res<FrameCount>,
+    mut dead_players: ResMut<DeadPlayers>,
+    wave_state: Res<WaveState>,
+    query: Query<(&GgrsNetId, &Player, Option<&Points>), (With<Death>, With<Rollback>)>,
+) {
+    let system_span = span!(Level::INFO, "ggrs", f = frame.frame, s = "record_dead_players");
+    let _enter = system_span.enter();
+
+    for (net_id, player, points) in order_iter!(query) {
+        if dead_players.is_dead(player.handle) {
+            continue;
+        }
+
+        info!("{} {} died", frame.as_ref(), net_id);
+
+        dead_players.players.push(DeadPlayer {
+            handle: player.handle,
+            points: points.map(|p| p.current).unwrap_or_default(),
+            died_at_frame: frame.frame,
+            died_in_wave: wave_state.current_wave,
+        });
+    }
+
+    dead_players.players.sort_by_key(|p| p.handle);
+}
+
+/// Re-create the dead players allowed back by the `RespawnRule` of the game mode.
+///
+/// With `RespawnRule::NextWave`, players that died during the current grace
+/// period wait for the next wave.
+pub fn rollback_respawn_players(
+    frame: Res<FrameCount>,
+    mut commands: Commands,
+    config: Res<RespawnConfig>,
+    mut dead_players: ResMut<DeadPlayers>,
+    (wave_state, game_mode): (Res<WaveState>, Res<ActiveGameMode>),
+    session_building: Option<Res<GgrsSessionBuilding>>,
+    spawn_query: Query<(&PlayerSpawnConfig, &GlobalTransform)>,
+    global_assets: Res<GlobalAsset>,
+    weapons_asset: Res<Assets<WeaponsConfig>>,
+    melee_weapons_asset: Res<Assets<MeleeWeaponsConfig>>,
+    character_asset: Res<Assets<CharacterConfig>>,
+    collision_settings: Res<CollisionSettings>,
+    asset_server: Res<AssetServer>,
+    mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
+    sprint_sheet_assets: Res<Assets<SpriteSheetConfig>>,
+    mut id_factory: ResMut<GgrsNetIdFactory>,
+) {
+    let rule = game_mode.rules.respawn;
+    if !dead_players
+        .players
+        .iter()
+        .any(|p| p.can_respawn(rule, frame.frame, &wave_state))
+    {
+        return;
+    }
+
+    let system_span = span!(Level::INFO, "ggrs", f = frame.frame, s = "respawn_players");
+    let _enter = system_span.enter();
+
+    // Map entities are the same on every peer, sort them to pick the same spawn
+    let mut spawns: Vec<(usize, Vec3)> = spawn_query
+        .iter()
+        .map(|(spawn, transform)| (spawn.index, transform.translation()))
+        .collect();
+    spawns.sort_by_key(|(index, _)| *index);
+
+    let (respawning, waiting): (Vec<DeadPlayer>, Vec<DeadPlayer>) = dead_players
+        .players
+        .drain(..)
+        .partition(|p| p.can_respawn(rule, frame.frame, &wave_state));
+    dead_players.players = waiting;
+
+    for dead_player in respawning {
+        let position = spawns
+            .iter()
+            .find(|(index, _)| *index == dead_player.handle)
+            .or(spawns.first())
+            .map(|(_, translation)| fixed_math::vec3_to_fixed(*translation))
+            .unwrap_or(fixed_math::FixedVec3::ZERO);
+
+        let session_player = session_building
+            .as_ref()
+            .and_then(|sb| sb.players.iter().find(|p| p.handle == dead_player.handle));
+        let (local, name, pubkey) = match session_player {
+            Some(p) => (p.is_local, p.name.clone(), p.pubkey.clone()),
+            None => (false, format!("Player {}", dead_player.handle + 1), String::new()),
+        };
+
+        let points = config.points_after_penalty(dead_player.points);
+
+        info!(
+            "{} respawning player {} at {} with {} points",
+            frame.as_ref(), dead_player.handle, position, points
+        );
+
+        create_player_with_loadout(
+            &mut commands,
+            &global_assets,
+            &weapons_asset,
+            &melee_weapons_asset,
+            &character_asset,
+            &collision_settings,
+            &asset_server,
+            &mut texture_atlas_layouts,
+            &sprint_sheet_assets,
+            position,
+            local,
+            dead_player.handle,
+            name,
+            pubkey,
+            Some(&config.loadout),
+            points,
+            &mut id_factory,
+        );
+    }
+}
+
+/// Local-only entity that keeps the camera controls working while the local player is dead
+#[derive(Component)]
+pub struct Spectator {
+    pub handle: usize,
+}
+
+/// Spawn a spectator for every dead local player and remove it once the player respawned.
+///
+/// The spectator is also the HUD text telling who is being watched.
+pub fn update_spectators(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    frame: Res<FrameCount>,
+    game_mode: Res<ActiveGameMode>,
+    dead_players: Res<DeadPlayers>,
+    session_building: Option<Res<GgrsSessionBuilding>>,
+    camera_query: Query<&GameCamera>,
+    player_query: Query<&Player>,
+    mut spectator_query: Query<(Entity, &Spectator, &mut Text)>,
+) {
+    let target_name = camera_query
+        .single()
+        .ok()
+        .and_then(|camera| camera.target_player_id)
+        .and_then(|entity| player_query.get(entity).ok())
+        .map(|player| player.name.clone());
+
+    for (entity, spectator, mut text) in spectator_query.iter_mut() {
+        let Some(dead_player) = dead_players.get(spectator.handle) else {
+            commands.entity(entity).despawn();
+            continue;
+        };
+
+        let respawn_text = match game_mode.rules.respawn {
+            RespawnRule::NextWave => "Respawn next wave".to_string(),
+            RespawnRule::AfterFrames(frames) => {
+                let remaining_frames = dead_player
+                    .died_at_frame
+                    .saturating_add(frames)
+                    .saturating_sub(frame.frame);
+                format!("Respawn in {}s", remaining_frames.div_ceil(60))
+            }
+        };
+        text.0 = match target_name.as_ref() {
+            Some(name) => format!("SPECTATING {}\n{}", name, respawn_text),
+            None => respawn_text,
+        };
+    }
+
+    let Some(session_building) = session_building else {
+        return;
+    };
+
+    for session_player in session_building.players.iter().filter(|p| p.is_local) {
+        let handle = session_player.handle;
+        if !dead_players.is_dead(handle)
+            || spectator_query.iter().any(|(_, spectator, _)| spectator.handle == handle)
+        {
+            continue;
+        }
+
+        commands.spawn((
+            Spectator { handle },
+            ActionState::<PlayerAction>::default(),
+            get_input_map(),
+            Text::new(""),
+            TextFont {
+                font: asset_server.load("fonts/FiraMono-Medium.ttf"),
+                font_size: 16.0,
+                ..Default::default()
+            },
+            TextColor(Color::srgb(1.0, 0.3, 0.3)),
+            TextLayout::new_with_justify(Justify::Center),
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(40.0),
+                width: Val::Percent(100.0),
+                ..default()
+            },
+        ));
+    }
+}
@@ -0,0 +1,93 @@
+//! Stamina spent by sprint, dash and melee attacks.
+//!
+//! Sprinting drains stamina every frame, dashes and melee attacks cost a
+//! fixed amount when started. None of them can start while stamina is
+//! empty. Stamina regenerates once nothing used it for `regen_delay_frames`.
+//!
+//! GGRS CRITICAL: `Stamina` is a rollback component, it must only change
+//! inside the `GgrsSchedule`.
+
+use bevy::{log::{tracing::span, Level}, prelude::*};
+use bevy_fixed::fixed_math;
+use bevy_ggrs::Rollback;
+use serde::{Deserialize, Serialize};
+use utils::{frame::FrameCount, net_id::GgrsNetId, order_mut_iter};
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct StaminaConfig {
+    pub max: fixed_math::Fixed,
+    pub regen_rate: fixed_math::Fixed, // Stamina per second
+    pub regen_delay_frames: u32,       // Frames without use before regen starts
+    #[serde(default)]
+    pub sprint_cost_per_second: fixed_math::Fixed,
+    #[serde(default)]
+    pub dash_cost: fixed_math::Fixed,
+}
+
+/// GGRS CRITICAL: Must be registered with `.rollback_component_with_clone::<Stamina>()`
+#[derive(Component, Clone, Debug, Serialize, Deserialize)]
+pub struct Stamina {
+    pub current: fixed_math::Fixed,
+    pub max: fixed_math::Fixed,
+    pub regen_rate: fixed_math::Fixed,
+    pub regen_delay_frames: u32,
+    pub sprint_cost_per_frame: fixed_math::Fixed,
+    pub dash_cost: fixed_math::Fixed,
+    pub last_use_frame: u32,
+}
+
+impl From<StaminaConfig> for Stamina {
+    fn from(value: StaminaConfig) -> Self {
+        Self {
+            current: value.max,
+            max: value.max,
+            regen_rate: value.regen_rate,
+            regen_delay_frames: value.regen_delay_frames,
+            sprint_cost_per_frame: value.sprint_cost_per_second / fixed_math::new(60.0),
+            dash_cost: value.dash_cost,
+            last_use_frame: 0,
+        }
+    }
+}
+
+impl Stamina {
+    pub fn is_empty(&self) -> bool {
+        self.current <= fixed_math::FIXED_ZERO
+    }
+
+    /// Spend stamina, the last action can overdraw what is left
+    pub fn consume(&mut self, amount: fixed_math::Fixed, frame: u32) {
+        if amount <= fixed_math::FIXED_ZERO {
+            return;
+        }
+        self.current = (self.current - amount).max(fixed_math::FIXED_ZERO);
+        self.last_use_frame = frame;
+    }
+
+    /// Spend stamina if not empty, returns whether the action can happen
+    pub fn try_consume(&mut self, amount: fixed_math::Fixed, frame: u32) -> bool {
+        if self.is_empty() {
+            return false;
+        }
+        self.consume(amount, frame);
+        true
+    }
+}
+
+// SYSTEM: STAMINA REGEN
+pub fn rollback_stamina_regeneration(
+    frame: Res<FrameCount>,
+    mut query: Query<(&GgrsNetId, &mut Stamina), With<Rollback>>,
+) {
+    let system_span = span!(Level::INFO, "ggrs", f = frame.frame, s = "stamina_regen");
+    let _enter = system_span.enter();
+
+    for (_g_id, mut stamina) in order_mut_iter!(query) {
+        let frames_since_use = frame.frame.saturating_sub(stamina.last_use_frame);
+
+        if frames_since_use >= stamina.regen_delay_frames && stamina.current < stamina.max {
+            let regen_per_frame = stamina.regen_rate / fixed_math::new(60.0);
+            stamina.current = (stamina.current + regen_per_frame).min(stamina.max);
+        }
+    }
+}
@@ -0,0 +1,292 @@
+//! Timed buffs and debuffs on characters.
+//!
+//! A `StatusEffects` component holds at most one entry per kind, each entry
+//! expiring at a given `FrameCount`. Re-applying an effect follows the
+//! stacking rule of its kind (see `StatusEffectKind::stacking`).
+//!
+//! The effects are read through the modifier helpers:
+//! - movement (players and enemies) uses `speed_multiplier`
+//! - `rollback_apply_accumulated_damage` skips damage while `is_invulnerable`
+//! - the AI and weapons skip stunned characters and scale their damage
+//!   with `damage_multiplier`
+//! - `rollback_tick_status_effects` turns burning and poison into damage
+//!
+//! GGRS CRITICAL: `StatusEffects` is a rollback component, effects must only
+//! be applied inside the `GgrsSchedule`. Entries are kept sorted by kind so
+//! the iteration order is deterministic.
+
+use bevy::{log::{tracing::span, Level}, prelude::*};
+use bevy_fixed::fixed_math;
+use bevy_ggrs::Rollback;
+use serde::{Deserialize, Serialize};
+use utils::{frame::FrameCount, net_id::GgrsNetId, order_mut_iter};
+
+use super::health::{DamageAccumulator, HitBy};
+
+/// Damage over time is applied every this many frames
+pub const STATUS_TICK_FRAMES: u32 = 30;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Reflect, Serialize, Deserialize)]
+pub enum StatusEffectKind {
+    /// Damage over time, magnitude is the damage per second of one stack
+    Burning,
+    /// Movement speed multiplier (0.5 = half speed), stacks count the hits
+    Slowed,
+    /// Can't move, attack or fire
+    Stunned,
+    /// Damage over time, magnitude is the damage per second
+    Poisoned,
+    /// Outgoing damage multiplier
+    DamageBoost,
+    /// Ignores all incoming damage
+    Invulnerable,
+}
+
+/// What happens when an effect is applied to a character that already has it
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StatusStacking {
+    /// Keep the strongest magnitude and the latest expiration
+    Refresh,
+    /// Add a stack (up to `max_stacks`) and refresh the duration
+    Stack { max_stacks: u32 },
+    /// Add the new duration to the remaining one, keep the strongest magnitude
+    Extend,
+}
+
+impl StatusEffectKind {
+    pub fn stacking(&self) -> StatusStacking {
+        match self {
+            StatusEffectKind::Burning => StatusStacking::Stack { max_stacks: 5 },
+            StatusEffectKind::Slowed => StatusStacking::Stack { max_stacks: u32::MAX },
+            StatusEffectKind::Poisoned => StatusStacking::Extend,
+            StatusEffectKind::Stunned
+            | StatusEffectKind::DamageBoost
+            | StatusEffectKind::Invulnerable => StatusStacking::Refresh,
+        }
+    }
+
+    /// Whether `new` is a stronger magnitude than `current` for this kind
+    fn is_stronger(&self, new: fixed_math::Fixed, current: fixed_math::Fixed) -> bool {
+        match self {
+            // A lower speed multiplier is a stronger slow
+            StatusEffectKind::Slowed => new < current,
+            _ => new > current,
+        }
+    }
+}
+
+/// A timed effect on a character
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct StatusEffect {
+    pub kind: StatusEffectKind,
+    pub magnitude: fixed_math::Fixed,
+    pub stacks: u32,
+    /// The effect is active until this frame (exclusive)
+    pub expires_at_frame: u32,
+    /// Who applied the effect, credited for damage over time kills
+    pub source: Option<Vec<HitBy>>,
+}
+
+impl StatusEffect {
+    pub fn new(
+        kind: StatusEffectKind,
+        magnitude: fixed_math::Fixed,
+        current_frame: u32,
+        duration_frames: u32,
+    ) -> Self {
+        Self {
+            kind,
+            magnitude,
+            stacks: 1,
+            expires_at_frame: current_frame + duration_frames,
+            source: None,
+        }
+    }
+
+    pub fn with_source(mut self, source: Vec<HitBy>) -> Self {
+        self.source = Some(source);
+        self
+    }
+
+    pub fn is_active(&self, frame: u32) -> bool {
+        frame < self.expires_at_frame
+    }
+}
+
+/// Timed effects of a character, sorted by kind
+///
+/// GGRS CRITICAL: Must be registered with `.rollback_component_with_clone::<StatusEffects>()`
+#[derive(Component, Clone, Debug, Default, Serialize, Deserialize)]
+pub struct StatusEffects {
+    pub effects: Vec<StatusEffect>,
+}
+
+impl StatusEffects {
+    /// Apply an effect following the stacking rule of its kind
+    pub fn apply(&mut self, frame: u32, effect: StatusEffect) {
+        let index = match self.effects.binary_search_by_key(&effect.kind, |e| e.kind) {
+            Ok(index) => index,
+            Err(index) => {
+                self.effects.insert(index, effect);
+                return;
+            }
+        };
+
+        let current = &mut self.effects[index];
+
+        // An expired entry not yet cleaned up is simply replaced
+        if !current.is_active(frame) {
+            *current = effect;
+            return;
+        }
+
+        let kind = effect.kind;
+        match kind.stacking() {
+            StatusStacking::Refresh => {
+                if kind.is_stronger(effect.magnitude, current.magnitude) {
+                    current.magnitude = effect.magnitude;
+                }
+                current.expires_at_frame = current.expires_at_frame.max(effect.expires_at_frame);
+            }
+            StatusStacking::Stack { max_stacks } => {
+                current.stacks = current.stacks.saturating_add(1).min(max_stacks);
+                if kind.is_stronger(effect.magnitude, current.magnitude) {
+                    current.magnitude = effect.magnitude;
+                }
+                current.expires_at_frame = current.expires_at_frame.max(effect.expires_at_frame);
+            }
+            StatusStacking::Extend => {
+                if kind.is_stronger(effect.magnitude, current.magnitude) {
+                    current.magnitude = effect.magnitude;
+                }
+                let duration = effect.expires_at_frame.saturating_sub(frame);
+                current.expires_at_frame += duration;
+            }
+        }
+
+        // The latest source gets the credit
+        if effect.source.is_some() {
+            current.source = effect.source;
+        }
+    }
+
+    /// Active effect of the given kind
+    pub fn get(&self, kind: StatusEffectKind, frame: u32) -> Option<&StatusEffect> {
+        self.effects
+            .iter()
+            .find(|effect| effect.kind == kind && effect.is_active(frame))
+    }
+
+    pub fn has(&self, kind: StatusEffectKind, frame: u32) -> bool {
+        self.get(kind, frame).is_some()
+    }
+
+    pub fn is_stunned(&self, frame: u32) -> bool {
+        self.has(StatusEffectKind::Stunned, frame)
+    }
+
+    pub fn is_invulnerable(&self, frame: u32) -> bool {
+        self.has(StatusEffectKind::Invulnerable, frame)
+    }
+
+    /// Movement speed multiplier, zero while stunned
+    pub fn speed_multiplier(&self, frame: u32) -> fixed_math::Fixed {
+        if self.is_stunned(frame) {
+            return fixed_math::FIXED_ZERO;
+        }
+        self.get(StatusEffectKind::Slowed, frame)
+            .map_or(fixed_math::FIXED_ONE, |effect| effect.magnitude)
+    }
+
+    /// Outgoing damage multiplier
+    pub fn damage_multiplier(&self, frame: u32) -> fixed_math::Fixed {
+        self.get(StatusEffectKind::DamageBoost, frame)
+            .map_or(fixed_math::FIXED_ONE, |effect| effect.magnitude)
+    }
+
+    /// Damage per second of all the damage over time effects
+    pub fn damage_per_second(&self, frame: u32) -> fixed_math::Fixed {
+        self.effects
+            .iter()
+            .filter(|effect| effect.is_active(frame))
+            .filter_map(|effect| match effect.kind {
+                StatusEffectKind::Burning | StatusEffectKind::Poisoned => Some(
+                    effect
+                        .magnitude
+                        .saturating_mul(fixed_math::Fixed::from_num(effect.stacks)),
+                ),
+                _ => None,
+            })
+            .fold(fixed_math::FIXED_ZERO, |total, dps| total.saturating_add(dps))
+    }
+
+    /// Source of the most recent damage over time effect
+    fn damage_over_time_source(&self, frame: u32) -> Option<Vec<HitBy>> {
+        self.effects
+            .iter()
+            .filter(|effect| effect.is_active(frame))
+            .filter(|effect| {
+                matches!(effect.kind, StatusEffectKind::Burning | StatusEffectKind::Poisoned)
+            })
+            .find_map(|effect| effect.source.clone())
+    }
+
+    pub fn remove(&mut self, kind: StatusEffectKind) {
+        self.effects.retain(|effect| effect.kind != kind);
+    }
+
+    pub fn remove_expired(&mut self, frame: u32) {
+        self.effects.retain(|effect| effect.is_active(frame));
+    }
+}
+
+// SYSTEM: STATUS EFFECTS
+/// Apply damage over time and remove the expired effects.
+///
+/// Runs before `rollback_apply_accumulated_damage`, the damage goes through
+/// the `DamageAccumulator` like any other hit.
+pub fn rollback_tick_status_effects(
+    frame: Res<FrameCount>,
+    mut commands: Commands,
+    mut query: Query<
+        (&GgrsNetId, Entity, &mut StatusEffects, Option<&mut DamageAccumulator>),
+        With<Rollback>,
+    >,
+) {
+    let system_span = span!(Level::INFO, "ggrs", f = frame.frame, s = "status_effects");
+    let _enter = system_span.enter();
+
+    for (g_id, entity, mut status_effects, opt_accumulator) in order_mut_iter!(query) {
+        if status_effects.effects.is_empty() {
+            continue;
+        }
+
+        if frame.frame % STATUS_TICK_FRAMES == 0 {
+            let damage = status_effects
+                .damage_per_second(frame.frame)
+                .saturating_mul(fixed_math::Fixed::from_num(STATUS_TICK_FRAMES))
+                / fixed_math::new(60.0);
+
+            if damage > fixed_math::FIXED_ZERO {
+                let last_hit_by = status_effects.damage_over_time_source(frame.frame);
+                info!("{} status effects deal {} dmg", g_id, damage);
+
+                if let Some(mut accumulator) = opt_accumulator {
+                    accumulator.total_damage = accumulator.total_damage.saturating_add(damage);
+                    accumulator.hit_count += 1;
+                    if accumulator.last_hit_by.is_none() {
+                        accumulator.last_hit_by = last_hit_by;
+                    }
+                } else {
+                    commands.entity(entity).insert(DamageAccumulator {
+                        hit_count: 1,
+                        total_damage: damage,
+                        last_hit_by,
+                    });
+                }
+            }
+        }
+
+        status_effects.remove_expired(frame.frame);
+    }
+}
@@ -61,6 +61,7 @@ pub struct CollisionSettings {
     pub wall_layer: usize,
     pub window_layer: usize,
     pub bullet_layer: usize,
+    pub enemy_bullet_layer: usize,
     pub layer_matrix: [[bool; 8]; 8], // Collision matrix for which layers collide
 }
 
@@ -76,6 +77,7 @@ impl Default for CollisionSettings {
         let wall_layer = 4;
         let window_layer = 5;
         let bullet_layer = 6;
+        let enemy_bullet_layer = 7;
 
         // Set up collision relationships
         layer_matrix[enemy_layer][wall_layer] = true;
@@ -89,9 +91,12 @@ impl Default for CollisionSettings {
         layer_matrix[enemy_layer][player_layer] = true;
         layer_matrix[player_layer][enemy_layer] = true;
 
-        // Window layer only collides with player bodies (not bullets or enemies)
+        // Window layer only collides with bodies (not bullets), enemies climb
+        // through the broken ones instead of walking
         layer_matrix[window_layer][player_layer] = true;
         layer_matrix[player_layer][window_layer] = true;
+        layer_matrix[window_layer][enemy_layer] = true;
+        layer_matrix[enemy_layer][window_layer] = true;
 
         // Bullets collide with walls and enemies, but not windows or players
         layer_matrix[bullet_layer][wall_layer] = true;
@@ -99,6 +104,12 @@ impl Default for CollisionSettings {
         layer_matrix[bullet_layer][enemy_layer] = true;
         layer_matrix[enemy_layer][bullet_layer] = true;
 
+        // Enemy projectiles collide with walls and players, they pass over windows
+        layer_matrix[enemy_bullet_layer][wall_layer] = true;
+        layer_matrix[wall_layer][enemy_bullet_layer] = true;
+        layer_matrix[enemy_bullet_layer][player_layer] = true;
+        layer_matrix[player_layer][enemy_bullet_layer] = true;
+
         Self {
             enemy_layer,
             environment_layer,
@@ -106,6 +117,7 @@ impl Default for CollisionSettings {
             wall_layer,
             window_layer,
             bullet_layer,
+            enemy_bullet_layer,
             layer_matrix,
         }
     }
@@ -19,7 +19,7 @@ use utils::{
 };
 
 use crate::{
-    audio::ZAudioPlugin, camera::CameraControlPlugin, character::{player::jjrs::PeerConfig, BaseCharacterGamePlugin}, collider::{debug::DebugColliderGamePlugin, BaseColliderGamePlugin}, frame::{increase_frame_system, FrameDebugUIPlugin}, global_asset::{add_global_asset, loading_asset_system}, jjrs::{local::{setup_ggrs_local, system_after_map_loaded_local}, log_ggrs_events, p2p::{start_matchbox_socket, system_after_map_loaded, wait_for_players}, GggrsSessionConfigurationState, GameDisconnectedEvent}, light::ZLightPlugin, system_set::RollbackSystemSet, ui::GameUiPlugin, waves::WaveSystemPlugin, weapons::BaseWeaponGamePlugin
+    audio::ZAudioPlugin, camera::CameraControlPlugin, character::{player::jjrs::PeerConfig, BaseCharacterGamePlugin}, collider::{debug::DebugColliderGamePlugin, BaseColliderGamePlugin}, frame::{increase_frame_system, FrameDebugUIPlugin}, economy::EconomyPlugin, game_mode::GameModePlugin, global_asset::{add_global_asset, loading_asset_system}, jjrs::{local::{setup_ggrs_local, system_after_map_loaded_local}, log_ggrs_events, p2p::{start_matchbox_socket, system_after_map_loaded, wait_for_players}, GggrsSessionConfigurationState, GameDisconnectedEvent}, light::ZLightPlugin, mystery_box::MysteryBoxPlugin, perk::PerkPlugin, power::PowerPlugin, powerup::PowerUpPlugin, room::RoomPlugin, system_set::RollbackSystemSet, throwable::ThrowablePlugin, ui::GameUiPlugin, waves::WaveSystemPlugin, weapons::BaseWeaponGamePlugin
 };
 
 
@@ -87,8 +87,16 @@ impl Plugin for CoreSetupPlugin {
         app.add_plugins(DebugColliderGamePlugin);
         app.add_plugins(BaseCharacterGamePlugin {});
         app.add_plugins(crate::interaction::InteractionPlugin);
+        app.add_plugins(RoomPlugin);
+        app.add_plugins(PowerPlugin);
         app.add_plugins(GameUiPlugin);
+        app.add_plugins(GameModePlugin);
         app.add_plugins(WaveSystemPlugin);
+        app.add_plugins(EconomyPlugin);
+        app.add_plugins(MysteryBoxPlugin);
+        app.add_plugins(PowerUpPlugin);
+        app.add_plugins(PerkPlugin);
+        app.add_plugins(ThrowablePlugin);
 
         #[cfg(feature = "debug_ui")]
         app.add_plugins(EguiPlugin::default());
@@ -0,0 +1,186 @@
+//! Per-player points economy.
+//!
+//! Players earn points by killing enemies and spend them on paid
+//! interactables (mystery box, ...).
+//!
+//! GGRS CRITICAL: `Points` is a rollback component, all changes must happen
+//! inside the `GgrsSchedule`.
+
+use bevy::{log::{tracing::span, Level}, prelude::*};
+use bevy_ggrs::{GgrsSchedule, Rollback, RollbackApp};
+use serde::{Deserialize, Serialize};
+use utils::{frame::FrameCount, net_id::GgrsNetId, order_iter, order_mut_iter};
+
+use crate::{
+    character::{
+        enemy::{archetype::EnemyLoot, Enemy},
+        health::{rollback_apply_accumulated_damage, rollback_apply_death, Death, HitBy},
+        player::{LocalPlayer, Player},
+    },
+    core::AppState,
+    game_mode::ActiveGameMode,
+    powerup::PowerUpState,
+    system_set::RollbackSystemSet,
+};
+
+/// Points a player starts the game with
+pub const DEFAULT_STARTING_POINTS: u32 = 500;
+
+/// Points owned by a player
+#[derive(Component, Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct Points {
+    pub current: u32,
+}
+
+impl Default for Points {
+    fn default() -> Self {
+        Self {
+            current: DEFAULT_STARTING_POINTS,
+        }
+    }
+}
+
+impl Points {
+    pub fn can_afford(&self, cost: u32) -> bool {
+        self.current >= cost
+    }
+
+    /// Remove `cost` points if the player can afford it, return false otherwise
+    pub fn try_spend(&mut self, cost: u32) -> bool {
+        if !self.can_afford(cost) {
+            return false;
+        }
+        self.current -= cost;
+        true
+    }
+
+    pub fn earn(&mut self, amount: u32) {
+        self.current = self.current.saturating_add(amount);
+    }
+}
+
+/// Resource that configures how many points are rewarded
+#[derive(Resource, Clone, Debug, Serialize, Deserialize)]
+pub struct EconomyConfig {
+    /// Points given to the player landing the killing blow on an enemy,
+    /// unless its archetype has a point value
+    pub kill_reward: u32,
+}
+
+impl Default for EconomyConfig {
+    fn default() -> Self {
+        Self { kill_reward: 100 }
+    }
+}
+
+/// Reward the player that landed the last hit on each dying enemy.
+///
+/// The reward is the point value of the enemy archetype (the configured kill
+/// reward when missing), scaled by the game mode and doubled while the double points
+/// power-up is active.
+///
+/// Runs after damage is applied (Death added) but before the entity is despawned.
+pub fn award_kill_points_system(
+    frame: Res<FrameCount>,
+    config: Res<EconomyConfig>,
+    game_mode: Res<ActiveGameMode>,
+    powerup_state: Res<PowerUpState>,
+    enemy_query: Query<(&GgrsNetId, &Death, Option<&EnemyLoot>), (With<Enemy>, With<Rollback>)>,
+    mut player_query: Query<(&GgrsNetId, &Player, &mut Points), With<Rollback>>,
+) {
+    let system_span = span!(Level::INFO, "ggrs", f = frame.frame, s = "award_kill_points");
+    let _enter = system_span.enter();
+
+    let multiplier = powerup_state.points_multiplier(frame.frame);
+
+    for (enemy_id, death, loot) in order_iter!(enemy_query) {
+        // The most recent player hit gets the kill
+        let Some(killer_handle) = death.last_hit_by.as_ref().and_then(|hits| {
+            hits.iter().rev().find_map(|hit| match hit {
+                HitBy::Player(handle) => Some(*handle),
+                HitBy::Entity(_) | HitBy::Enemy(_) => None,
+            })
+        }) else {
+            continue;
+        };
+
+        let base_reward = loot
+            .and_then(|loot| loot.point_value)
+            .unwrap_or(config.kill_reward);
+        let reward = base_reward * game_mode.rules.kill_reward_percent / 100 * multiplier;
+
+        for (player_id, player, mut points) in order_mut_iter!(player_query) {
+            if player.handle != killer_handle {
+                continue;
+            }
+            points.earn(reward);
+            info!(
+                "{} {} earned {} points for killing {} (total {})",
+                frame.as_ref(), player_id, reward, enemy_id, points.current
+            );
+        }
+    }
+}
+
+/// Component marker for the points HUD text
+#[derive(Component)]
+struct PointsText;
+
+fn setup_points_ui(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let font = asset_server.load("fonts/FiraMono-Medium.ttf");
+
+    commands.spawn((
+        PointsText,
+        Text::new(""),
+        TextFont {
+            font,
+            font_size: 16.0,
+            ..Default::default()
+        },
+        TextColor(Color::srgb(1.0, 0.85, 0.2)),
+        TextLayout::new_with_justify(Justify::Right),
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(3.0),
+            right: Val::Px(5.0),
+            ..default()
+        },
+    ));
+}
+
+fn update_points_text(
+    q_player: Query<&Points, With<LocalPlayer>>,
+    mut q_text: Query<&mut Text, With<PointsText>>,
+) {
+    let Ok(mut text) = q_text.single_mut() else {
+        return;
+    };
+    text.0 = match q_player.single() {
+        Ok(points) => format!("Points: {}", points.current),
+        Err(_) => String::new(),
+    };
+}
+
+pub struct EconomyPlugin;
+
+impl Plugin for EconomyPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<EconomyConfig>();
+
+        app.rollback_component_with_copy::<Points>();
+
+        app.add_systems(
+            GgrsSchedule,
+            award_kill_points_system
+                .after(rollback_apply_accumulated_damage)
+                .before(rollback_apply_death)
+                .in_set(RollbackSystemSet::DeathManagement),
+        );
+
+        app.add_systems(OnEnter(AppState::InGame), setup_points_ui);
+        app.add_systems(
+            Update,
+            update_points_text.run_if(in_state(AppState::InGame)),
+        );
+    }
+}
@@ -0,0 +1,245 @@
+//! Pluggable game modes.
+//!
+//! # Overview
+//!
+//! A game mode owns the rules of a match:
+//!
+//! - **Win/lose conditions**: systems registered by the mode that set the
+//!   rollback `GameOutcome`
+//! - **Enemy spawning**: which spawners are active (waves, map spawners, none)
+//! - **Starting loadout and economy**: see `GameModeRules`
+//!
+//! The mode is picked in the lobby (`--game-mode` on native, `data-game-mode`
+//! on the web canvas) and stored in `ActiveGameMode` before the game loads.
+//! The peers exchange it in the online lobby and don't start the game when
+//! they disagree.
+//!
+//! # Usage
+//!
+//! Games built on the engine register their own modes after `CoreSetupPlugin`:
+//!
+//! ```rust,ignore
+//! struct MyMode;
+//!
+//! impl GameMode for MyMode {
+//!     fn id(&self) -> &'static str { "my_mode" }
+//!     fn display_name(&self) -> &'static str { "My Mode" }
+//!     fn rules(&self) -> GameModeRules { GameModeRules::default() }
+//!     fn build(&self, app: &mut App) {
+//!         app.add_systems(
+//!             GgrsSchedule,
+//!             my_win_condition
+//!                 .run_if(game_mode_active("my_mode"))
+//!                 .in_set(RollbackSystemSet::DeathManagement),
+//!         );
+//!     }
+//! }
+//!
+//! app.add_game_mode(MyMode);
+//! ```
+//!
+//! GGRS CRITICAL: `ActiveGameMode` never changes once the game is loading, so
+//! run conditions based on it are deterministic.
+
+pub mod modes;
+pub mod outcome;
+
+use bevy::prelude::*;
+use bevy_ggrs::RollbackApp;
+
+use crate::{core::AppState, jjrs::GggrsSessionConfiguration, waves::WaveModeEnabled};
+
+pub use modes::{EndlessHordeMode, TimedSurvivalMode, WavesMode};
+pub use outcome::GameOutcome;
+
+/// Spawners driving the enemies of a game mode
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EnemySpawning {
+    /// `WaveSystemPlugin` spawns waves of enemies from the map spawners
+    Waves,
+    /// Map spawners spawn enemies on their own cooldown
+    Spawners,
+    /// No enemies are spawned by the engine
+    None,
+}
+
+/// When the dead players come back
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RespawnRule {
+    /// Once the next wave starts (`WaveState::current_wave` moved past the
+    /// wave the player died in)
+    NextWave,
+    /// A fixed number of frames after dying, for modes without waves
+    AfterFrames(u32),
+}
+
+/// Rules applied by the engine for a game mode
+#[derive(Debug, Clone)]
+pub struct GameModeRules {
+    pub enemy_spawning: EnemySpawning,
+    /// Grace period and delay between waves, without them waves chain back to back
+    pub wave_breaks: bool,
+    /// Weapons given to the players at the start, every weapon if `None`
+    pub starting_loadout: Option<Vec<String>>,
+    /// Points the players start with
+    pub starting_points: u32,
+    /// Part of the economy kill reward given for a kill, in percent
+    pub kill_reward_percent: u32,
+    pub respawn: RespawnRule,
+}
+
+impl Default for GameModeRules {
+    fn default() -> Self {
+        Self {
+            enemy_spawning: EnemySpawning::Waves,
+            wave_breaks: true,
+            starting_loadout: None,
+            starting_points: crate::economy::DEFAULT_STARTING_POINTS,
+            kill_reward_percent: 100,
+            respawn: RespawnRule::NextWave,
+        }
+    }
+}
+
+/// Contract implemented by every game mode
+pub trait GameMode: Send + Sync + 'static {
+    /// Unique id used to select the mode in the lobby
+    fn id(&self) -> &'static str;
+    /// Name shown to the players
+    fn display_name(&self) -> &'static str;
+    fn rules(&self) -> GameModeRules;
+    /// Register the systems of the mode (win/lose conditions, HUD, ...).
+    ///
+    /// Systems must be gated with `game_mode_active(self.id())`.
+    fn build(&self, app: &mut App);
+}
+
+/// A game mode known by the engine
+#[derive(Debug, Clone)]
+pub struct RegisteredGameMode {
+    pub id: &'static str,
+    pub display_name: &'static str,
+    pub rules: GameModeRules,
+}
+
+/// Every game mode that can be selected in the lobby
+#[derive(Resource, Debug, Default)]
+pub struct GameModeRegistry {
+    pub modes: Vec<RegisteredGameMode>,
+}
+
+impl GameModeRegistry {
+    pub fn get(&self, id: &str) -> Option<&RegisteredGameMode> {
+        self.modes.iter().find(|mode| mode.id == id)
+    }
+}
+
+/// Game mode played in the current match
+#[derive(Resource, Debug, Clone)]
+pub struct ActiveGameMode {
+    pub id: String,
+    pub display_name: String,
+    pub rules: GameModeRules,
+}
+
+impl From<&RegisteredGameMode> for ActiveGameMode {
+    fn from(mode: &RegisteredGameMode) -> Self {
+        Self {
+            id: mode.id.to_string(),
+            display_name: mode.display_name.to_string(),
+            rules: mode.rules.clone(),
+        }
+    }
+}
+
+impl Default for ActiveGameMode {
+    fn default() -> Self {
+        Self {
+            id: WavesMode.id().to_string(),
+            display_name: WavesMode.display_name().to_string(),
+            rules: WavesMode.rules(),
+        }
+    }
+}
+
+/// Run condition: the given game mode is the active one
+pub fn game_mode_active(id: &'static str) -> impl FnMut(Res<ActiveGameMode>) -> bool + Clone {
+    move |active: Res<ActiveGameMode>| active.id == id
+}
+
+/// Extension to register game modes on the app
+pub trait GameModeAppExt {
+    fn add_game_mode<M: GameMode>(&mut self, mode: M) -> &mut Self;
+}
+
+impl GameModeAppExt for App {
+    fn add_game_mode<M: GameMode>(&mut self, mode: M) -> &mut Self {
+        mode.build(self);
+
+        let mut registry = self
+            .world_mut()
+            .get_resource_or_insert_with(GameModeRegistry::default);
+        if registry.get(mode.id()).is_some() {
+            warn!("Game mode {} is registered twice", mode.id());
+        }
+        registry.modes.push(RegisteredGameMode {
+            id: mode.id(),
+            display_name: mode.display_name(),
+            rules: mode.rules(),
+        });
+
+        self
+    }
+}
+
+/// Plugin that adds the game mode selection and the built-in modes.
+pub struct GameModePlugin;
+
+impl Plugin for GameModePlugin {
+    fn build(&self, app: &mut App) {
+        // Resources
+        app.init_resource::<GameModeRegistry>();
+        app.init_resource::<ActiveGameMode>();
+        app.init_resource::<GameOutcome>();
+
+        // Rollback registration
+        app.rollback_resource_with_copy::<GameOutcome>();
+
+        // Built-in modes
+        app.add_game_mode(WavesMode)
+            .add_game_mode(EndlessHordeMode)
+            .add_game_mode(TimedSurvivalMode::default());
+
+        // The lobby configuration is known once the global assets are loaded
+        app.add_systems(OnExit(AppState::Loading), select_game_mode);
+    }
+}
+
+/// Resolve the game mode requested in the lobby configuration
+fn select_game_mode(
+    mut commands: Commands,
+    registry: Res<GameModeRegistry>,
+    session_config: Option<Res<GggrsSessionConfiguration>>,
+) {
+    let requested = session_config
+        .as_ref()
+        .map(|config| config.game_mode.as_str())
+        .unwrap_or_default();
+
+    let active = match registry.get(requested) {
+        Some(mode) => ActiveGameMode::from(mode),
+        None => {
+            if !requested.is_empty() {
+                warn!("Unknown game mode {}, falling back to waves", requested);
+            }
+            ActiveGameMode::default()
+        }
+    };
+
+    info!("Game mode {} selected", active.display_name);
+
+    commands.insert_resource(WaveModeEnabled(
+        active.rules.enemy_spawning == EnemySpawning::Waves,
+    ));
+    commands.insert_resource(active);
+}
@@ -0,0 +1,205 @@
+//! Built-in game modes.
+//!
+//! - **Waves**: waves of enemies with a break between them, lost when every player is down
+//! - **Endless Horde**: waves chained back to back without breaks
+//! - **Timed Survival**: map spawners keep spawning, won by surviving until the timer ends
+
+use bevy::{log::{tracing::span, Level}, prelude::*};
+use bevy_ggrs::GgrsSchedule;
+use utils::frame::FrameCount;
+
+use crate::{
+    character::player::respawn::rollback_respawn_players,
+    core::AppState,
+    system_set::RollbackSystemSet,
+};
+
+use super::{
+    game_mode_active,
+    outcome::{defeat_when_all_players_down, GameOutcome},
+    EnemySpawning, GameMode, GameModeRules, RespawnRule,
+};
+
+/// Register the shared lose condition for a mode
+fn add_defeat_condition(app: &mut App, id: &'static str) {
+    app.add_systems(
+        GgrsSchedule,
+        defeat_when_all_players_down
+            .run_if(game_mode_active(id))
+            .after(rollback_respawn_players)
+            .in_set(RollbackSystemSet::DeathManagement),
+    );
+}
+
+/// Wave survival, the original mode
+pub struct WavesMode;
+
+impl GameMode for WavesMode {
+    fn id(&self) -> &'static str {
+        "waves"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Waves"
+    }
+
+    fn rules(&self) -> GameModeRules {
+        GameModeRules::default()
+    }
+
+    fn build(&self, app: &mut App) {
+        add_defeat_condition(app, self.id());
+    }
+}
+
+/// Waves without any break, the next wave starts once the current one is spawned
+pub struct EndlessHordeMode;
+
+impl GameMode for EndlessHordeMode {
+    fn id(&self) -> &'static str {
+        "endless"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Endless Horde"
+    }
+
+    fn rules(&self) -> GameModeRules {
+        GameModeRules {
+            wave_breaks: false,
+            starting_points: 1000,
+            kill_reward_percent: 50,
+            ..Default::default()
+        }
+    }
+
+    fn build(&self, app: &mut App) {
+        add_defeat_condition(app, self.id());
+    }
+}
+
+/// Survive until the timer runs out
+pub struct TimedSurvivalMode {
+    pub duration_minutes: u32,
+}
+
+impl Default for TimedSurvivalMode {
+    fn default() -> Self {
+        Self { duration_minutes: 10 }
+    }
+}
+
+/// Duration of the timed survival mode
+#[derive(Resource, Debug, Clone, Copy)]
+pub struct TimedSurvivalConfig {
+    pub duration_frames: u32,
+}
+
+impl TimedSurvivalConfig {
+    pub fn remaining_frames(&self, frame: u32) -> u32 {
+        self.duration_frames.saturating_sub(frame)
+    }
+}
+
+impl GameMode for TimedSurvivalMode {
+    fn id(&self) -> &'static str {
+        "timed"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Timed Survival"
+    }
+
+    fn rules(&self) -> GameModeRules {
+        GameModeRules {
+            enemy_spawning: EnemySpawning::Spawners,
+            // No waves to wait for
+            respawn: RespawnRule::AfterFrames(30 * 60), // 30 seconds at 60 FPS
+            ..Default::default()
+        }
+    }
+
+    fn build(&self, app: &mut App) {
+        app.insert_resource(TimedSurvivalConfig {
+            duration_frames: self.duration_minutes * 60 * 60, // 60 FPS
+        });
+
+        add_defeat_condition(app, self.id());
+        app.add_systems(
+            GgrsSchedule,
+            victory_when_timer_ends
+                .run_if(game_mode_active(self.id()))
+                .after(defeat_when_all_players_down)
+                .in_set(RollbackSystemSet::DeathManagement),
+        );
+
+        app.add_systems(
+            OnEnter(AppState::InGame),
+            setup_survival_timer_ui.run_if(game_mode_active(self.id())),
+        );
+        app.add_systems(
+            Update,
+            update_survival_timer_ui
+                .run_if(game_mode_active(self.id()))
+                .run_if(in_state(AppState::InGame)),
+        );
+    }
+}
+
+/// Win condition: the players survived until the end of the timer
+fn victory_when_timer_ends(
+    frame: Res<FrameCount>,
+    config: Res<TimedSurvivalConfig>,
+    mut outcome: ResMut<GameOutcome>,
+) {
+    if *outcome != GameOutcome::InProgress || config.remaining_frames(frame.frame) > 0 {
+        return;
+    }
+
+    let system_span = span!(Level::INFO, "ggrs", f = frame.frame, s = "game_outcome");
+    let _enter = system_span.enter();
+
+    info!("{} survived until the end of the timer, victory", frame.as_ref());
+    *outcome = GameOutcome::Victory;
+}
+
+/// Component marker for the survival timer HUD text
+#[derive(Component)]
+struct SurvivalTimerText;
+
+fn setup_survival_timer_ui(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn((
+        SurvivalTimerText,
+        Text::new(""),
+        TextFont {
+            font: asset_server.load("fonts/FiraMono-Medium.ttf"),
+            font_size: 20.0,
+            ..Default::default()
+        },
+        TextColor(Color::WHITE),
+        TextLayout::new_with_justify(Justify::Center),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(10.0),
+            width: Val::Percent(100.0),
+            ..default()
+        },
+    ));
+}
+
+fn update_survival_timer_ui(
+    frame: Res<FrameCount>,
+    config: Res<TimedSurvivalConfig>,
+    mut text_query: Query<&mut Text, With<SurvivalTimerText>>,
+) {
+    let Ok(mut text) = text_query.single_mut() else {
+        return;
+    };
+
+    let remaining_seconds = config.remaining_frames(frame.frame).div_ceil(60);
+    text.0 = format!(
+        "SURVIVE {:02}:{:02}",
+        remaining_seconds / 60,
+        remaining_seconds % 60
+    );
+}
@@ -0,0 +1,49 @@
+//! Match outcome shared by every game mode.
+//!
+//! GGRS CRITICAL: `GameOutcome` is a rollback resource, it must only be set
+//! by systems running inside the `GgrsSchedule`.
+
+use bevy::{log::{tracing::span, Level}, prelude::*};
+use bevy_ggrs::Rollback;
+use serde::{Deserialize, Serialize};
+use utils::{frame::FrameCount, net_id::GgrsNetId};
+
+use crate::character::player::{downed::Downed, respawn::DeadPlayers, Player};
+
+/// Result of the match, decided by the active game mode
+///
+/// GGRS CRITICAL: Must be registered with `.rollback_resource_with_copy::<GameOutcome>()`
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GameOutcome {
+    #[default]
+    InProgress,
+    Victory,
+    Defeat,
+}
+
+/// Lose condition: every player is downed or dead.
+///
+/// Runs after the dead players are despawned.
+pub fn defeat_when_all_players_down(
+    frame: Res<FrameCount>,
+    mut outcome: ResMut<GameOutcome>,
+    dead_players: Res<DeadPlayers>,
+    player_query: Query<(&GgrsNetId, Option<&Downed>), (With<Player>, With<Rollback>)>,
+) {
+    if *outcome != GameOutcome::InProgress {
+        return;
+    }
+
+    // Players are not spawned yet
+    if player_query.is_empty() && dead_players.players.is_empty() {
+        return;
+    }
+
+    if player_query.iter().all(|(_, downed)| downed.is_some()) {
+        let system_span = span!(Level::INFO, "ggrs", f = frame.frame, s = "game_outcome");
+        let _enter = system_span.enter();
+
+        info!("{} every player is down, defeat", frame.as_ref());
+        *outcome = GameOutcome::Defeat;
+    }
+}
@@ -4,10 +4,17 @@ use utils::bmap;
 
 use crate::{
     camera::CameraSettingsAsset,
-    character::config::CharacterConfig,
+    character::{
+        config::CharacterConfig,
+        enemy::archetype::{archetype_path, EnemyArchetype, EnemyRegistry, ENEMY_REGISTRY_PATH},
+    },
     core::{AppState, OnlineState},
+    mystery_box::MysteryBoxConfig,
+    perk::PerkConfig,
+    powerup::PowerUpConfig,
+    throwable::ThrowableConfig,
     waves::WaveConfig,
-    weapons::{melee::MeleeWeaponsConfig, WeaponsConfig},
+    weapons::{melee::MeleeWeaponsConfig, upgrade::WeaponUpgradesConfig, WeaponsConfig},
 };
 
 const PLAYER_SPRITESHEET_CONFIG_PATH: &str = "ZombieShooter/Sprites/Character/player_sheet.ron";
@@ -23,8 +30,13 @@ pub struct GlobalAsset {
     pub spritesheets: HashMap<String, HashMap<String, Handle<SpriteSheetConfig>>>,
     pub animations: HashMap<String, Handle<AnimationMapConfig>>,
     pub character_configs: HashMap<String, Handle<CharacterConfig>>,
+    // Enemy types, their config, sprites and animations are added to the maps
+    // above under the archetype id once the archetype is loaded
+    pub enemy_registry: Handle<EnemyRegistry>,
+    pub enemy_archetypes: HashMap<String, Handle<EnemyArchetype>>,
     pub weapons: Handle<WeaponsConfig>,
     pub melee_weapons: Handle<MeleeWeaponsConfig>,
+    pub weapon_upgrades: Handle<WeaponUpgradesConfig>,
     pub camera: Handle<CameraSettingsAsset>,
 
     // Visual effects
@@ -33,6 +45,18 @@ pub struct GlobalAsset {
 
     // Wave spawning config (optional - only loaded when wave mode is used)
     pub wave_config: Option<Handle<WaveConfig>>,
+
+    // Power-up drops config (optional - lives alongside the wave config)
+    pub powerup_config: Option<Handle<PowerUpConfig>>,
+
+    // Mystery box config (optional - only used when the map has box locations)
+    pub mystery_box_config: Option<Handle<MysteryBoxConfig>>,
+
+    // Perk machines config (optional - only used when the map has perk machines)
+    pub perk_config: Option<Handle<PerkConfig>>,
+
+    // Throwables config (optional - players get no throwables without it)
+    pub throwable_config: Option<Handle<ThrowableConfig>>,
 }
 
 impl GlobalAsset {
@@ -53,37 +77,22 @@ impl GlobalAsset {
                 ),
                 "machine_gun" => bmap!(
                     "body" => asset_server.load("ZombieShooter/Sprites/Character/machine_gun_sheet.ron")
-                ),
-                "zombie_1" => bmap!(
-                    "body" => asset_server.load("ZombieShooter/Sprites/Zombie/zombie_sheet.ron"),
-                    "shadow" => asset_server.load("ZombieShooter/Sprites/Character/shadow_sheet.ron")
-                ),
-                "zombie_2" => bmap!(
-                    "body" => asset_server.load("ZombieShooter/Sprites/Zombie/zombie_hard_sheet.ron"),
-                    "shadow" => asset_server.load("ZombieShooter/Sprites/Character/shadow_sheet.ron")
-                ),
-                "zombie_full" => bmap!(
-                    "body" => asset_server.load("ZombieShooter/Sprites/Zombie/zombie_full_sheet.ron"),
-                    "shadow" => asset_server.load("ZombieShooter/Sprites/Character/shadow_sheet.ron")
                 )
             ),
             animations: bmap!(
                 "player" => asset_server.load(PLAYER_ANIMATIONS_CONFIG_PATH),
                 "machine_gun" => asset_server.load(PLAYER_ANIMATIONS_CONFIG_PATH),
                 "pistol" => asset_server.load(PLAYER_ANIMATIONS_CONFIG_PATH),
-                "shotgun" => asset_server.load(PLAYER_ANIMATIONS_CONFIG_PATH),
-                "zombie_1" => asset_server.load("ZombieShooter/Sprites/Zombie/zombie_animation.ron"),
-                "zombie_2" => asset_server.load("ZombieShooter/Sprites/Zombie/zombie_animation.ron"),
-                "zombie_full" => asset_server.load("ZombieShooter/Sprites/Zombie/zombie_full_animation.ron")
+                "shotgun" => asset_server.load(PLAYER_ANIMATIONS_CONFIG_PATH)
             ),
             character_configs: bmap!(
-                "player" => asset_server.load(PLAYER_CONFIG_PATH),
-                "zombie_1" => asset_server.load("ZombieShooter/Sprites/Zombie/zombie_config.ron"),
-                "zombie_2" => asset_server.load("ZombieShooter/Sprites/Zombie/zombie_hard_config.ron"),
-                "zombie_full" => asset_server.load("ZombieShooter/Sprites/Zombie/zombie_full_config.ron")
+                "player" => asset_server.load(PLAYER_CONFIG_PATH)
             ),
+            enemy_registry: asset_server.load(ENEMY_REGISTRY_PATH),
+            enemy_archetypes: HashMap::new(),
             weapons: asset_server.load("ZombieShooter/Sprites/Character/weapons.ron"),
             melee_weapons: asset_server.load("weapons/melee/melee_weapons.ron"),
+            weapon_upgrades: asset_server.load("weapons/upgrades.ron"),
             camera: asset_server.load("camera.ron"),
             
             // Visual effects
@@ -92,6 +101,18 @@ impl GlobalAsset {
 
             // Wave spawning config
             wave_config: Some(asset_server.load("waves/wave_config.ron")),
+
+            // Power-up drops config
+            powerup_config: Some(asset_server.load("waves/powerup_config.ron")),
+
+            // Mystery box config
+            mystery_box_config: Some(asset_server.load("mystery_box/mystery_box_config.ron")),
+
+            // Perk machines config
+            perk_config: Some(asset_server.load("perks/perk_config.ron")),
+
+            // Throwables config
+            throwable_config: Some(asset_server.load("throwables/throwable_config.ron")),
         }
     }
 }
@@ -102,12 +123,72 @@ pub fn add_global_asset(mut commands: Commands, asset_server: Res<AssetServer>)
     commands.insert_resource(global_asset);
 }
 
+/// Request every archetype of the registry, then the character config,
+/// sprites and animations of every loaded archetype.
+///
+/// Returns `false` while some archetypes are not loaded yet.
+fn load_enemy_archetypes(
+    global_assets: &mut GlobalAsset,
+    asset_server: &AssetServer,
+    registry_assets: &Assets<EnemyRegistry>,
+    archetype_assets: &Assets<EnemyArchetype>,
+) -> bool {
+    let Some(registry) = registry_assets.get(&global_assets.enemy_registry) else {
+        return false;
+    };
+
+    for id in registry.archetypes.iter() {
+        if !global_assets.enemy_archetypes.contains_key(id) {
+            let handle = asset_server.load(archetype_path(id));
+            global_assets.enemy_archetypes.insert(id.clone(), handle);
+        }
+    }
+
+    let mut all_loaded = true;
+    let archetypes: Vec<_> = global_assets
+        .enemy_archetypes
+        .iter()
+        .map(|(id, handle)| (id.clone(), handle.clone()))
+        .collect();
+    for (id, handle) in archetypes {
+        let Some(archetype) = archetype_assets.get(&handle) else {
+            all_loaded = false;
+            continue;
+        };
+        if global_assets.character_configs.contains_key(&id) {
+            continue;
+        }
+
+        let layers = archetype
+            .sprite_layers
+            .iter()
+            .map(|(layer, path)| (layer.clone(), asset_server.load(path.clone())))
+            .collect();
+        global_assets.spritesheets.insert(id.clone(), layers);
+        global_assets
+            .animations
+            .insert(id.clone(), asset_server.load(archetype.animations.clone()));
+        global_assets
+            .character_configs
+            .insert(id, asset_server.load(archetype.character_config.clone()));
+    }
+
+    all_loaded
+}
+
 pub fn loading_asset_system(
     mut app_state: ResMut<NextState<AppState>>,
     online: Res<OnlineState>,
-    global_assets: Res<GlobalAsset>,
+    mut global_assets: ResMut<GlobalAsset>,
     asset_server: Res<AssetServer>,
+    registry_assets: Res<Assets<EnemyRegistry>>,
+    archetype_assets: Res<Assets<EnemyArchetype>>,
 ) {
+    // The enemy types add their assets to the maps checked below
+    if !load_enemy_archetypes(&mut global_assets, &asset_server, &registry_assets, &archetype_assets) {
+        return;
+    }
+
     for (_, v) in global_assets.spritesheets.iter() {
         for (_, handle) in v.iter() {
             if !asset_server.load_state(handle).is_loaded() {
@@ -134,6 +215,9 @@ pub fn loading_asset_system(
     if !asset_server.load_state(&global_assets.melee_weapons).is_loaded() {
         return;
     }
+    if !asset_server.load_state(&global_assets.weapon_upgrades).is_loaded() {
+        return;
+    }
     if !asset_server.load_state(&global_assets.camera).is_loaded() {
         return;
     }
@@ -153,6 +237,34 @@ pub fn loading_asset_system(
         }
     }
 
+    // Check power-up config (if loaded)
+    if let Some(powerup_config) = &global_assets.powerup_config {
+        if !asset_server.load_state(powerup_config).is_loaded() {
+            return;
+        }
+    }
+
+    // Check mystery box config (if loaded)
+    if let Some(mystery_box_config) = &global_assets.mystery_box_config {
+        if !asset_server.load_state(mystery_box_config).is_loaded() {
+            return;
+        }
+    }
+
+    // Check perk config (if loaded)
+    if let Some(perk_config) = &global_assets.perk_config {
+        if !asset_server.load_state(perk_config).is_loaded() {
+            return;
+        }
+    }
+
+    // Check throwable config (if loaded)
+    if let Some(throwable_config) = &global_assets.throwable_config {
+        if !asset_server.load_state(throwable_config).is_loaded() {
+            return;
+        }
+    }
+
     if matches!(*online, OnlineState::Online) {
         app_state.set(AppState::LobbyOnline);
     } else {
@@ -7,6 +7,8 @@ use utils::{frame::FrameCount, net_id::GgrsNetId, order_iter};
 use crate::{
     collider::{Collider, CollisionLayer},
     core::AppState,
+    power::{handle_power_switch_interaction, PowerState},
+    room::UnlockedRooms,
     system_set::RollbackSystemSet,
 };
 
@@ -55,7 +57,15 @@ impl Default for Interactable {
 pub enum InteractionType {
     Door,
     Window,
-    // Future: Crate, Weapon, Soda, etc.
+    MysteryBox,
+    UpgradeStation,
+    /// Hold next to a downed teammate to revive them
+    Revive,
+    /// Turns on the power of the map
+    PowerSwitch,
+    /// Perk machine selling a buff
+    Perk,
+    // Future: Weapon, etc.
 }
 
 /// Component that marks an entity as capable of interacting
@@ -107,7 +117,7 @@ pub fn interaction_detection_system(
     mut event_writer: MessageWriter<InteractionEvent>,
     interactors: Query<
         (&GgrsNetId, Entity, &fixed_math::FixedTransform3D, &crate::character::player::input::InteractionInput),
-        (With<Interactor>, With<Rollback>),
+        (With<Interactor>, With<Rollback>, Without<crate::character::player::downed::Downed>),
     >,
     interactables: Query<
         (
@@ -178,6 +188,11 @@ pub fn interaction_detection_system(
             let interaction_type_str = match interaction_type {
                 InteractionType::Door => "Door",
                 InteractionType::Window => "Window",
+                InteractionType::MysteryBox => "MysteryBox",
+                InteractionType::UpgradeStation => "UpgradeStation",
+                InteractionType::Revive => "Revive",
+                InteractionType::PowerSwitch => "PowerSwitch",
+                InteractionType::Perk => "Perk",
             };
             info!("{} interaction detected: interactor {} with {} ({}) at distance_sq {:?}", 
                   frame.as_ref(), interactor_net_id, net_id, interaction_type_str,
@@ -244,7 +259,9 @@ pub fn handle_door_interaction(
     mut event_reader: MessageReader<InteractionEvent>,
     mut door_opened_writer: MessageWriter<DoorOpenedEvent>,
     mut commands: Commands,
-    door_query: Query<(Entity, &map::game::entity::MapRollbackItem, &map::game::entity::map::door::DoorComponent), (With<Interactable>, With<Rollback>)>,
+    mut unlocked_rooms: ResMut<UnlockedRooms>,
+    power: Res<PowerState>,
+    door_query: Query<(Entity, &map::game::entity::MapRollbackItem, &map::game::entity::map::door::DoorComponent, Option<&map::game::entity::map::door::DoorGridPosition>), (With<Interactable>, With<Rollback>)>,
     all_doors_query: Query<(Entity, &GgrsNetId, &map::game::entity::MapRollbackItem, &map::game::entity::map::door::DoorComponent, &map::game::entity::map::door::DoorGridPosition), With<Rollback>>,
 ) {
     let system_span = span!(Level::INFO, "ggrs", f = frame.frame, s = "handle_door_interaction");
@@ -257,7 +274,16 @@ pub fn handle_door_interaction(
         }
 
         // Verify the interactable entity exists and is a rollback entity
-        if let Ok((door_entity, rollback_item, door_component)) = door_query.get(event.interactable) {
+        if let Ok((door_entity, rollback_item, door_component, grid_pos)) = door_query.get(event.interactable) {
+            // Electrified doors only open once the power is on
+            if door_component.config.electrify && !power.on {
+                info!(
+                    "{} door {} requires power, not opened",
+                    frame.as_ref(), event.interactable_net_id
+                );
+                continue;
+            }
+
             info!(
                 "{} door interaction triggered: interactor {} on door {}",
                 frame.as_ref(), event.interactor_net_id, event.interactable_net_id
@@ -280,6 +306,17 @@ pub fn handle_door_interaction(
                 door_net_id: event.interactable_net_id.clone(),
                 visual_entity: rollback_item.parent,
             });
+
+            // Unlock the rooms on both sides of the door
+            let connected_rooms = grid_pos
+                .map(|pos| &pos.level_iid)
+                .into_iter()
+                .chain(door_component.config.paired_door.as_ref().map(|(level_iid, _)| level_iid));
+            for level_iid in connected_rooms {
+                if unlocked_rooms.unlock(level_iid) {
+                    info!("{} room {} unlocked by door {}", frame.as_ref(), level_iid, event.interactable_net_id);
+                }
+            }
             
             // If this door has a paired door, open it too
             if let Some((paired_level_iid, (paired_x, paired_y))) = &door_component.config.paired_door {
@@ -493,8 +530,10 @@ impl Plugin for InteractionPlugin {
             GgrsSchedule,
             (
                 interaction_detection_system,
+                handle_power_switch_interaction,
                 handle_door_interaction,
                 handle_window_repair,
+                crate::character::player::downed::handle_revive_interaction,
             )
                 .chain()
                 .after(RollbackSystemSet::Input)
@@ -604,7 +643,12 @@ pub fn display_interaction_prompts(
     mut text_query: Query<&mut Text, With<InteractionPromptText>>,
     local_interactors: Query<
         &fixed_math::FixedTransform3D,
-        (With<Interactor>, With<Rollback>, With<crate::character::player::LocalPlayer>),
+        (
+            With<Interactor>,
+            With<Rollback>,
+            With<crate::character::player::LocalPlayer>,
+            Without<crate::character::player::downed::Downed>,
+        ),
     >,
     interactables: Query<
         (
@@ -613,20 +657,49 @@ pub fn display_interaction_prompts(
             &Interactable,
             Option<&map::game::entity::map::door::DoorComponent>,
             Option<&map::game::entity::map::window::WindowHealth>,
+            Option<&crate::weapons::upgrade::UpgradeStation>,
+            Option<&crate::perk::PerkMachine>,
+            Option<(&crate::character::player::Player, &crate::character::player::downed::Downed)>,
+        ),
+        (
+            Or<(Without<Interactor>, With<crate::character::player::downed::Downed>)>,
+            With<Rollback>,
         ),
-        (Without<Interactor>, With<Rollback>),
     >,
+    mystery_box_state: Res<crate::mystery_box::MysteryBoxState>,
+    mystery_box_config_assets: Res<Assets<crate::mystery_box::MysteryBoxConfig>>,
+    global_assets: Res<crate::global_asset::GlobalAsset>,
+    upgrades_assets: Res<Assets<crate::weapons::upgrade::WeaponUpgradesConfig>>,
+    perk_config_assets: Res<Assets<crate::perk::PerkConfig>>,
+    frame: Res<FrameCount>,
+    downed_config: Res<crate::character::player::downed::DownedConfig>,
+    power: Res<PowerState>,
 ) {
     // Track the closest door across all LOCAL players
-    // Store: (distance, cost, position, range)
-    let mut closest_door_info: Option<(f32, i32, Vec3, f32)> = None;
+    // Store: (distance, cost, requires power, position, range)
+    let mut closest_door_info: Option<(f32, i32, bool, Vec3, f32)> = None;
     // Track the closest window
     // Store: (distance, current_health, max_health, position, range)
     let mut closest_window_info: Option<(f32, u8, u8, Vec3, f32)> = None;
+    // Track the closest mystery box
+    // Store: (distance, position, range)
+    let mut closest_mystery_box_info: Option<(f32, Vec3, f32)> = None;
+    // Track the closest downed teammate
+    // Store: (distance, name, revive progress frames, position, range)
+    let mut closest_revive_info: Option<(f32, String, u32, Vec3, f32)> = None;
+    // Track the closest upgrade station
+    // Store: (distance, phase, position, range)
+    let mut closest_upgrade_station_info: Option<(f32, crate::weapons::upgrade::UpgradeStationPhase, Vec3, f32)> = None;
+    // Track the closest power switch
+    // Store: (distance, position, range)
+    let mut closest_power_switch_info: Option<(f32, Vec3, f32)> = None;
+    // Track the closest perk machine
+    // Store: (distance, perk id, position, range)
+    let mut closest_perk_info: Option<(f32, String, Vec3, f32)> = None;
     
     // Only check local players
     for interactor_transform in local_interactors.iter() {
-        for (_interactable_entity, interactable_transform, interactable, door_component_opt, window_health_opt) in interactables.iter() {
+        for (_interactable_entity, interactable_transform, interactable, door_component_opt, window_health_opt, upgrade_station_opt, perk_machine_opt, downed_opt) in interactables.iter() {
             // Calculate distance
             let distance_vec = interactable_transform.translation - interactor_transform.translation;
             let distance_sq: fixed_math::FixedWide = distance_vec.length_squared();
@@ -647,18 +720,78 @@ pub fn display_interaction_prompts(
 
                 // Check if it's a door
                 if let Some(door_component) = door_component_opt {
+                    let requires_power = door_component.config.electrify && !power.on;
                     match &closest_door_info {
                         None => {
-                            closest_door_info = Some((distance, door_component.config.cost, pos, interaction_range));
+                            closest_door_info = Some((distance, door_component.config.cost, requires_power, pos, interaction_range));
+                        }
+                        Some((closest_dist, _, _, _, _)) => {
+                            if distance < *closest_dist {
+                                closest_door_info = Some((distance, door_component.config.cost, requires_power, pos, interaction_range));
+                            }
+                        }
+                    }
+                }
+
+                // Check if it's the power switch
+                if interactable.interaction_type == InteractionType::PowerSwitch {
+                    let is_closer = closest_power_switch_info
+                        .as_ref()
+                        .is_none_or(|(closest_dist, _, _)| distance < *closest_dist);
+                    if is_closer {
+                        closest_power_switch_info = Some((distance, pos, interaction_range));
+                    }
+                }
+
+                // Check if it's the mystery box
+                if interactable.interaction_type == InteractionType::MysteryBox {
+                    match &closest_mystery_box_info {
+                        None => {
+                            closest_mystery_box_info = Some((distance, pos, interaction_range));
                         }
-                        Some((closest_dist, _, _, _)) => {
+                        Some((closest_dist, _, _)) => {
                             if distance < *closest_dist {
-                                closest_door_info = Some((distance, door_component.config.cost, pos, interaction_range));
+                                closest_mystery_box_info = Some((distance, pos, interaction_range));
                             }
                         }
                     }
                 }
 
+                // Check if it's a downed teammate
+                if let Some((player, downed)) = downed_opt {
+                    let is_closer = closest_revive_info
+                        .as_ref()
+                        .is_none_or(|(closest_dist, _, _, _, _)| distance < *closest_dist);
+                    if is_closer {
+                        let progress = if downed.is_being_revived(frame.frame) {
+                            downed.revive_progress_frames
+                        } else {
+                            0
+                        };
+                        closest_revive_info = Some((distance, player.name.clone(), progress, pos, interaction_range));
+                    }
+                }
+
+                // Check if it's an upgrade station
+                if let Some(upgrade_station) = upgrade_station_opt {
+                    let is_closer = closest_upgrade_station_info
+                        .as_ref()
+                        .is_none_or(|(closest_dist, _, _, _)| distance < *closest_dist);
+                    if is_closer {
+                        closest_upgrade_station_info = Some((distance, upgrade_station.phase.clone(), pos, interaction_range));
+                    }
+                }
+
+                // Check if it's a perk machine
+                if let Some(perk_machine) = perk_machine_opt {
+                    let is_closer = closest_perk_info
+                        .as_ref()
+                        .is_none_or(|(closest_dist, _, _, _)| distance < *closest_dist);
+                    if is_closer {
+                        closest_perk_info = Some((distance, perk_machine.perk.clone(), pos, interaction_range));
+                    }
+                }
+
                 // Check if it's a window
                 if let Some(window_health) = window_health_opt {
                     match &closest_window_info {
@@ -676,8 +809,29 @@ pub fn display_interaction_prompts(
         }
     }
 
-    // Priority: show door prompt if there's a door nearby, otherwise show window prompt
-    if let Some((_distance, cost, door_pos, interaction_range)) = closest_door_info {
+    // Priority: revive prompt, then door prompt, then power switch prompt, then mystery box prompt,
+    // then upgrade station prompt, then perk machine prompt, then window prompt
+    if let Some((_distance, name, progress, player_pos, interaction_range)) = closest_revive_info {
+        // Draw outer range circle in red with low opacity for downed teammates
+        gizmos.circle(
+            Isometry3d::from_translation(player_pos),
+            interaction_range,
+            Color::srgba(1.0, 0.3, 0.3, 0.3),
+        );
+
+        // Update the text UI
+        if let Ok(mut text) = text_query.single_mut() {
+            if progress > 0 {
+                text.0 = format!(
+                    "Reviving {} ({}%)",
+                    name,
+                    progress * 100 / downed_config.revive_frames.max(1)
+                );
+            } else {
+                text.0 = format!("Hold H to revive {}", name);
+            }
+        }
+    } else if let Some((_distance, cost, requires_power, door_pos, interaction_range)) = closest_door_info {
         // Draw outer range circle in yellow with low opacity
         gizmos.circle(
             Isometry3d::from_translation(door_pos),
@@ -687,7 +841,93 @@ pub fn display_interaction_prompts(
         
         // Update the text UI
         if let Ok(mut text) = text_query.single_mut() {
-            text.0 = format!("Press H to open door (Cost: {})", cost);
+            if requires_power {
+                text.0 = "Door requires power".to_string();
+            } else {
+                text.0 = format!("Press H to open door (Cost: {})", cost);
+            }
+        }
+    } else if let Some((_distance, switch_pos, interaction_range)) = closest_power_switch_info {
+        // Draw outer range circle in orange with low opacity for the power switch
+        gizmos.circle(
+            Isometry3d::from_translation(switch_pos),
+            interaction_range,
+            Color::srgba(1.0, 0.6, 0.0, 0.3),
+        );
+
+        // Update the text UI
+        if let Ok(mut text) = text_query.single_mut() {
+            text.0 = "Press H to turn on the power".to_string();
+        }
+    } else if let Some((_distance, box_pos, interaction_range)) = closest_mystery_box_info {
+        // Draw outer range circle in blue with low opacity for the mystery box
+        gizmos.circle(
+            Isometry3d::from_translation(box_pos),
+            interaction_range,
+            Color::srgba(0.4, 0.8, 1.0, 0.3),
+        );
+
+        let cost = crate::mystery_box::systems::get_mystery_box_config(&global_assets, &mystery_box_config_assets)
+            .map(|config| config.cost)
+            .unwrap_or_default();
+
+        // Update the text UI
+        if let Ok(mut text) = text_query.single_mut() {
+            text.0 = match &mystery_box_state.phase {
+                crate::mystery_box::MysteryBoxPhase::Idle => {
+                    format!("Press H for a random weapon (Cost: {})", cost)
+                }
+                crate::mystery_box::MysteryBoxPhase::Ready { weapon, .. } => {
+                    format!("Press H to take {}", weapon)
+                }
+                _ => String::new(),
+            };
+        }
+    } else if let Some((_distance, phase, station_pos, interaction_range)) = closest_upgrade_station_info {
+        // Draw outer range circle in purple with low opacity for the upgrade station
+        gizmos.circle(
+            Isometry3d::from_translation(station_pos),
+            interaction_range,
+            Color::srgba(0.7, 0.4, 1.0, 0.3),
+        );
+
+        let cost = upgrades_assets
+            .get(&global_assets.weapon_upgrades)
+            .map(|config| config.cost)
+            .unwrap_or_default();
+
+        // Update the text UI
+        if let Ok(mut text) = text_query.single_mut() {
+            text.0 = match phase {
+                _ if !power.on => "Upgrade station requires power".to_string(),
+                crate::weapons::upgrade::UpgradeStationPhase::Idle => {
+                    format!("Press H to upgrade weapon (Cost: {})", cost)
+                }
+                crate::weapons::upgrade::UpgradeStationPhase::Upgrading { .. } => "Upgrading...".to_string(),
+                crate::weapons::upgrade::UpgradeStationPhase::Ready { .. } => "Press H to collect weapon".to_string(),
+            };
+        }
+    } else if let Some((_distance, perk, machine_pos, interaction_range)) = closest_perk_info {
+        // Draw outer range circle in pink with low opacity for perk machines
+        gizmos.circle(
+            Isometry3d::from_translation(machine_pos),
+            interaction_range,
+            Color::srgba(1.0, 0.4, 0.7, 0.3),
+        );
+
+        let definition = global_assets
+            .perk_config
+            .as_ref()
+            .and_then(|h| perk_config_assets.get(h))
+            .and_then(|config| config.perks.get(&perk));
+
+        // Update the text UI
+        if let Ok(mut text) = text_query.single_mut() {
+            text.0 = match definition {
+                _ if !power.on => "Perk machine requires power".to_string(),
+                Some(definition) => format!("Press H to buy {} (Cost: {})", definition.display_name, definition.price),
+                None => String::new(),
+            };
         }
     } else if let Some((_distance, current_health, max_health, window_pos, interaction_range)) = closest_window_info {
         // Draw outer range circle in green with low opacity for windows
@@ -52,6 +52,9 @@ pub struct GggrsSessionConfiguration {
     pub lobby: String,
     pub connection: GggrsConnectionConfiguration,
     pub players: Vec<PlayerConfig>,
+    // Id of the game mode, the peers check they all picked the same one in
+    // `p2p::wait_for_players`, see `game_mode::GameModeRegistry`
+    pub game_mode: String,
 }
 
 // This state is used to mark if extra external settings need to be configure
@@ -1,11 +1,15 @@
-use bevy::prelude::*;
+use bevy::{platform::collections::HashMap, prelude::*};
 use bevy_fixed::rng::RollbackRng;
 use bevy_ggrs::ggrs::PlayerType;
-use bevy_matchbox::{prelude::PeerState, MatchboxSocket};
+use bevy_matchbox::{
+    prelude::{PeerId, PeerState},
+    MatchboxSocket,
+};
 
 use crate::{
     character::player::jjrs::PeerConfig,
     core::{AppState, OnlineState},
+    game_mode::ActiveGameMode,
     jjrs::{
         GggrsSessionConfiguration, GggrsSessionConfigurationState, GgrsPlayer, GgrsSessionBuilding,
     },
@@ -13,6 +17,10 @@ use crate::{
 
 // For matchbox socket connection
 
+// Channel 0 is given to GGRS once the game starts, the lobby uses its own
+// channel to agree on the match settings before that
+const LOBBY_CHANNEL: usize = 1;
+
 pub fn start_matchbox_socket(mut commands: Commands, ggrs_config: Res<GggrsSessionConfiguration>) {
     use bevy_matchbox::matchbox_socket::{ChannelConfig, RtcIceServerConfig, WebRtcSocketBuilder};
 
@@ -31,6 +39,7 @@ pub fn start_matchbox_socket(mut commands: Commands, ggrs_config: Res<GggrsSessi
     let socket = WebRtcSocketBuilder::new(url)
         .ice_server(ice_server)
         .add_channel(ChannelConfig::reliable())
+        .add_channel(ChannelConfig::reliable())
         .build();
 
     commands.insert_resource(MatchboxSocket::from(socket));
@@ -45,6 +54,9 @@ pub fn wait_for_players(
     ggrs_config: Res<GggrsSessionConfiguration>,
     online_state: Res<OnlineState>,
     session_state: Res<GggrsSessionConfigurationState>,
+    game_mode: Res<ActiveGameMode>,
+    // Game mode selected by each remote peer
+    mut peer_game_modes: Local<HashMap<PeerId, String>>,
 ) {
     if !matches!(online_state.as_ref(), OnlineState::Online) {
         return;
@@ -60,9 +72,29 @@ pub fn wait_for_players(
     for (peer, new_state) in peer_changes {
         // you can also handle the specific dis(connections) as they occur:
         match new_state {
-            PeerState::Connected => info!("peer {peer} connected"),
-            PeerState::Disconnected => info!("peer {peer} disconnected"),
+            PeerState::Connected => {
+                info!("peer {peer} connected");
+                // Every peer sends its game mode, the match only starts if they all agree
+                socket
+                    .channel_mut(LOBBY_CHANNEL)
+                    .send(game_mode.id.as_bytes().into(), peer);
+            }
+            PeerState::Disconnected => {
+                info!("peer {peer} disconnected");
+                peer_game_modes.remove(&peer);
+            }
+        }
+    }
+
+    for (peer, packet) in socket.channel_mut(LOBBY_CHANNEL).receive() {
+        let peer_game_mode = String::from_utf8_lossy(&packet).to_string();
+        if peer_game_mode != game_mode.id {
+            error!(
+                "peer {peer} selected game mode {}, but {} is selected here, the game can't start",
+                peer_game_mode, game_mode.id
+            );
         }
+        peer_game_modes.insert(peer, peer_game_mode);
     }
     let players = socket.players();
 
@@ -87,6 +119,21 @@ pub fn wait_for_players(
         return;
     }
 
+    // Peers with another game mode would desync on the first frame
+    let mut agreed = true;
+    for peer in socket.connected_peers() {
+        match peer_game_modes.get(&peer) {
+            Some(peer_game_mode) => agreed &= *peer_game_mode == game_mode.id,
+            None => {
+                info!("Waiting for the game mode of peer {peer}");
+                agreed = false;
+            }
+        }
+    }
+    if !agreed {
+        return;
+    }
+
     info!(
         "All {} players are connected and ready, transitioning to GameLoading",
         num_players
@@ -4,12 +4,20 @@ pub mod camera;
 pub mod character;
 pub mod collider;
 pub mod core;
+pub mod economy;
 pub mod frame;
+pub mod game_mode;
 pub mod global_asset;
 pub mod interaction;
 pub mod jjrs;
 pub mod light;
+pub mod mystery_box;
+pub mod perk;
+pub mod power;
+pub mod powerup;
+pub mod room;
 pub mod system_set;
+pub mod throwable;
 pub mod ui;
 pub mod waves;
 pub mod weapons;
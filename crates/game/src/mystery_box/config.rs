@@ -0,0 +1,111 @@
+//! Mystery box configuration loaded from RON files.
+//!
+//! GGRS CRITICAL: All fields use deterministic types (no f32/f64 for game logic).
+
+use bevy::{platform::collections::HashMap, prelude::*, reflect::TypePath};
+use bevy_fixed::fixed_math;
+use serde::{Deserialize, Serialize};
+
+/// Main mystery box configuration loaded from RON file
+#[derive(Asset, TypePath, Debug, Clone, Serialize, Deserialize)]
+pub struct MysteryBoxConfig {
+    /// Points charged for one roll
+    pub cost: u32,
+    /// Range within which a player can use the box
+    pub interaction_range: fixed_math::Fixed,
+
+    // === Timing (in frames at 60fps) ===
+
+    /// Duration of the cycling animation before the weapon is revealed
+    pub cycle_frames: u32,
+    /// Frames each weapon stays displayed while cycling (visual only)
+    pub cycle_step_frames: u32,
+    /// Frames the roller has to take the revealed weapon before it is lost
+    pub take_window_frames: u32,
+    /// Frames the box is unavailable while moving to another location
+    pub move_frames: u32,
+
+    // === Relocation ===
+
+    /// Minimum number of uses before the box moves
+    pub min_uses_before_move: u32,
+    /// Maximum number of uses before the box moves (inclusive)
+    pub max_uses_before_move: u32,
+
+    // === Weapon Pool ===
+
+    /// Weapon name (key in `WeaponsConfig`) -> weight
+    /// Weights are relative, not percentages
+    pub weapon_pool: HashMap<String, u32>,
+}
+
+impl Default for MysteryBoxConfig {
+    fn default() -> Self {
+        let mut weapon_pool = HashMap::default();
+        weapon_pool.insert("pistol".to_string(), 20);
+        weapon_pool.insert("shotgun".to_string(), 40);
+        weapon_pool.insert("machine_gun".to_string(), 40);
+
+        Self {
+            cost: 950,
+            interaction_range: fixed_math::new(40.0),
+            cycle_frames: 240,
+            cycle_step_frames: 8,
+            take_window_frames: 480,
+            move_frames: 600,
+            min_uses_before_move: 4,
+            max_uses_before_move: 8,
+            weapon_pool,
+        }
+    }
+}
+
+impl MysteryBoxConfig {
+    /// Weapon names sorted by key.
+    ///
+    /// GGRS CRITICAL: Always iterate the pool through this to keep a deterministic order.
+    pub fn sorted_pool(&self) -> Vec<(&String, u32)> {
+        let mut pool: Vec<_> = self
+            .weapon_pool
+            .iter()
+            .filter(|(_, weight)| **weight > 0)
+            .map(|(name, weight)| (name, *weight))
+            .collect();
+        pool.sort_by_key(|(name, _)| *name);
+        pool
+    }
+
+    /// Sum of all weights in the pool
+    pub fn total_weight(&self) -> u32 {
+        self.weapon_pool.values().sum()
+    }
+
+    /// Pick a weapon from the pool given a roll in `0..total_weight()`
+    pub fn pick_weapon(&self, roll: u32) -> Option<&String> {
+        let mut cumulative = 0u32;
+        for (name, weight) in self.sorted_pool() {
+            cumulative += weight;
+            if roll < cumulative {
+                return Some(name);
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pick_weapon_uses_sorted_cumulative_weights() {
+        let config = MysteryBoxConfig::default();
+        // Sorted: machine_gun (0..40), pistol (40..60), shotgun (60..100)
+        assert_eq!(config.total_weight(), 100);
+        assert_eq!(config.pick_weapon(0).map(String::as_str), Some("machine_gun"));
+        assert_eq!(config.pick_weapon(39).map(String::as_str), Some("machine_gun"));
+        assert_eq!(config.pick_weapon(40).map(String::as_str), Some("pistol"));
+        assert_eq!(config.pick_weapon(99).map(String::as_str), Some("shotgun"));
+        assert_eq!(config.pick_weapon(100), None);
+    }
+}
@@ -0,0 +1,81 @@
+//! Mystery box: pay points to roll a random weapon.
+//!
+//! # Overview
+//!
+//! The box sits on one of the map `CrateLocation` entities. A player pays
+//! `cost` points, the result is rolled immediately with `RollbackRng` from a
+//! weighted RON pool and stays hidden while the weapons cycle. Once revealed,
+//! only the roller can take it during a limited window of frames. After a
+//! random number of uses the box moves to another location.
+//!
+//! # Box Flow
+//!
+//! ```text
+//! Unplaced → Idle → Cycling → Ready ─┬─→ Idle
+//!             ↑                      └─→ Moving
+//!             └─────────────────────────────┘
+//! ```
+//!
+//! # Configuration
+//!
+//! Configure via `assets/mystery_box/mystery_box_config.ron`. See `config::MysteryBoxConfig` for options.
+
+pub mod config;
+pub mod state;
+pub mod systems;
+pub mod ui;
+
+use bevy::prelude::*;
+use bevy_common_assets::ron::RonAssetPlugin;
+use bevy_ggrs::{GgrsSchedule, RollbackApp};
+use serde::{Deserialize, Serialize};
+
+use crate::core::AppState;
+use crate::interaction::{handle_window_repair, interaction_detection_system};
+use crate::system_set::RollbackSystemSet;
+
+pub use config::MysteryBoxConfig;
+pub use state::{MysteryBoxPhase, MysteryBoxState};
+
+/// Marker for a map location the mystery box can appear on
+#[derive(Component, Clone, Copy, Debug, Default, Serialize, Deserialize)]
+pub struct MysteryBoxLocation;
+
+/// Plugin that adds the mystery box.
+pub struct MysteryBoxPlugin;
+
+impl Plugin for MysteryBoxPlugin {
+    fn build(&self, app: &mut App) {
+        // RON asset plugin for mystery box configuration
+        app.add_plugins(RonAssetPlugin::<MysteryBoxConfig>::new(&["ron"]));
+
+        // Resources
+        app.init_resource::<MysteryBoxState>();
+
+        // Rollback registration
+        app.rollback_resource_with_clone::<MysteryBoxState>();
+
+        app.add_systems(
+            GgrsSchedule,
+            (
+                systems::handle_mystery_box_interaction,
+                systems::mystery_box_phase_system,
+            )
+                .chain()
+                .after(interaction_detection_system)
+                .after(handle_window_repair)
+                .in_set(RollbackSystemSet::Interaction),
+        );
+
+        // Visuals (outside GGRS schedule), derived from the rollback state
+        app.add_systems(OnEnter(AppState::InGame), ui::setup_mystery_box_ui);
+        app.add_systems(
+            Update,
+            (
+                ui::update_mystery_box_locations,
+                ui::update_mystery_box_display,
+            )
+                .run_if(in_state(AppState::InGame)),
+        );
+    }
+}
@@ -0,0 +1,75 @@
+//! Mystery box state machine.
+//!
+//! GGRS CRITICAL: This resource must be registered for rollback. Everything the
+//! box displays (including the cycling animation) is derived from it so the
+//! visuals stay correct after a rollback.
+
+use bevy::prelude::*;
+use ggrs::PlayerHandle;
+use serde::{Deserialize, Serialize};
+use utils::net_id::GgrsNetId;
+
+/// Current phase of the mystery box
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum MysteryBoxPhase {
+    /// The box has not been placed on a location yet
+    #[default]
+    Unplaced,
+    /// Waiting for a player to buy a roll
+    Idle,
+    /// Weapons are cycling, the result is already rolled but hidden
+    Cycling {
+        roller: PlayerHandle,
+        start_frame: u32,
+        weapon: String,
+    },
+    /// Weapon revealed, only the roller can take it until it expires
+    Ready {
+        roller: PlayerHandle,
+        expires_at_frame: u32,
+        weapon: String,
+    },
+    /// The box left its location and will reappear elsewhere
+    Moving { until_frame: u32 },
+}
+
+/// Mystery box state resource
+///
+/// GGRS CRITICAL: Must be registered with `.rollback_resource_with_clone::<MysteryBoxState>()`
+#[derive(Resource, Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MysteryBoxState {
+    /// Current phase of the box
+    pub phase: MysteryBoxPhase,
+    /// Location the box currently sits on (None while unplaced or moving)
+    pub location: Option<GgrsNetId>,
+    /// Location the box left when it started moving (avoid reappearing there)
+    pub previous_location: Option<GgrsNetId>,
+    /// Completed uses at the current location
+    pub uses_since_move: u32,
+    /// Uses allowed at the current location before moving (rolled on placement)
+    pub uses_before_move: u32,
+}
+
+impl MysteryBoxState {
+    /// Close the current use, the box either goes back to idle or starts moving
+    pub fn finish_use(&mut self, current_frame: u32, move_frames: u32, can_move: bool) {
+        self.uses_since_move += 1;
+        if can_move && self.uses_since_move >= self.uses_before_move {
+            self.phase = MysteryBoxPhase::Moving {
+                until_frame: current_frame + move_frames,
+            };
+        } else {
+            self.phase = MysteryBoxPhase::Idle;
+        }
+    }
+
+    /// Weapon name currently shown by the box, if any
+    pub fn weapon(&self) -> Option<&String> {
+        match &self.phase {
+            MysteryBoxPhase::Cycling { weapon, .. } | MysteryBoxPhase::Ready { weapon, .. } => {
+                Some(weapon)
+            }
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,300 @@
+//! Mystery box systems.
+//!
+//! GGRS CRITICAL: All systems must be deterministic.
+
+use bevy::{log::{tracing::span, Level}, prelude::*};
+use bevy_fixed::rng::RollbackRng;
+use bevy_ggrs::Rollback;
+use utils::{frame::FrameCount, net_id::{GgrsNetId, GgrsNetIdFactory}, order_iter};
+
+use crate::{
+    character::{
+        enemy::create::SpawnAssets,
+        player::{input::InteractionInput, Player},
+    },
+    economy::Points,
+    global_asset::GlobalAsset,
+    interaction::{Interactable, InteractionEvent, InteractionType},
+    perk::{systems::get_perk_config, PerkConfig, Perks},
+    weapons::{spawn_weapon_for_player, WeaponInventory, WeaponModesState},
+};
+
+use super::{
+    config::MysteryBoxConfig,
+    state::{MysteryBoxPhase, MysteryBoxState},
+    MysteryBoxLocation,
+};
+
+/// Get the loaded mystery box config, if any
+pub fn get_mystery_box_config<'a>(
+    global_assets: &GlobalAsset,
+    config_assets: &'a Assets<MysteryBoxConfig>,
+) -> Option<&'a MysteryBoxConfig> {
+    global_assets
+        .mystery_box_config
+        .as_ref()
+        .and_then(|h| config_assets.get(h))
+}
+
+/// System that handles players buying a roll and taking the rolled weapon.
+///
+/// Interactions are one-shot: the interaction button must be freshly pressed,
+/// holding it does not buy a new roll every frame.
+pub fn handle_mystery_box_interaction(
+    frame: Res<FrameCount>,
+    mut commands: Commands,
+    mut event_reader: MessageReader<InteractionEvent>,
+    mut state: ResMut<MysteryBoxState>,
+    mut rng: ResMut<RollbackRng>,
+    config_assets: Res<Assets<MysteryBoxConfig>>,
+    perk_config_assets: Res<Assets<PerkConfig>>,
+    global_assets: Res<GlobalAsset>,
+    mut spawn_assets: SpawnAssets,
+    mut id_factory: ResMut<GgrsNetIdFactory>,
+    mut player_query: Query<
+        (&Player, &InteractionInput, &mut Points, &mut WeaponInventory, Option<&Perks>),
+        With<Rollback>,
+    >,
+    mut weapon_query: Query<&mut WeaponModesState>,
+    location_query: Query<(), (With<MysteryBoxLocation>, With<Rollback>)>,
+) {
+    let system_span = span!(Level::INFO, "ggrs", f = frame.frame, s = "handle_mystery_box_interaction");
+    let _enter = system_span.enter();
+
+    let Some(config) = get_mystery_box_config(&global_assets, &config_assets) else {
+        return;
+    };
+    let can_move = location_query.iter().count() > 1;
+    let base_weapon_slots = get_perk_config(&global_assets, &perk_config_assets)
+        .map(|perk_config| perk_config.base_weapon_slots);
+
+    // Events are delivered in deterministic order from interaction_detection_system
+    for event in event_reader.read() {
+        if event.interaction_type != InteractionType::MysteryBox {
+            continue;
+        }
+
+        let Ok((player, interaction_input, mut points, mut inventory, perks)) =
+            player_query.get_mut(event.interactor)
+        else {
+            continue;
+        };
+
+        if !interaction_input.just_pressed {
+            continue;
+        }
+
+        match state.phase.clone() {
+            MysteryBoxPhase::Idle => {
+                if !points.can_afford(config.cost) {
+                    info!(
+                        "{} mystery box: {} cannot afford roll ({} < {})",
+                        frame.as_ref(), event.interactor_net_id, points.current, config.cost
+                    );
+                    continue;
+                }
+
+                let total_weight = config.total_weight();
+                if total_weight == 0 {
+                    warn!("mystery box weapon pool is empty");
+                    continue;
+                }
+
+                let roll = rng.next_u32_range(0, total_weight);
+                let Some(weapon) = config.pick_weapon(roll).cloned() else {
+                    continue;
+                };
+
+                points.try_spend(config.cost);
+                info!(
+                    "{} mystery box: {} paid {} and rolled {} (roll {}/{})",
+                    frame.as_ref(), event.interactor_net_id, config.cost, weapon, roll, total_weight
+                );
+
+                state.phase = MysteryBoxPhase::Cycling {
+                    roller: player.handle,
+                    start_frame: frame.frame,
+                    weapon,
+                };
+            }
+            MysteryBoxPhase::Ready { roller, weapon, .. } if roller == player.handle => {
+                let Some(weapon_asset) = spawn_assets
+                    .weapons_asset
+                    .get(&global_assets.weapons)
+                    .and_then(|weapons| weapons.0.get(&weapon))
+                    .cloned()
+                else {
+                    warn!("mystery box weapon {} not found in weapons config", weapon);
+                    continue;
+                };
+
+                if let Some(index) = inventory
+                    .weapons
+                    .iter()
+                    .position(|(_, w)| w.config.name == weapon_asset.config.name)
+                {
+                    // Already owned: refill it and switch to it
+                    // (refill from the owned config, it may have been upgraded)
+                    let (weapon_entity, owned_weapon) = &inventory.weapons[index];
+                    if let Ok(mut modes_state) = weapon_query.get_mut(*weapon_entity) {
+                        modes_state.refill(&owned_weapon.config);
+                    }
+                    inventory.active_weapon_index = index;
+                } else {
+                    // Inventory full: the new weapon replaces the active one
+                    if let Some(base_weapon_slots) = base_weapon_slots {
+                        let weapon_slots = perks
+                            .map_or(base_weapon_slots, |perks| perks.weapon_slots(base_weapon_slots));
+                        if !inventory.weapons.is_empty() && inventory.weapons.len() >= weapon_slots {
+                            let (replaced_entity, replaced_weapon) =
+                                inventory.weapons.remove(inventory.active_weapon_index);
+                            info!(
+                                "{} mystery box: {} inventory full, {} replaced",
+                                frame.as_ref(), event.interactor_net_id, replaced_weapon.config.name
+                            );
+                            commands.entity(replaced_entity).despawn();
+                        }
+                    }
+
+                    spawn_weapon_for_player(
+                        &mut commands,
+                        &global_assets,
+                        &spawn_assets.asset_server,
+                        &mut spawn_assets.texture_atlas_layouts,
+                        &spawn_assets.spritesheet_assets,
+                        true,
+                        event.interactor,
+                        weapon_asset,
+                        &mut inventory,
+                        &mut id_factory,
+                    );
+                }
+                inventory.frame_switched = frame.frame;
+                inventory.clear_reloading();
+
+                info!(
+                    "{} mystery box: {} took {}",
+                    frame.as_ref(), event.interactor_net_id, weapon
+                );
+
+                state.finish_use(frame.frame, config.move_frames, can_move);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// System that advances the mystery box state machine.
+///
+/// Places the box on a location, reveals the weapon once cycling is over,
+/// expires untaken weapons and relocates the box after its uses run out.
+pub fn mystery_box_phase_system(
+    frame: Res<FrameCount>,
+    mut commands: Commands,
+    mut state: ResMut<MysteryBoxState>,
+    mut rng: ResMut<RollbackRng>,
+    config_assets: Res<Assets<MysteryBoxConfig>>,
+    global_assets: Res<GlobalAsset>,
+    location_query: Query<(&GgrsNetId, Entity), (With<MysteryBoxLocation>, With<Rollback>)>,
+) {
+    let system_span = span!(Level::INFO, "ggrs", f = frame.frame, s = "mystery_box_phase_system");
+    let _enter = system_span.enter();
+
+    let Some(config) = get_mystery_box_config(&global_assets, &config_assets) else {
+        return;
+    };
+
+    // GGRS CRITICAL: locations sorted by net id
+    let locations = order_iter!(location_query);
+    if locations.is_empty() {
+        return;
+    }
+
+    let current_frame = frame.frame;
+
+    match state.phase.clone() {
+        MysteryBoxPhase::Unplaced => {
+            place_box(&mut commands, &mut state, &mut rng, config, &locations);
+        }
+        MysteryBoxPhase::Idle => {}
+        MysteryBoxPhase::Cycling {
+            roller,
+            start_frame,
+            weapon,
+        } => {
+            if current_frame >= start_frame + config.cycle_frames {
+                info!(
+                    "{} mystery box: {} revealed for player {}",
+                    frame.as_ref(), weapon, roller
+                );
+                state.phase = MysteryBoxPhase::Ready {
+                    roller,
+                    expires_at_frame: current_frame + config.take_window_frames,
+                    weapon,
+                };
+            }
+        }
+        MysteryBoxPhase::Ready {
+            expires_at_frame,
+            weapon,
+            ..
+        } => {
+            if current_frame >= expires_at_frame {
+                info!("{} mystery box: {} was not taken", frame.as_ref(), weapon);
+                state.finish_use(current_frame, config.move_frames, locations.len() > 1);
+            }
+        }
+        MysteryBoxPhase::Moving { until_frame } => {
+            // Leave the current location as soon as the box starts moving
+            if let Some(location) = state.location.take() {
+                if let Some((_, entity)) = locations.iter().find(|(id, _)| **id == location) {
+                    commands.entity(*entity).remove::<Interactable>();
+                }
+                info!("{} mystery box: leaving {}", frame.as_ref(), location);
+                state.previous_location = Some(location);
+            }
+
+            if current_frame >= until_frame {
+                place_box(&mut commands, &mut state, &mut rng, config, &locations);
+            }
+        }
+    }
+}
+
+/// Place the box on a random location, avoiding the one it just left
+fn place_box(
+    commands: &mut Commands,
+    state: &mut MysteryBoxState,
+    rng: &mut RollbackRng,
+    config: &MysteryBoxConfig,
+    locations: &[(&GgrsNetId, Entity)],
+) {
+    let candidates: Vec<&(&GgrsNetId, Entity)> = locations
+        .iter()
+        .filter(|(id, _)| locations.len() == 1 || Some(*id) != state.previous_location.as_ref())
+        .collect();
+    if candidates.is_empty() {
+        return;
+    }
+
+    let index = rng.next_u32_range(0, candidates.len() as u32) as usize;
+    let (net_id, entity) = candidates[index];
+
+    commands.entity(*entity).insert(Interactable {
+        interaction_range: config.interaction_range,
+        interaction_type: InteractionType::MysteryBox,
+    });
+
+    state.location = Some((*net_id).clone());
+    state.uses_since_move = 0;
+    state.uses_before_move = rng.next_u32_range_inclusive(
+        config.min_uses_before_move,
+        config.max_uses_before_move.max(config.min_uses_before_move),
+    );
+    state.phase = MysteryBoxPhase::Idle;
+
+    info!(
+        "mystery box placed at {} for {} uses",
+        net_id, state.uses_before_move
+    );
+}
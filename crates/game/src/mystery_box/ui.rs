@@ -0,0 +1,120 @@
+//! Mystery box visuals.
+//!
+//! Everything here is derived every frame from the rollback `MysteryBoxState`
+//! and `FrameCount`, so the cycling animation is replayed correctly after a
+//! rollback instead of keeping its own local timer.
+
+use bevy::prelude::*;
+use bevy_fixed::fixed_math;
+use map::game::entity::MapRollbackItem;
+use utils::{frame::FrameCount, net_id::GgrsNetId};
+
+use super::{
+    config::MysteryBoxConfig,
+    state::{MysteryBoxPhase, MysteryBoxState},
+    systems::get_mystery_box_config,
+    MysteryBoxLocation,
+};
+use crate::global_asset::GlobalAsset;
+
+/// Component marker for the weapon name floating above the box
+#[derive(Component)]
+pub struct MysteryBoxDisplayText;
+
+pub fn setup_mystery_box_ui(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let font = asset_server.load("fonts/FiraMono-Medium.ttf");
+
+    commands.spawn((
+        MysteryBoxDisplayText,
+        Text2d::new(""),
+        TextFont {
+            font,
+            font_size: 10.0,
+            ..Default::default()
+        },
+        TextColor(Color::srgb(0.6, 0.9, 1.0)),
+        Transform::from_translation(Vec3::new(0.0, 0.0, 10.0)),
+        Visibility::Hidden,
+    ));
+}
+
+/// Show the LDTK sprite only for the location the box currently sits on
+pub fn update_mystery_box_locations(
+    state: Res<MysteryBoxState>,
+    location_query: Query<(&GgrsNetId, &MapRollbackItem), With<MysteryBoxLocation>>,
+    mut visibility_query: Query<&mut Visibility>,
+) {
+    for (net_id, rollback_item) in location_query.iter() {
+        let is_active = state.location.as_ref() == Some(net_id);
+        if let Ok(mut visibility) = visibility_query.get_mut(rollback_item.parent) {
+            visibility.set_if_neq(if is_active {
+                Visibility::Inherited
+            } else {
+                Visibility::Hidden
+            });
+        }
+    }
+}
+
+/// Update the floating text with the cycling or revealed weapon
+pub fn update_mystery_box_display(
+    frame: Res<FrameCount>,
+    state: Res<MysteryBoxState>,
+    config_assets: Res<Assets<MysteryBoxConfig>>,
+    global_assets: Res<GlobalAsset>,
+    location_query: Query<(&GgrsNetId, &fixed_math::FixedTransform3D), With<MysteryBoxLocation>>,
+    mut text_query: Query<(&mut Text2d, &mut Transform, &mut Visibility), With<MysteryBoxDisplayText>>,
+) {
+    let Ok((mut text, mut transform, mut visibility)) = text_query.single_mut() else {
+        return;
+    };
+
+    let location_transform = state.location.as_ref().and_then(|location| {
+        location_query
+            .iter()
+            .find(|(net_id, _)| *net_id == location)
+            .map(|(_, transform)| transform)
+    });
+
+    let (Some(config), Some(location_transform)) = (
+        get_mystery_box_config(&global_assets, &config_assets),
+        location_transform,
+    ) else {
+        visibility.set_if_neq(Visibility::Hidden);
+        return;
+    };
+
+    let label = match &state.phase {
+        MysteryBoxPhase::Cycling { start_frame, .. } => {
+            // Pick the displayed weapon from the elapsed frames so it is rollback-proof
+            let pool = config.sorted_pool();
+            if pool.is_empty() {
+                String::new()
+            } else {
+                let step = frame.frame.saturating_sub(*start_frame)
+                    / config.cycle_step_frames.max(1);
+                pool[step as usize % pool.len()].0.clone()
+            }
+        }
+        MysteryBoxPhase::Ready {
+            expires_at_frame,
+            weapon,
+            ..
+        } => format!(
+            "{} ({}s)",
+            weapon,
+            expires_at_frame.saturating_sub(frame.frame) / 60
+        ),
+        _ => String::new(),
+    };
+
+    if label.is_empty() {
+        visibility.set_if_neq(Visibility::Hidden);
+        return;
+    }
+
+    let position = fixed_math::fixed_to_vec2(location_transform.translation.truncate());
+    transform.translation = position.extend(10.0) + Vec3::new(0.0, 16.0, 0.0);
+    text.0 = label;
+    visibility.set_if_neq(Visibility::Inherited);
+}
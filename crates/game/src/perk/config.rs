@@ -0,0 +1,60 @@
+//! Perk machines configuration loaded from RON files.
+//!
+//! GGRS CRITICAL: All fields used by game logic use deterministic types
+//! (no f32/f64), `color` is only used by the HUD.
+
+use bevy::{platform::collections::HashMap, prelude::*, reflect::TypePath};
+use bevy_fixed::fixed_math;
+use serde::{Deserialize, Serialize};
+
+/// Buff given by a perk, kept until the player goes down
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum PerkEffect {
+    /// Extra max health
+    MaxHealth { bonus: fixed_math::Fixed },
+    /// Reload time multiplier (0.5 = twice as fast)
+    FasterReload { reload_time_multiplier: fixed_math::Fixed },
+    /// Health regen rate and delay multipliers
+    FasterRegen {
+        regen_rate_multiplier: fixed_math::Fixed,
+        regen_delay_multiplier: fixed_math::Fixed,
+    },
+    /// Dash cooldown multiplier (0.5 = twice as often)
+    FasterDash { cooldown_multiplier: fixed_math::Fixed },
+    /// Extra weapons the player can carry
+    ExtraWeaponSlot { slots: usize },
+}
+
+/// A perk sold by the perk machines
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PerkDefinition {
+    pub display_name: String,
+    /// Short label shown in the HUD icon
+    pub icon: String,
+    /// Color of the HUD icon (visual only)
+    pub color: (f32, f32, f32),
+    pub price: u32,
+    pub effect: PerkEffect,
+}
+
+/// Main perk configuration loaded from RON file
+#[derive(Asset, TypePath, Debug, Clone, Serialize, Deserialize)]
+pub struct PerkConfig {
+    /// Maximum number of perks a player can own at the same time
+    pub max_perks_per_player: usize,
+    /// Weapons a player can carry without perk, a new weapon replaces the
+    /// active one once the inventory is full
+    pub base_weapon_slots: usize,
+    /// Perk id -> definition, the id is set on the map perk machines
+    pub perks: HashMap<String, PerkDefinition>,
+}
+
+impl Default for PerkConfig {
+    fn default() -> Self {
+        Self {
+            max_perks_per_player: 4,
+            base_weapon_slots: 3,
+            perks: HashMap::default(),
+        }
+    }
+}
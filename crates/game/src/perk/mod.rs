@@ -0,0 +1,73 @@
+//! Perk machines selling persistent per-player buffs.
+//!
+//! # Overview
+//!
+//! Perk machines are map entities (`SodaLocation` in LDtk, with a `perk`
+//! field naming the perk). Once the power is on, a player can buy each perk
+//! once. Perks are kept until the player goes down:
+//!
+//! - **MaxHealth**: extra max health
+//! - **FasterReload**: scales the weapons `reload_time_seconds`
+//! - **FasterRegen**: faster `HealthRegen`
+//! - **FasterDash**: shorter `DashState` cooldown
+//! - **ExtraWeaponSlot**: one more weapon before the mystery box replaces the active one
+//!
+//! Owned perks live in the rollback `Perks` component and are read by the
+//! weapon, health and movement systems through its modifier helpers.
+//!
+//! # Configuration
+//!
+//! Configure via `assets/perks/perk_config.ron`. See `config::PerkConfig` for options.
+
+pub mod config;
+pub mod state;
+pub mod systems;
+pub mod ui;
+
+use bevy::prelude::*;
+use bevy_common_assets::ron::RonAssetPlugin;
+use bevy_ggrs::{GgrsSchedule, RollbackApp};
+
+use crate::character::player::downed::rollback_down_players;
+use crate::core::AppState;
+use crate::system_set::RollbackSystemSet;
+
+pub use config::{PerkConfig, PerkDefinition, PerkEffect};
+pub use state::{OwnedPerk, PerkMachine, Perks};
+
+/// Plugin that adds the perk machines.
+pub struct PerkPlugin;
+
+impl Plugin for PerkPlugin {
+    fn build(&self, app: &mut App) {
+        // RON asset plugin for perk configuration
+        app.add_plugins(RonAssetPlugin::<PerkConfig>::new(&["ron"]));
+
+        // Rollback registration
+        app.rollback_component_with_clone::<Perks>()
+            .rollback_component_with_clone::<PerkMachine>();
+
+        // Perks are bought with the other interactions, before movement
+        app.add_systems(
+            GgrsSchedule,
+            systems::handle_perk_interaction
+                .after(crate::powerup::systems::powerup_pickup_system)
+                .in_set(RollbackSystemSet::Interaction),
+        );
+
+        // Perks are lost once the player is downed
+        app.add_systems(
+            GgrsSchedule,
+            systems::rollback_lose_perks_when_downed
+                .after(rollback_down_players)
+                .in_set(RollbackSystemSet::DeathManagement),
+        );
+
+        // Visuals (outside GGRS schedule), derived from the rollback state
+        app.add_systems(OnEnter(AppState::InGame), ui::setup_perk_ui);
+        app.add_systems(
+            Update,
+            ui::update_perk_hud.run_if(in_state(AppState::InGame)),
+        );
+    }
+}
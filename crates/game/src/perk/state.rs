@@ -0,0 +1,111 @@
+//! Perks owned by the players.
+//!
+//! The modifiers are read by the weapon, health and movement systems through
+//! the helpers of `Perks`, a player without perk gets the base values.
+//!
+//! GGRS CRITICAL: `Perks` is a rollback component, all changes must happen
+//! inside the `GgrsSchedule`.
+
+use bevy::prelude::*;
+use bevy_fixed::fixed_math;
+use serde::{Deserialize, Serialize};
+
+use super::config::PerkEffect;
+
+/// Perk machine on the map, selling the perk with the given id
+#[derive(Component, Clone, Debug, Serialize, Deserialize)]
+pub struct PerkMachine {
+    pub perk: String,
+}
+
+/// A perk owned by a player
+///
+/// The effect is copied from the config when bought so the modifiers don't
+/// need the config.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct OwnedPerk {
+    pub id: String,
+    pub effect: PerkEffect,
+}
+
+/// Perks owned by a player, in buying order
+///
+/// GGRS CRITICAL: Must be registered with `.rollback_component_with_clone::<Perks>()`
+#[derive(Component, Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Perks {
+    pub owned: Vec<OwnedPerk>,
+}
+
+impl Perks {
+    pub fn has(&self, id: &str) -> bool {
+        self.owned.iter().any(|perk| perk.id == id)
+    }
+
+    fn effects(&self) -> impl Iterator<Item = &PerkEffect> {
+        self.owned.iter().map(|perk| &perk.effect)
+    }
+
+    /// Max health added by the perks
+    pub fn max_health_bonus(&self) -> fixed_math::Fixed {
+        self.effects()
+            .filter_map(|effect| match effect {
+                PerkEffect::MaxHealth { bonus } => Some(*bonus),
+                _ => None,
+            })
+            .fold(fixed_math::FIXED_ZERO, |total, bonus| total.saturating_add(bonus))
+    }
+
+    /// Reload time of a weapon with the perks applied
+    pub fn reload_time(&self, reload_time_seconds: fixed_math::Fixed) -> fixed_math::Fixed {
+        self.effects()
+            .filter_map(|effect| match effect {
+                PerkEffect::FasterReload { reload_time_multiplier } => Some(*reload_time_multiplier),
+                _ => None,
+            })
+            .fold(reload_time_seconds, |time, multiplier| time.saturating_mul(multiplier))
+    }
+
+    /// Health regen rate with the perks applied
+    pub fn regen_rate(&self, regen_rate: fixed_math::Fixed) -> fixed_math::Fixed {
+        self.effects()
+            .filter_map(|effect| match effect {
+                PerkEffect::FasterRegen { regen_rate_multiplier, .. } => Some(*regen_rate_multiplier),
+                _ => None,
+            })
+            .fold(regen_rate, |rate, multiplier| rate.saturating_mul(multiplier))
+    }
+
+    /// Frames to wait after damage before regen starts, with the perks applied
+    pub fn regen_delay_frames(&self, regen_delay_frames: u32) -> u32 {
+        self.effects()
+            .filter_map(|effect| match effect {
+                PerkEffect::FasterRegen { regen_delay_multiplier, .. } => Some(*regen_delay_multiplier),
+                _ => None,
+            })
+            .fold(regen_delay_frames, |delay, multiplier| {
+                fixed_math::Fixed::from_num(delay).saturating_mul(multiplier).to_num::<u32>()
+            })
+    }
+
+    /// Dash cooldown with the perks applied
+    pub fn dash_cooldown_frames(&self, cooldown_frames: u32) -> u32 {
+        self.effects()
+            .filter_map(|effect| match effect {
+                PerkEffect::FasterDash { cooldown_multiplier } => Some(*cooldown_multiplier),
+                _ => None,
+            })
+            .fold(cooldown_frames, |cooldown, multiplier| {
+                fixed_math::Fixed::from_num(cooldown).saturating_mul(multiplier).to_num::<u32>()
+            })
+    }
+
+    /// Weapons the player can carry with the perks applied
+    pub fn weapon_slots(&self, base_weapon_slots: usize) -> usize {
+        self.effects()
+            .filter_map(|effect| match effect {
+                PerkEffect::ExtraWeaponSlot { slots } => Some(*slots),
+                _ => None,
+            })
+            .fold(base_weapon_slots, |total, slots| total + slots)
+    }
+}
@@ -0,0 +1,160 @@
+//! Perk systems.
+//!
+//! GGRS CRITICAL: All systems must be deterministic.
+
+use bevy::{log::{tracing::span, Level}, prelude::*};
+use bevy_ggrs::Rollback;
+use utils::{frame::FrameCount, net_id::GgrsNetId, order_mut_iter};
+
+use crate::{
+    character::{
+        health::Health,
+        player::{downed::Downed, input::InteractionInput, Player},
+    },
+    economy::Points,
+    global_asset::GlobalAsset,
+    interaction::{InteractionEvent, InteractionType},
+    power::PowerState,
+};
+
+use super::{
+    config::{PerkConfig, PerkEffect},
+    state::{OwnedPerk, PerkMachine, Perks},
+};
+
+/// Get the loaded perk config, if any
+pub fn get_perk_config<'a>(
+    global_assets: &GlobalAsset,
+    config_assets: &'a Assets<PerkConfig>,
+) -> Option<&'a PerkConfig> {
+    global_assets
+        .perk_config
+        .as_ref()
+        .and_then(|h| config_assets.get(h))
+}
+
+/// System that handles players buying a perk from a perk machine.
+///
+/// Perk machines stay inactive until the power is on. Interactions are
+/// one-shot: the interaction button must be freshly pressed.
+pub fn handle_perk_interaction(
+    frame: Res<FrameCount>,
+    mut event_reader: MessageReader<InteractionEvent>,
+    power: Res<PowerState>,
+    config_assets: Res<Assets<PerkConfig>>,
+    global_assets: Res<GlobalAsset>,
+    machine_query: Query<&PerkMachine, With<Rollback>>,
+    mut player_query: Query<
+        (&InteractionInput, &mut Points, &mut Perks, &mut Health),
+        (With<Player>, With<Rollback>),
+    >,
+) {
+    let system_span = span!(Level::INFO, "ggrs", f = frame.frame, s = "handle_perk_interaction");
+    let _enter = system_span.enter();
+
+    let Some(config) = get_perk_config(&global_assets, &config_assets) else {
+        return;
+    };
+
+    // Events are delivered in deterministic order from interaction_detection_system
+    for event in event_reader.read() {
+        if event.interaction_type != InteractionType::Perk {
+            continue;
+        }
+
+        let Ok(machine) = machine_query.get(event.interactable) else {
+            continue;
+        };
+        let Ok((interaction_input, mut points, mut perks, mut health)) =
+            player_query.get_mut(event.interactor)
+        else {
+            continue;
+        };
+
+        if !interaction_input.just_pressed {
+            continue;
+        }
+
+        if !power.on {
+            info!(
+                "{} perk machine {}: requires power",
+                frame.as_ref(), event.interactable_net_id
+            );
+            continue;
+        }
+
+        let Some(definition) = config.perks.get(&machine.perk) else {
+            warn!("perk {} not found in perk config", machine.perk);
+            continue;
+        };
+
+        if perks.has(&machine.perk) {
+            info!(
+                "{} perk machine {}: {} already owns {}",
+                frame.as_ref(), event.interactable_net_id, event.interactor_net_id, machine.perk
+            );
+            continue;
+        }
+
+        if perks.owned.len() >= config.max_perks_per_player {
+            info!(
+                "{} perk machine {}: {} already owns {} perks",
+                frame.as_ref(), event.interactable_net_id, event.interactor_net_id, perks.owned.len()
+            );
+            continue;
+        }
+
+        if !points.try_spend(definition.price) {
+            info!(
+                "{} perk machine {}: {} cannot afford {} ({} < {})",
+                frame.as_ref(), event.interactable_net_id, event.interactor_net_id,
+                machine.perk, points.current, definition.price
+            );
+            continue;
+        }
+
+        // Max health is the only effect applied once, the others are read by the systems
+        if let PerkEffect::MaxHealth { bonus } = definition.effect {
+            health.max = health.max.saturating_add(bonus);
+            health.current = health.current.saturating_add(bonus);
+        }
+
+        perks.owned.push(OwnedPerk {
+            id: machine.perk.clone(),
+            effect: definition.effect.clone(),
+        });
+
+        info!(
+            "{} perk machine {}: {} paid {} for {}",
+            frame.as_ref(), event.interactable_net_id, event.interactor_net_id,
+            definition.price, machine.perk
+        );
+    }
+}
+
+/// Players lose all their perks when they go down.
+///
+/// Runs after the downed state is added, the max health bonus is removed.
+pub fn rollback_lose_perks_when_downed(
+    frame: Res<FrameCount>,
+    mut query: Query<(&GgrsNetId, &mut Perks, &mut Health), (With<Downed>, With<Rollback>)>,
+) {
+    let system_span = span!(Level::INFO, "ggrs", f = frame.frame, s = "lose_perks");
+    let _enter = system_span.enter();
+
+    for (net_id, mut perks, mut health) in order_mut_iter!(query) {
+        if perks.owned.is_empty() {
+            continue;
+        }
+
+        let bonus = perks.max_health_bonus();
+        health.max = health.max.saturating_sub(bonus);
+        health.current = health.current.min(health.max);
+
+        info!(
+            "{} {} is down and lost {} perks",
+            frame.as_ref(), net_id, perks.owned.len()
+        );
+        perks.owned.clear();
+    }
+}
@@ -0,0 +1,97 @@
+//! Perk HUD icons.
+//!
+//! Derived every frame from the rollback `Perks` of the local player.
+
+use bevy::prelude::*;
+
+use crate::{character::player::LocalPlayer, global_asset::GlobalAsset};
+
+use super::{config::PerkConfig, state::Perks, systems::get_perk_config};
+
+/// Size of a perk icon in pixels
+const PERK_ICON_SIZE: f32 = 24.0;
+
+/// Container of the perk icons, remembers the perks it shows
+#[derive(Component, Default)]
+pub struct PerkHud {
+    shown: Vec<String>,
+}
+
+pub fn setup_perk_ui(mut commands: Commands) {
+    commands.spawn((
+        PerkHud::default(),
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(75.0),
+            left: Val::Px(5.0),
+            flex_direction: FlexDirection::Row,
+            column_gap: Val::Px(4.0),
+            ..default()
+        },
+    ));
+}
+
+/// Show one colored icon per perk owned by the local player
+pub fn update_perk_hud(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    global_assets: Res<GlobalAsset>,
+    config_assets: Res<Assets<PerkConfig>>,
+    player_query: Query<&Perks, With<LocalPlayer>>,
+    mut hud_query: Query<(Entity, &mut PerkHud)>,
+) {
+    let Ok((hud_entity, mut hud)) = hud_query.single_mut() else {
+        return;
+    };
+
+    let owned: Vec<String> = player_query
+        .iter()
+        .next()
+        .map(|perks| perks.owned.iter().map(|perk| perk.id.clone()).collect())
+        .unwrap_or_default();
+
+    if owned == hud.shown {
+        return;
+    }
+
+    let Some(config) = get_perk_config(&global_assets, &config_assets) else {
+        return;
+    };
+
+    let font = asset_server.load("fonts/FiraMono-Medium.ttf");
+
+    commands.entity(hud_entity).despawn_related::<Children>();
+    commands.entity(hud_entity).with_children(|parent| {
+        for id in owned.iter() {
+            let Some(definition) = config.perks.get(id) else {
+                continue;
+            };
+            let (r, g, b) = definition.color;
+
+            parent
+                .spawn((
+                    Node {
+                        width: Val::Px(PERK_ICON_SIZE),
+                        height: Val::Px(PERK_ICON_SIZE),
+                        justify_content: JustifyContent::Center,
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgb(r, g, b)),
+                ))
+                .with_children(|icon| {
+                    icon.spawn((
+                        Text::new(definition.icon.clone()),
+                        TextFont {
+                            font: font.clone(),
+                            font_size: 10.0,
+                            ..Default::default()
+                        },
+                        TextColor(Color::BLACK),
+                    ));
+                });
+        }
+    });
+
+    hud.shown = owned;
+}
@@ -0,0 +1,116 @@
+//! Map power, turned on with the power switch.
+//!
+//! Until the power is on, electrified doors (`DoorConfig.electrify`) and the
+//! weapon upgrade station can't be used and the map is darker. Maps without a
+//! power switch start with the power on.
+//!
+//! GGRS CRITICAL: `PowerState` is a rollback resource, all changes must happen
+//! inside the `GgrsSchedule`.
+
+use bevy::{log::{tracing::span, Level}, prelude::*};
+use bevy_ggrs::{GgrsSchedule, Rollback, RollbackApp};
+use serde::{Deserialize, Serialize};
+use utils::frame::FrameCount;
+
+use crate::{
+    camera::DEFAULT_AMBIENT_BRIGHTNESS,
+    interaction::{Interactable, InteractionEvent, InteractionType},
+    system_set::RollbackSystemSet,
+};
+
+/// Ambient light brightness while the power is off
+pub const UNPOWERED_AMBIENT_BRIGHTNESS: f32 = 0.1;
+
+/// Whether the power of the map is on
+///
+/// GGRS CRITICAL: Must be registered with `.rollback_resource_with_copy::<PowerState>()`
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PowerState {
+    pub on: bool,
+}
+
+impl PowerState {
+    /// Ambient light brightness for the current power state
+    pub fn ambient_brightness(&self) -> f32 {
+        if self.on {
+            DEFAULT_AMBIENT_BRIGHTNESS
+        } else {
+            UNPOWERED_AMBIENT_BRIGHTNESS
+        }
+    }
+}
+
+/// Marker for the power switch map entity
+#[derive(Component, Clone, Copy, Debug, Default, Serialize, Deserialize)]
+pub struct PowerSwitch;
+
+/// Turn the power on when a player uses the power switch.
+///
+/// The switch stops being interactable once used.
+pub fn handle_power_switch_interaction(
+    frame: Res<FrameCount>,
+    mut commands: Commands,
+    mut event_reader: MessageReader<InteractionEvent>,
+    mut power: ResMut<PowerState>,
+    switch_query: Query<(), (With<PowerSwitch>, With<Rollback>)>,
+) {
+    let system_span = span!(Level::INFO, "ggrs", f = frame.frame, s = "handle_power_switch_interaction");
+    let _enter = system_span.enter();
+
+    for event in event_reader.read() {
+        if event.interaction_type != InteractionType::PowerSwitch
+            || switch_query.get(event.interactable).is_err()
+        {
+            continue;
+        }
+
+        if !power.on {
+            info!(
+                "{} power turned on by {} with switch {}",
+                frame.as_ref(), event.interactor_net_id, event.interactable_net_id
+            );
+            power.on = true;
+        }
+
+        commands.entity(event.interactable).remove::<Interactable>();
+    }
+}
+
+/// Turn the power on for maps without a power switch
+pub fn rollback_power_without_switch(
+    frame: Res<FrameCount>,
+    mut power: ResMut<PowerState>,
+    switch_query: Query<(), (With<PowerSwitch>, With<Rollback>)>,
+) {
+    if power.on || !switch_query.is_empty() {
+        return;
+    }
+
+    let system_span = span!(Level::INFO, "ggrs", f = frame.frame, s = "power_without_switch");
+    let _enter = system_span.enter();
+
+    info!("{} no power switch on the map, power is on", frame.as_ref());
+    power.on = true;
+}
+
+/// Plugin that adds the map power.
+///
+/// `handle_power_switch_interaction` is registered with the other interaction
+/// handlers by `InteractionPlugin`.
+pub struct PowerPlugin;
+
+impl Plugin for PowerPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PowerState>();
+
+        app.rollback_resource_with_copy::<PowerState>()
+            .rollback_component_with_copy::<PowerSwitch>();
+
+        app.add_systems(
+            GgrsSchedule,
+            rollback_power_without_switch
+                .before(handle_power_switch_interaction)
+                .in_set(RollbackSystemSet::Interaction),
+        );
+    }
+}
@@ -0,0 +1,97 @@
+//! Power-up drop configuration loaded from RON files.
+//!
+//! GGRS CRITICAL: All fields use deterministic types (no f32/f64 for game logic).
+
+use bevy::{platform::collections::HashMap, prelude::*, reflect::TypePath};
+use bevy_fixed::fixed_math;
+use serde::{Deserialize, Serialize};
+
+use super::state::PowerUpKind;
+
+/// Main power-up configuration loaded from RON file
+#[derive(Asset, TypePath, Debug, Clone, Serialize, Deserialize)]
+pub struct PowerUpConfig {
+    // === Drops ===
+
+    /// Chance for a killed enemy to drop a power-up, in percent (0-100)
+    pub drop_chance_percent: u32,
+    /// Maximum number of power-ups dropped during a single wave
+    pub max_drops_per_wave: u32,
+    /// Power-up kind -> weight
+    /// Weights are relative, not percentages
+    pub drop_weights: HashMap<PowerUpKind, u32>,
+
+    // === Pickup ===
+
+    /// Distance within which a player collects a dropped power-up
+    pub pickup_radius: fixed_math::Fixed,
+    /// Frames a dropped power-up stays on the ground before disappearing
+    pub pickup_lifetime_frames: u32,
+
+    // === Effects (in frames at 60fps) ===
+
+    /// Duration of insta-kill
+    pub insta_kill_frames: u32,
+    /// Duration of double points
+    pub double_points_frames: u32,
+    /// Points given to every player when a nuke is collected
+    pub nuke_reward: u32,
+    /// Points given to every player when a carpenter is collected
+    pub carpenter_reward: u32,
+}
+
+impl Default for PowerUpConfig {
+    fn default() -> Self {
+        let mut drop_weights = HashMap::default();
+        drop_weights.insert(PowerUpKind::MaxAmmo, 25);
+        drop_weights.insert(PowerUpKind::InstaKill, 20);
+        drop_weights.insert(PowerUpKind::DoublePoints, 25);
+        drop_weights.insert(PowerUpKind::Nuke, 15);
+        drop_weights.insert(PowerUpKind::Carpenter, 15);
+
+        Self {
+            drop_chance_percent: 3,
+            max_drops_per_wave: 4,
+            drop_weights,
+            pickup_radius: fixed_math::new(16.0),
+            pickup_lifetime_frames: 1800,
+            insta_kill_frames: 1800,
+            double_points_frames: 1800,
+            nuke_reward: 400,
+            carpenter_reward: 200,
+        }
+    }
+}
+
+impl PowerUpConfig {
+    /// Drop weights sorted by kind.
+    ///
+    /// GGRS CRITICAL: Always iterate the weights through this to keep a deterministic order.
+    pub fn sorted_weights(&self) -> Vec<(PowerUpKind, u32)> {
+        let mut weights: Vec<_> = self
+            .drop_weights
+            .iter()
+            .filter(|(_, weight)| **weight > 0)
+            .map(|(kind, weight)| (*kind, *weight))
+            .collect();
+        weights.sort_by_key(|(kind, _)| *kind);
+        weights
+    }
+
+    /// Sum of all drop weights
+    pub fn total_weight(&self) -> u32 {
+        self.drop_weights.values().sum()
+    }
+
+    /// Pick a power-up kind given a roll in `0..total_weight()`
+    pub fn pick_kind(&self, roll: u32) -> Option<PowerUpKind> {
+        let mut cumulative = 0u32;
+        for (kind, weight) in self.sorted_weights() {
+            cumulative += weight;
+            if roll < cumulative {
+                return Some(kind);
+            }
+        }
+        None
+    }
+}
@@ -0,0 +1,83 @@
+//! Power-ups dropped by killed enemies.
+//!
+//! # Overview
+//!
+//! Every enemy killed by a player rolls a drop chance with `RollbackRng`. A
+//! dropped power-up is a rollback entity that any player can collect by
+//! walking over it before it expires:
+//!
+//! - **Max Ammo**: refill every weapon of every player
+//! - **Insta-Kill**: any player hit kills an enemy, for a limited time
+//! - **Double Points**: kill rewards are doubled, for a limited time
+//! - **Nuke**: kill every enemy alive
+//! - **Carpenter**: fully repair every window
+//!
+//! Timed effects are stored as end frames in the rollback `PowerUpState`.
+//!
+//! # Configuration
+//!
+//! Configure via `assets/waves/powerup_config.ron`. See `config::PowerUpConfig` for options.
+
+pub mod config;
+pub mod state;
+pub mod systems;
+pub mod ui;
+
+use bevy::prelude::*;
+use bevy_common_assets::ron::RonAssetPlugin;
+use bevy_ggrs::{GgrsSchedule, RollbackApp};
+
+use crate::character::health::{rollback_apply_accumulated_damage, rollback_apply_death};
+use crate::core::AppState;
+use crate::system_set::RollbackSystemSet;
+
+pub use config::PowerUpConfig;
+pub use state::{PowerUpKind, PowerUpPickup, PowerUpState};
+
+/// Plugin that adds power-up drops.
+pub struct PowerUpPlugin;
+
+impl Plugin for PowerUpPlugin {
+    fn build(&self, app: &mut App) {
+        // RON asset plugin for power-up configuration
+        app.add_plugins(RonAssetPlugin::<PowerUpConfig>::new(&["ron"]));
+
+        // Resources
+        app.init_resource::<PowerUpState>();
+
+        // Register types for reflection (useful for debugging)
+        app.register_type::<PowerUpKind>();
+
+        // Rollback registration
+        app.rollback_resource_with_clone::<PowerUpState>();
+        app.rollback_component_with_copy::<PowerUpPickup>();
+
+        // Pickups are collected with the other interactions, before movement
+        app.add_systems(
+            GgrsSchedule,
+            systems::powerup_pickup_system
+                .after(crate::weapons::upgrade::upgrade_station_phase_system)
+                .in_set(RollbackSystemSet::Interaction),
+        );
+
+        // Insta-kill rewrites the damage before it is applied,
+        // drops are rolled once Death is added but before the entity is despawned
+        app.add_systems(
+            GgrsSchedule,
+            (
+                systems::insta_kill_damage_system.before(rollback_apply_accumulated_damage),
+                systems::powerup_drop_system
+                    .after(rollback_apply_accumulated_damage)
+                    .before(rollback_apply_death),
+            )
+                .in_set(RollbackSystemSet::DeathManagement),
+        );
+
+        // Visuals (outside GGRS schedule), derived from the rollback state
+        app.add_systems(OnEnter(AppState::InGame), ui::setup_powerup_ui);
+        app.add_systems(
+            Update,
+            (ui::update_powerup_hud, ui::update_powerup_pickups).run_if(in_state(AppState::InGame)),
+        );
+    }
+}
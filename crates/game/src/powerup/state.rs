@@ -0,0 +1,111 @@
+//! Power-up state: dropped pickups and active timed effects.
+//!
+//! GGRS CRITICAL: `PowerUpState` must be registered for rollback and
+//! `PowerUpPickup` entities are rollback entities. The HUD is derived from them.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Kinds of power-ups an enemy can drop
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize, Reflect,
+)]
+pub enum PowerUpKind {
+    /// Refill the ammo of every weapon of every player
+    MaxAmmo,
+    /// Any player hit kills an enemy, for a limited time
+    InstaKill,
+    /// Kill rewards are doubled, for a limited time
+    DoublePoints,
+    /// Kill every enemy alive
+    Nuke,
+    /// Fully repair every window
+    Carpenter,
+}
+
+impl PowerUpKind {
+    /// Color of the dropped pickup (visual only)
+    pub fn color(&self) -> Color {
+        match self {
+            PowerUpKind::MaxAmmo => Color::srgb(0.2, 0.9, 0.2),
+            PowerUpKind::InstaKill => Color::srgb(0.9, 0.1, 0.1),
+            PowerUpKind::DoublePoints => Color::srgb(1.0, 0.85, 0.2),
+            PowerUpKind::Nuke => Color::srgb(1.0, 0.5, 0.0),
+            PowerUpKind::Carpenter => Color::srgb(0.6, 0.4, 0.2),
+        }
+    }
+}
+
+impl fmt::Display for PowerUpKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            PowerUpKind::MaxAmmo => "Max Ammo",
+            PowerUpKind::InstaKill => "Insta-Kill",
+            PowerUpKind::DoublePoints => "Double Points",
+            PowerUpKind::Nuke => "Nuke",
+            PowerUpKind::Carpenter => "Carpenter",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Power-up lying on the ground, waiting to be collected
+#[derive(Component, Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct PowerUpPickup {
+    pub kind: PowerUpKind,
+    /// Frame at which the pickup disappears if nobody collected it
+    pub expires_at_frame: u32,
+}
+
+/// Global power-up state resource
+///
+/// GGRS CRITICAL: Must be registered with `.rollback_resource_with_clone::<PowerUpState>()`
+#[derive(Resource, Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PowerUpState {
+    /// Insta-kill is active until this frame (exclusive)
+    pub insta_kill_until_frame: Option<u32>,
+    /// Double points is active until this frame (exclusive)
+    pub double_points_until_frame: Option<u32>,
+
+    // === Drop Tracking ===
+
+    /// Wave the drop counter belongs to
+    pub drops_wave: u32,
+    /// Power-ups dropped during `drops_wave`
+    pub drops_this_wave: u32,
+}
+
+impl PowerUpState {
+    pub fn is_insta_kill_active(&self, frame: u32) -> bool {
+        self.insta_kill_until_frame.is_some_and(|until| frame < until)
+    }
+
+    pub fn is_double_points_active(&self, frame: u32) -> bool {
+        self.double_points_until_frame.is_some_and(|until| frame < until)
+    }
+
+    /// Multiplier applied to kill rewards
+    pub fn points_multiplier(&self, frame: u32) -> u32 {
+        if self.is_double_points_active(frame) {
+            2
+        } else {
+            1
+        }
+    }
+
+    /// Timed effects still running with their remaining frames, in display order
+    pub fn active_effects(&self, frame: u32) -> Vec<(PowerUpKind, u32)> {
+        [
+            (PowerUpKind::InstaKill, self.insta_kill_until_frame),
+            (PowerUpKind::DoublePoints, self.double_points_until_frame),
+        ]
+        .into_iter()
+        .filter_map(|(kind, until)| {
+            until
+                .filter(|until| frame < *until)
+                .map(|until| (kind, until - frame))
+        })
+        .collect()
+    }
+}
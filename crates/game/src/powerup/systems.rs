@@ -0,0 +1,273 @@
+//! Power-up systems.
+//!
+//! GGRS CRITICAL: All systems must be deterministic.
+
+use bevy::{log::{tracing::span, Level}, prelude::*};
+use bevy_fixed::{fixed_math, rng::RollbackRng};
+use bevy_ggrs::{AddRollbackCommandExtension, Rollback};
+use map::game::entity::{map::window::WindowHealth, MapRollbackItem};
+use utils::{frame::FrameCount, net_id::{GgrsNetId, GgrsNetIdFactory}, order_iter, order_mut_iter};
+
+use crate::{
+    character::{
+        enemy::{ai::obstacle::Obstacle, archetype::EnemyLoot, Enemy},
+        health::{DamageAccumulator, Death, Health, HitBy},
+        player::Player,
+    },
+    economy::Points,
+    global_asset::GlobalAsset,
+    interaction::WindowRepairedEvent,
+    waves::WaveState,
+    weapons::{Weapon, WeaponInventory, WeaponModesState},
+};
+
+use super::{
+    config::PowerUpConfig,
+    state::{PowerUpKind, PowerUpPickup, PowerUpState},
+};
+
+/// Get the loaded power-up config, if any
+pub fn get_powerup_config<'a>(
+    global_assets: &GlobalAsset,
+    config_assets: &'a Assets<PowerUpConfig>,
+) -> Option<&'a PowerUpConfig> {
+    global_assets
+        .powerup_config
+        .as_ref()
+        .and_then(|h| config_assets.get(h))
+}
+
+/// System that rolls a power-up drop for every enemy killed by a player.
+///
+/// Runs after damage is applied (Death added) but before the entity is despawned.
+/// Enemies killed without a player hit (nuke, ...) never drop anything.
+/// The drop table of the enemy archetype overrides the config chance and weights.
+pub fn powerup_drop_system(
+    frame: Res<FrameCount>,
+    mut commands: Commands,
+    mut state: ResMut<PowerUpState>,
+    mut rng: ResMut<RollbackRng>,
+    mut id_factory: ResMut<GgrsNetIdFactory>,
+    wave_state: Res<WaveState>,
+    config_assets: Res<Assets<PowerUpConfig>>,
+    global_assets: Res<GlobalAsset>,
+    enemy_query: Query<
+        (&GgrsNetId, &fixed_math::FixedTransform3D, &Death, Option<&EnemyLoot>),
+        (With<Enemy>, With<Rollback>),
+    >,
+) {
+    let system_span = span!(Level::INFO, "ggrs", f = frame.frame, s = "powerup_drop");
+    let _enter = system_span.enter();
+
+    let Some(config) = get_powerup_config(&global_assets, &config_assets) else {
+        return;
+    };
+
+    // Reset the drop counter when a new wave starts
+    if state.drops_wave != wave_state.current_wave {
+        state.drops_wave = wave_state.current_wave;
+        state.drops_this_wave = 0;
+    }
+
+    for (enemy_id, transform, death, loot) in order_iter!(enemy_query) {
+        let killed_by_player = death
+            .last_hit_by
+            .as_ref()
+            .is_some_and(|hits| hits.iter().any(|hit| matches!(hit, HitBy::Player(_))));
+        if !killed_by_player || state.drops_this_wave >= config.max_drops_per_wave {
+            continue;
+        }
+
+        // GGRS CRITICAL: always consume the same rolls for a kill
+        let chance_roll = rng.next_u32_range(0, 100);
+        let drop_chance_percent = loot
+            .and_then(|loot| loot.drop_chance_percent)
+            .unwrap_or(config.drop_chance_percent);
+        if chance_roll >= drop_chance_percent {
+            continue;
+        }
+
+        let kind = match loot.filter(|loot| !loot.drop_weights.is_empty()) {
+            Some(loot) => {
+                let total_weight = loot.total_drop_weight();
+                loot.pick_drop(rng.next_u32_range(0, total_weight))
+            }
+            None => {
+                let total_weight = config.total_weight();
+                if total_weight == 0 {
+                    continue;
+                }
+                config.pick_kind(rng.next_u32_range(0, total_weight))
+            }
+        };
+        let Some(kind) = kind else {
+            continue;
+        };
+
+        state.drops_this_wave += 1;
+
+        let pickup_transform = fixed_math::FixedTransform3D::new(
+            transform.translation,
+            fixed_math::FixedMat3::IDENTITY,
+            fixed_math::FixedVec3::ONE,
+        );
+        let g_id = id_factory.next(format!("powerup_{:?}", kind));
+
+        info!(
+            "{} {} dropped by {} at {}",
+            frame.as_ref(), g_id, enemy_id, pickup_transform.translation
+        );
+
+        commands
+            .spawn((
+                PowerUpPickup {
+                    kind,
+                    expires_at_frame: frame.frame + config.pickup_lifetime_frames,
+                },
+                Sprite::from_color(kind.color(), Vec2::new(8.0, 8.0)),
+                pickup_transform.to_bevy_transform(),
+                pickup_transform,
+                g_id,
+            ))
+            .add_rollback();
+    }
+}
+
+/// System that turns player damage into a killing blow while insta-kill is active.
+///
+/// Runs before the accumulated damage is applied.
+pub fn insta_kill_damage_system(
+    frame: Res<FrameCount>,
+    state: Res<PowerUpState>,
+    mut enemy_query: Query<(&GgrsNetId, &Health, &mut DamageAccumulator), (With<Enemy>, With<Rollback>)>,
+) {
+    if !state.is_insta_kill_active(frame.frame) {
+        return;
+    }
+
+    for (_enemy_id, health, mut accumulator) in order_mut_iter!(enemy_query) {
+        let hit_by_player = accumulator
+            .last_hit_by
+            .as_ref()
+            .is_some_and(|hits| hits.iter().any(|hit| matches!(hit, HitBy::Player(_))));
+        if hit_by_player && accumulator.total_damage < health.current {
+            accumulator.total_damage = health.current;
+        }
+    }
+}
+
+/// System that lets players collect dropped power-ups and applies their effect.
+pub fn powerup_pickup_system(
+    frame: Res<FrameCount>,
+    mut commands: Commands,
+    mut state: ResMut<PowerUpState>,
+    config_assets: Res<Assets<PowerUpConfig>>,
+    global_assets: Res<GlobalAsset>,
+    mut window_repaired_writer: MessageWriter<WindowRepairedEvent>,
+    pickup_query: Query<(&GgrsNetId, Entity, &PowerUpPickup, &fixed_math::FixedTransform3D), With<Rollback>>,
+    mut player_query: Query<
+        (&GgrsNetId, &fixed_math::FixedTransform3D, &mut Points, &WeaponInventory),
+        (With<Player>, With<Rollback>),
+    >,
+    mut weapon_query: Query<(&Weapon, &mut WeaponModesState)>,
+    enemy_query: Query<(&GgrsNetId, Entity), (With<Enemy>, With<Rollback>, Without<Death>)>,
+    mut window_query: Query<
+        (&GgrsNetId, &MapRollbackItem, &mut WindowHealth, Option<&mut Obstacle>),
+        With<Rollback>,
+    >,
+) {
+    let system_span = span!(Level::INFO, "ggrs", f = frame.frame, s = "powerup_pickup");
+    let _enter = system_span.enter();
+
+    let Some(config) = get_powerup_config(&global_assets, &config_assets) else {
+        return;
+    };
+
+    // GGRS CRITICAL: player positions sorted by net id
+    let players: Vec<(GgrsNetId, fixed_math::FixedVec2)> = order_iter!(player_query)
+        .into_iter()
+        .map(|(net_id, transform, _, _)| (net_id.clone(), transform.translation.truncate()))
+        .collect();
+
+    for (pickup_id, pickup_entity, pickup, pickup_transform) in order_iter!(pickup_query) {
+        if frame.frame >= pickup.expires_at_frame {
+            info!("{} {} expired", frame.as_ref(), pickup_id);
+            commands.entity(pickup_entity).despawn();
+            continue;
+        }
+
+        let pickup_position = pickup_transform.translation.truncate();
+        let Some((collector_id, _)) = players
+            .iter()
+            .find(|(_, position)| position.distance(&pickup_position) <= config.pickup_radius)
+        else {
+            continue;
+        };
+
+        info!("{} {} collected {}", frame.as_ref(), collector_id, pickup.kind);
+        commands.entity(pickup_entity).despawn();
+
+        match pickup.kind {
+            PowerUpKind::MaxAmmo => {
+                for (_, _, _, inventory) in order_iter!(player_query) {
+                    for (weapon_entity, _) in inventory.weapons.iter() {
+                        if let Ok((weapon, mut modes_state)) = weapon_query.get_mut(*weapon_entity) {
+                            modes_state.refill(&weapon.config);
+                        }
+                    }
+                }
+            }
+            PowerUpKind::InstaKill => {
+                state.insta_kill_until_frame = Some(frame.frame + config.insta_kill_frames);
+            }
+            PowerUpKind::DoublePoints => {
+                state.double_points_until_frame = Some(frame.frame + config.double_points_frames);
+            }
+            PowerUpKind::Nuke => {
+                for (enemy_id, enemy_entity) in order_iter!(enemy_query) {
+                    info!("{} {} killed by nuke", frame.as_ref(), enemy_id);
+                    // No player hit: nuked enemies give no kill reward and drop nothing
+                    commands.entity(enemy_entity).insert(Death { last_hit_by: None });
+                }
+                for (_, _, mut points, _) in order_mut_iter!(player_query) {
+                    points.earn(config.nuke_reward);
+                }
+            }
+            PowerUpKind::Carpenter => {
+                for (window_id, rollback_item, mut window_health, obstacle_opt) in order_mut_iter!(window_query) {
+                    if window_health.current >= window_health.max {
+                        continue;
+                    }
+                    window_health.current = window_health.max;
+                    window_health.can_repair_after_frame = None;
+
+                    // Sync with Obstacle if present (same as a manual repair)
+                    if let Some(mut obstacle) = obstacle_opt {
+                        obstacle.blocks_movement = true;
+                        if obstacle.health.is_some() {
+                            obstacle.health = Some(window_health.max as u32);
+                        }
+                    }
+
+                    window_repaired_writer.write(WindowRepairedEvent {
+                        window_net_id: window_id.clone(),
+                        visual_entity: rollback_item.parent,
+                        new_health: window_health.current,
+                        max_health: window_health.max,
+                    });
+                }
+                for (_, _, mut points, _) in order_mut_iter!(player_query) {
+                    points.earn(config.carpenter_reward);
+                }
+            }
+        }
+    }
+
+    // Clear timed effects once they ran out
+    if !state.is_insta_kill_active(frame.frame) {
+        state.insta_kill_until_frame = None;
+    }
+    if !state.is_double_points_active(frame.frame) {
+        state.double_points_until_frame = None;
+    }
+}
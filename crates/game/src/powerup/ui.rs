@@ -0,0 +1,72 @@
+//! Power-up visuals.
+//!
+//! Everything here is derived every frame from the rollback `PowerUpState`,
+//! the `PowerUpPickup` entities and `FrameCount`.
+
+use bevy::prelude::*;
+use utils::frame::FrameCount;
+
+use super::state::{PowerUpPickup, PowerUpState};
+
+/// Frames before expiry during which a dropped pickup blinks
+const PICKUP_BLINK_FRAMES: u32 = 300;
+
+/// Component marker for the active power-ups HUD text
+#[derive(Component)]
+pub struct PowerUpHudText;
+
+pub fn setup_powerup_ui(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let font = asset_server.load("fonts/FiraMono-Medium.ttf");
+
+    commands.spawn((
+        PowerUpHudText,
+        Text::new(""),
+        TextFont {
+            font,
+            font_size: 14.0,
+            ..Default::default()
+        },
+        TextColor(Color::srgb(1.0, 0.5, 0.0)),
+        TextLayout::new_with_justify(Justify::Right),
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(22.0),
+            right: Val::Px(5.0),
+            ..default()
+        },
+    ));
+}
+
+/// List the active timed power-ups with their remaining seconds
+pub fn update_powerup_hud(
+    frame: Res<FrameCount>,
+    state: Res<PowerUpState>,
+    mut text_query: Query<&mut Text, With<PowerUpHudText>>,
+) {
+    let Ok(mut text) = text_query.single_mut() else {
+        return;
+    };
+
+    text.0 = state
+        .active_effects(frame.frame)
+        .iter()
+        .map(|(kind, remaining_frames)| format!("{} {}s", kind, remaining_frames.div_ceil(60)))
+        .collect::<Vec<_>>()
+        .join("\n");
+}
+
+/// Blink the dropped pickups that are about to expire
+pub fn update_powerup_pickups(
+    frame: Res<FrameCount>,
+    mut pickup_query: Query<(&PowerUpPickup, &mut Visibility)>,
+) {
+    for (pickup, mut visibility) in pickup_query.iter_mut() {
+        let remaining = pickup.expires_at_frame.saturating_sub(frame.frame);
+        let hidden = remaining < PICKUP_BLINK_FRAMES && (remaining / 10) % 2 == 0;
+        visibility.set_if_neq(if hidden {
+            Visibility::Hidden
+        } else {
+            Visibility::Inherited
+        });
+    }
+}
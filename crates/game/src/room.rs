@@ -0,0 +1,124 @@
+//! Rooms unlocked by the players.
+//!
+//! Every LDtk level is a room (`RoomComponent`, `RoomBounds`, `LevelId`).
+//! A room is unlocked when it is a spawn room, when a player stands in it or
+//! when a door leading to it is opened. Enemy spawners are only used when
+//! they are in an unlocked room or just outside of one (the perimeter, for
+//! spawners placed behind the windows of a room).
+//!
+//! GGRS CRITICAL: `UnlockedRooms` is a rollback resource, all changes must
+//! happen inside the `GgrsSchedule`. The room entities are map entities and
+//! are the same on every peer.
+
+use bevy::{ecs::system::SystemParam, log::{tracing::span, Level}, prelude::*};
+use bevy_fixed::fixed_math;
+use bevy_ggrs::{GgrsSchedule, Rollback, RollbackApp};
+use map::game::entity::map::{
+    level_id::LevelId,
+    room::{RoomBounds, RoomComponent},
+};
+use serde::{Deserialize, Serialize};
+use utils::frame::FrameCount;
+
+use crate::{
+    character::player::Player,
+    interaction::handle_door_interaction,
+    system_set::RollbackSystemSet,
+};
+
+/// Distance outside of an unlocked room where spawners are still used
+pub const ROOM_PERIMETER_MARGIN: f32 = 64.0;
+
+/// Level iids of the unlocked rooms, kept sorted
+///
+/// GGRS CRITICAL: Must be registered with `.rollback_resource_with_clone::<UnlockedRooms>()`
+#[derive(Resource, Clone, Debug, Default, Serialize, Deserialize)]
+pub struct UnlockedRooms {
+    pub level_ids: Vec<String>,
+}
+
+impl UnlockedRooms {
+    pub fn is_unlocked(&self, level_id: &str) -> bool {
+        self.level_ids.binary_search_by(|id| id.as_str().cmp(level_id)).is_ok()
+    }
+
+    /// Unlock a room, return false if it was already unlocked
+    pub fn unlock(&mut self, level_id: &str) -> bool {
+        match self.level_ids.binary_search_by(|id| id.as_str().cmp(level_id)) {
+            Ok(_) => false,
+            Err(index) => {
+                self.level_ids.insert(index, level_id.to_string());
+                true
+            }
+        }
+    }
+}
+
+/// Rooms and their unlocked state, used by the enemy spawners
+#[derive(SystemParam)]
+pub struct SpawnerRooms<'w, 's> {
+    pub unlocked: Res<'w, UnlockedRooms>,
+    pub rooms: Query<'w, 's, (&'static RoomBounds, &'static LevelId)>,
+}
+
+impl SpawnerRooms<'_, '_> {
+    /// Room a spawner is active for: its own room if unlocked, otherwise the
+    /// unlocked room whose perimeter it is in. `None` if the spawner is locked.
+    pub fn active_room(&self, level_id: &LevelId, position: fixed_math::FixedVec2) -> Option<String> {
+        if self.unlocked.is_unlocked(&level_id.0) {
+            return Some(level_id.0.clone());
+        }
+
+        // Lowest level iid first so every peer picks the same room
+        let margin = fixed_math::new(ROOM_PERIMETER_MARGIN);
+        self.rooms
+            .iter()
+            .filter(|(bounds, id)| {
+                self.unlocked.is_unlocked(&id.0) && bounds.contains_with_margin(position, margin)
+            })
+            .map(|(_, id)| id.0.clone())
+            .min()
+    }
+}
+
+/// Unlock the spawn rooms and the rooms the players are standing in.
+///
+/// Runs after the door interactions so rooms opened this frame are logged once.
+pub fn rollback_unlock_occupied_rooms(
+    frame: Res<FrameCount>,
+    mut unlocked_rooms: ResMut<UnlockedRooms>,
+    room_query: Query<(&RoomComponent, &RoomBounds, &LevelId)>,
+    player_query: Query<&fixed_math::FixedTransform3D, (With<Player>, With<Rollback>)>,
+) {
+    let system_span = span!(Level::INFO, "ggrs", f = frame.frame, s = "unlock_rooms");
+    let _enter = system_span.enter();
+
+    for (room, bounds, level_id) in room_query.iter() {
+        let occupied = room.config.spawn
+            || player_query
+                .iter()
+                .any(|transform| bounds.contains(transform.translation.truncate()));
+
+        if occupied && unlocked_rooms.unlock(&level_id.0) {
+            info!("{} room {} unlocked", frame.as_ref(), level_id.0);
+        }
+    }
+}
+
+/// Plugin that tracks the unlocked rooms.
+pub struct RoomPlugin;
+
+impl Plugin for RoomPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<UnlockedRooms>();
+
+        app.rollback_resource_with_clone::<UnlockedRooms>();
+
+        app.add_systems(
+            GgrsSchedule,
+            rollback_unlock_occupied_rooms
+                .after(handle_door_interaction)
+                .in_set(RollbackSystemSet::Interaction),
+        );
+    }
+}
@@ -0,0 +1,88 @@
+//! Throwables configuration loaded from RON files.
+//!
+//! GGRS CRITICAL: All fields used by game logic use deterministic types
+//! (no f32/f64), `color` is only used by the visuals.
+
+use bevy::{platform::collections::HashMap, prelude::*, reflect::TypePath};
+use bevy_fixed::fixed_math;
+use serde::{Deserialize, Serialize};
+
+/// What a throwable does once thrown
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ThrowableKind {
+    /// Explodes when the fuse ends
+    Grenade {
+        blast_radius: fixed_math::Fixed,
+        damage: fixed_math::Fixed,
+    },
+    /// Attracts the enemies in `lure_radius` until the fuse ends, then explodes
+    Lure {
+        lure_radius: fixed_math::Fixed,
+        blast_radius: fixed_math::Fixed,
+        damage: fixed_math::Fixed,
+    },
+}
+
+impl ThrowableKind {
+    /// Blast radius and damage of the explosion at the end of the fuse
+    pub fn blast(&self) -> (fixed_math::Fixed, fixed_math::Fixed) {
+        match *self {
+            ThrowableKind::Grenade { blast_radius, damage }
+            | ThrowableKind::Lure { blast_radius, damage, .. } => (blast_radius, damage),
+        }
+    }
+}
+
+/// A throwable the players carry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThrowableDefinition {
+    pub display_name: String,
+    pub kind: ThrowableKind,
+
+    // === Flight (per second, converted to per frame when thrown) ===
+
+    /// Horizontal speed when thrown
+    pub throw_speed: fixed_math::Fixed,
+    /// Upward speed when thrown, gives the arc
+    pub launch_speed: fixed_math::Fixed,
+    /// Downward acceleration of the arc
+    pub gravity: fixed_math::Fixed,
+    /// Fraction of the horizontal speed kept each frame while rolling on the ground
+    pub friction: fixed_math::Fixed,
+    /// Fraction of the speed kept when bouncing on a wall or the ground
+    pub bounciness: fixed_math::Fixed,
+
+    /// Frames between the throw and the detonation
+    pub fuse_frames: u32,
+
+    // === Refill ===
+
+    /// Maximum carried
+    pub max_count: u32,
+    /// Given to every player at the start of each wave (up to `max_count`)
+    pub refill_per_wave: u32,
+
+    /// Color of the projectile (visual only)
+    pub color: (f32, f32, f32),
+}
+
+/// Main throwables configuration loaded from RON file
+#[derive(Asset, TypePath, Debug, Clone, Serialize, Deserialize)]
+pub struct ThrowableConfig {
+    /// Throwable ids of the player equipment slots, in order
+    pub slots: Vec<String>,
+    /// Frames between two throws of the same player
+    pub throw_cooldown_frames: u32,
+    /// Throwable id -> definition
+    pub throwables: HashMap<String, ThrowableDefinition>,
+}
+
+impl Default for ThrowableConfig {
+    fn default() -> Self {
+        Self {
+            slots: Vec::new(),
+            throw_cooldown_frames: 30,
+            throwables: HashMap::default(),
+        }
+    }
+}
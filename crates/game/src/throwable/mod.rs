@@ -0,0 +1,82 @@
+//! Throwable grenades and lures.
+//!
+//! # Overview
+//!
+//! Every player carries throwables in slots (frag grenades, monkey bombs,
+//! ...), refilled at the start of each wave. The throw button throws the
+//! active slot toward the cursor, modifier + throw switches slots.
+//!
+//! A thrown `Grenade` flies in an arc, bounces on the ground and the walls,
+//! rolls until friction stops it and explodes when its fuse ends through
+//! `weapons::explosion`, the same blast as the explosive bullets:
+//!
+//! - **Grenade**: explodes, nothing else
+//! - **Lure**: enemies in its radius walk straight to it until it explodes
+//!
+//! # Configuration
+//!
+//! Configure via `assets/throwables/throwable_config.ron`. See
+//! `config::ThrowableConfig` for options.
+
+pub mod config;
+pub mod state;
+pub mod systems;
+pub mod ui;
+
+use bevy::prelude::*;
+use bevy_common_assets::ron::RonAssetPlugin;
+use bevy_ggrs::{GgrsSchedule, RollbackApp};
+
+use crate::character::enemy::ai::{
+    behavior::enemy_target_selection, pathing::update_enemy_targets,
+};
+use crate::core::AppState;
+use crate::system_set::RollbackSystemSet;
+use crate::weapons::explosion::explosion_rollback_system;
+
+pub use config::{ThrowableConfig, ThrowableDefinition, ThrowableKind};
+pub use state::{Grenade, ThrowableSlot, Throwables};
+
+/// Plugin that adds the throwables.
+pub struct ThrowablePlugin;
+
+impl Plugin for ThrowablePlugin {
+    fn build(&self, app: &mut App) {
+        // RON asset plugin for throwable configuration
+        app.add_plugins(RonAssetPlugin::<ThrowableConfig>::new(&["ron"]));
+
+        // Rollback registration
+        app.rollback_component_with_clone::<Throwables>()
+            .rollback_component_with_clone::<Grenade>();
+
+        // Throws and detonations happen with the other weapons, the blast is
+        // applied by the explosion system in the same frame
+        app.add_systems(
+            GgrsSchedule,
+            (
+                systems::rollback_refill_throwables,
+                systems::rollback_throw_system.after(systems::rollback_refill_throwables),
+                systems::rollback_grenade_physics_system.after(systems::rollback_throw_system),
+            )
+                .before(explosion_rollback_system)
+                .in_set(RollbackSystemSet::Weapon),
+        );
+
+        // Lures override the player target before the paths are updated
+        app.add_systems(
+            GgrsSchedule,
+            systems::rollback_lure_enemies
+                .after(enemy_target_selection)
+                .before(update_enemy_targets)
+                .in_set(RollbackSystemSet::EnemyAI),
+        );
+
+        // Visuals (outside GGRS schedule), derived from the rollback state
+        app.add_systems(OnEnter(AppState::InGame), ui::setup_throwable_ui);
+        app.add_systems(
+            Update,
+            (ui::update_throwable_hud, ui::update_grenade_sprites)
+                .run_if(in_state(AppState::InGame)),
+        );
+    }
+}
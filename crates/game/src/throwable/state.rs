@@ -0,0 +1,77 @@
+//! Throwables carried by the players and thrown projectiles.
+//!
+//! GGRS CRITICAL: `Throwables` and `Grenade` are rollback components, all
+//! changes must happen inside the `GgrsSchedule`.
+
+use bevy::prelude::*;
+use bevy_fixed::fixed_math;
+use ggrs::PlayerHandle;
+use serde::{Deserialize, Serialize};
+
+use super::config::ThrowableKind;
+
+/// A throwable equipment slot
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ThrowableSlot {
+    pub id: String,
+    pub count: u32,
+}
+
+/// Throwables carried by a player
+///
+/// GGRS CRITICAL: Must be registered with `.rollback_component_with_clone::<Throwables>()`
+#[derive(Component, Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Throwables {
+    /// Slots in the config order, created on the first refill
+    pub slots: Vec<ThrowableSlot>,
+    pub active_slot: usize,
+    /// Wave of the last refill (`None` until the first refill)
+    pub refilled_wave: Option<u32>,
+    /// Throw button state of the previous frame, throws are one-shot
+    pub throw_held: bool,
+    /// A new throw is allowed from this frame
+    pub next_throw_frame: u32,
+}
+
+impl Throwables {
+    pub fn active(&self) -> Option<&ThrowableSlot> {
+        self.slots.get(self.active_slot)
+    }
+
+    /// Switch to the next slot
+    pub fn cycle(&mut self) {
+        if !self.slots.is_empty() {
+            self.active_slot = (self.active_slot + 1) % self.slots.len();
+        }
+    }
+}
+
+/// A thrown grenade (or lure) flying, rolling and bouncing until its fuse ends
+///
+/// The arc is simulated with a height above the ground, the projectile only
+/// slows down from friction while rolling on the ground.
+///
+/// GGRS CRITICAL: Must be registered with `.rollback_component_with_clone::<Grenade>()`
+#[derive(Component, Clone, Debug, Serialize, Deserialize)]
+pub struct Grenade {
+    pub throwable: String,
+    pub kind: ThrowableKind,
+    /// Horizontal velocity in pixels per frame
+    pub velocity: fixed_math::FixedVec2,
+    /// Height above the ground in pixels
+    pub height: fixed_math::Fixed,
+    /// Vertical velocity in pixels per frame
+    pub vertical_velocity: fixed_math::Fixed,
+    /// Vertical acceleration in pixels per frame squared
+    pub gravity: fixed_math::Fixed,
+    pub friction: fixed_math::Fixed,
+    pub bounciness: fixed_math::Fixed,
+    pub detonate_at_frame: u32,
+    pub player_handle: PlayerHandle,
+}
+
+impl Grenade {
+    pub fn is_on_ground(&self) -> bool {
+        self.height <= fixed_math::FIXED_ZERO
+    }
+}
@@ -0,0 +1,382 @@
+//! Throwable systems.
+//!
+//! GGRS CRITICAL: All systems must be deterministic.
+
+use animation::FacingDirection;
+use bevy::{log::{tracing::span, Level}, prelude::*};
+use bevy_fixed::fixed_math;
+use bevy_ggrs::{AddRollbackCommandExtension, PlayerInputs, Rollback};
+use utils::{
+    frame::FrameCount,
+    net_id::{GgrsNetId, GgrsNetIdFactory},
+    order_iter, order_mut_iter,
+};
+
+use crate::{
+    character::{
+        enemy::{
+            ai::state::{EnemyTarget, MonsterState, TargetType},
+            Enemy,
+        },
+        player::{
+            downed::Downed,
+            input::{INPUT_MODIFIER, INPUT_THROW},
+            jjrs::PeerConfig,
+            Player,
+        },
+        status_effect::StatusEffects,
+    },
+    collider::{is_colliding, Collider, ColliderShape, Wall},
+    global_asset::GlobalAsset,
+    waves::WaveState,
+    weapons::explosion::spawn_explosion,
+    GAME_SPEED,
+};
+
+use super::{
+    config::{ThrowableConfig, ThrowableKind},
+    state::{Grenade, ThrowableSlot, Throwables},
+};
+
+/// Radius of the projectile against the walls
+const GRENADE_RADIUS: f32 = 3.0;
+
+/// Size of the projectile sprite in pixels
+pub const GRENADE_SPRITE_SIZE: f32 = 5.0;
+
+/// Ground bounces slower than this (pixels per frame) stop the arc
+const MIN_BOUNCE_SPEED: f32 = 0.5;
+
+/// Get the loaded throwable config, if any
+pub fn get_throwable_config<'a>(
+    global_assets: &GlobalAsset,
+    config_assets: &'a Assets<ThrowableConfig>,
+) -> Option<&'a ThrowableConfig> {
+    global_assets
+        .throwable_config
+        .as_ref()
+        .and_then(|h| config_assets.get(h))
+}
+
+/// System that gives every player their throwables at the start of each wave.
+///
+/// Slots are created from the config on the first refill, the counts are
+/// capped to `max_count`.
+pub fn rollback_refill_throwables(
+    frame: Res<FrameCount>,
+    wave_state: Res<WaveState>,
+    config_assets: Res<Assets<ThrowableConfig>>,
+    global_assets: Res<GlobalAsset>,
+    mut player_query: Query<(&GgrsNetId, &mut Throwables), (With<Player>, With<Rollback>)>,
+) {
+    let system_span = span!(Level::INFO, "ggrs", f = frame.frame, s = "refill_throwables");
+    let _enter = system_span.enter();
+
+    let Some(config) = get_throwable_config(&global_assets, &config_assets) else {
+        return;
+    };
+
+    for (g_id, mut throwables) in order_mut_iter!(player_query) {
+        if throwables.refilled_wave == Some(wave_state.current_wave) {
+            continue;
+        }
+        throwables.refilled_wave = Some(wave_state.current_wave);
+
+        for id in config.slots.iter() {
+            if !throwables.slots.iter().any(|slot| &slot.id == id) {
+                throwables.slots.push(ThrowableSlot {
+                    id: id.clone(),
+                    count: 0,
+                });
+            }
+        }
+
+        for slot in throwables.slots.iter_mut() {
+            let Some(definition) = config.throwables.get(&slot.id) else {
+                continue;
+            };
+            slot.count = (slot.count + definition.refill_per_wave).min(definition.max_count);
+        }
+
+        info!("{} throwables refilled for wave {}", g_id, wave_state.current_wave);
+    }
+}
+
+/// System that handles the throw button.
+///
+/// Throws are one-shot: the button must be released between two throws.
+/// Modifier + throw switches to the next throwable slot instead.
+pub fn rollback_throw_system(
+    frame: Res<FrameCount>,
+    mut commands: Commands,
+    inputs: Res<PlayerInputs<PeerConfig>>,
+    mut id_factory: ResMut<GgrsNetIdFactory>,
+    config_assets: Res<Assets<ThrowableConfig>>,
+    global_assets: Res<GlobalAsset>,
+    mut player_query: Query<
+        (
+            &GgrsNetId,
+            &Player,
+            &fixed_math::FixedTransform3D,
+            &FacingDirection,
+            &mut Throwables,
+            Option<&Downed>,
+            Option<&StatusEffects>,
+        ),
+        With<Rollback>,
+    >,
+) {
+    let system_span = span!(Level::INFO, "ggrs", f = frame.frame, s = "throw");
+    let _enter = system_span.enter();
+
+    let Some(config) = get_throwable_config(&global_assets, &config_assets) else {
+        return;
+    };
+
+    for (g_id, player, transform, facing_direction, mut throwables, downed, status_effects) in
+        order_mut_iter!(player_query)
+    {
+        let (input, _status) = inputs[player.handle];
+
+        let is_holding = input.buttons & INPUT_THROW != 0;
+        let just_pressed = is_holding && !throwables.throw_held;
+        throwables.throw_held = is_holding;
+
+        if !just_pressed {
+            continue;
+        }
+
+        if input.buttons & INPUT_MODIFIER != 0 {
+            throwables.cycle();
+            continue;
+        }
+
+        // Downed and stunned players can't throw
+        if downed.is_some() || status_effects.is_some_and(|status| status.is_stunned(frame.frame)) {
+            continue;
+        }
+
+        if frame.frame < throwables.next_throw_frame {
+            continue;
+        }
+
+        let active_slot = throwables.active_slot;
+        let Some(slot) = throwables.slots.get_mut(active_slot) else {
+            continue;
+        };
+        if slot.count == 0 {
+            continue;
+        }
+        let Some(definition) = config.throwables.get(&slot.id) else {
+            continue;
+        };
+        slot.count -= 1;
+        let throwable_id = slot.id.clone();
+        throwables.next_throw_frame = frame.frame + config.throw_cooldown_frames;
+
+        // Thrown toward the cursor, or in front of the player without one
+        let mut direction = fixed_math::FixedVec2::new(
+            fixed_math::Fixed::from_num(input.pan_x),
+            fixed_math::Fixed::from_num(input.pan_y),
+        )
+        .normalize_or_zero();
+        if direction == fixed_math::FixedVec2::ZERO {
+            direction = fixed_math::FixedVec2::new(
+                fixed_math::Fixed::from_num(facing_direction.to_int()),
+                fixed_math::FIXED_ZERO,
+            );
+        }
+
+        let grenade = Grenade {
+            throwable: throwable_id.clone(),
+            kind: definition.kind.clone(),
+            velocity: direction * (definition.throw_speed / *GAME_SPEED),
+            height: fixed_math::FIXED_ZERO,
+            vertical_velocity: definition.launch_speed / *GAME_SPEED,
+            gravity: definition.gravity / (*GAME_SPEED * *GAME_SPEED),
+            friction: definition.friction,
+            bounciness: definition.bounciness,
+            detonate_at_frame: frame.frame + definition.fuse_frames,
+            player_handle: player.handle,
+        };
+
+        let grenade_transform = fixed_math::FixedTransform3D::new(
+            fixed_math::FixedVec3::new(
+                transform.translation.x,
+                transform.translation.y,
+                fixed_math::FIXED_ZERO,
+            ),
+            fixed_math::FixedMat3::IDENTITY,
+            fixed_math::FixedVec3::ONE,
+        );
+
+        let grenade_id = id_factory.next(throwable_id.clone());
+        info!("{} throws {} {}", g_id, throwable_id, grenade_id);
+
+        let (r, g, b) = definition.color;
+        commands
+            .spawn((
+                grenade,
+                Sprite::from_color(
+                    Color::srgb(r, g, b),
+                    Vec2::new(GRENADE_SPRITE_SIZE, GRENADE_SPRITE_SIZE),
+                ),
+                grenade_transform.to_bevy_transform(),
+                grenade_transform,
+                grenade_id,
+            ))
+            .add_rollback();
+    }
+}
+
+/// System that moves the thrown projectiles and detonates them.
+///
+/// The arc bounces on the ground until it runs out of speed, the projectile
+/// then rolls and slows down from friction. Walls bounce it back on the
+/// blocked axis.
+pub fn rollback_grenade_physics_system(
+    frame: Res<FrameCount>,
+    mut commands: Commands,
+    mut id_factory: ResMut<GgrsNetIdFactory>,
+    mut grenade_query: Query<
+        (&GgrsNetId, Entity, &mut fixed_math::FixedTransform3D, &mut Grenade),
+        With<Rollback>,
+    >,
+    wall_query: Query<
+        (&fixed_math::FixedTransform3D, &Collider),
+        (With<Wall>, Without<Grenade>),
+    >,
+) {
+    let system_span = span!(Level::INFO, "ggrs", f = frame.frame, s = "grenade_physics");
+    let _enter = system_span.enter();
+
+    let walls: Vec<_> = wall_query.iter().collect();
+    let grenade_collider = Collider {
+        offset: fixed_math::FixedVec3::ZERO,
+        shape: ColliderShape::Circle {
+            radius: fixed_math::new(GRENADE_RADIUS),
+        },
+    };
+    let hits_wall = |position: fixed_math::FixedVec2| {
+        let position = fixed_math::FixedVec3::new(position.x, position.y, fixed_math::FIXED_ZERO);
+        walls.iter().any(|(wall_transform, wall_collider)| {
+            is_colliding(&position, &grenade_collider, &wall_transform.translation, wall_collider)
+        })
+    };
+
+    for (g_id, entity, mut transform, mut grenade) in order_mut_iter!(grenade_query) {
+        let position = transform.translation.truncate();
+
+        if frame.frame >= grenade.detonate_at_frame {
+            info!("{} detonates at {}", g_id, position);
+            let (blast_radius, damage) = grenade.kind.blast();
+            spawn_explosion(
+                &mut commands,
+                position,
+                blast_radius,
+                damage,
+                grenade.player_handle,
+                &mut id_factory,
+            );
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        // Arc
+        if !grenade.is_on_ground() || grenade.vertical_velocity > fixed_math::FIXED_ZERO {
+            grenade.height += grenade.vertical_velocity;
+            grenade.vertical_velocity -= grenade.gravity;
+
+            if grenade.height <= fixed_math::FIXED_ZERO {
+                grenade.height = fixed_math::FIXED_ZERO;
+                let bounce = -grenade.vertical_velocity * grenade.bounciness;
+                grenade.vertical_velocity = if bounce > fixed_math::new(MIN_BOUNCE_SPEED) {
+                    bounce
+                } else {
+                    fixed_math::FIXED_ZERO
+                };
+            }
+        }
+
+        if grenade.is_on_ground() {
+            grenade.velocity = grenade.velocity * grenade.friction;
+        }
+
+        // Wall bounces, each blocked axis is reversed
+        let mut velocity = grenade.velocity;
+        if hits_wall(position + velocity) {
+            if hits_wall(position + fixed_math::FixedVec2::new(velocity.x, fixed_math::FIXED_ZERO)) {
+                velocity.x = -velocity.x * grenade.bounciness;
+            }
+            if hits_wall(position + fixed_math::FixedVec2::new(fixed_math::FIXED_ZERO, velocity.y)) {
+                velocity.y = -velocity.y * grenade.bounciness;
+            }
+        }
+        grenade.velocity = velocity;
+
+        let next_position = position + velocity;
+        if !hits_wall(next_position) {
+            transform.translation.x = next_position.x;
+            transform.translation.y = next_position.y;
+        }
+    }
+}
+
+/// System that pulls the enemies near a lure toward it.
+///
+/// Runs after `enemy_target_selection` and overrides its player target, the
+/// enemies go back to the players once the lure detonates.
+pub fn rollback_lure_enemies(
+    frame: Res<FrameCount>,
+    grenade_query: Query<(&GgrsNetId, &fixed_math::FixedTransform3D, &Grenade), With<Rollback>>,
+    mut enemy_query: Query<
+        (
+            &GgrsNetId,
+            &fixed_math::FixedTransform3D,
+            &mut EnemyTarget,
+            &mut MonsterState,
+        ),
+        (With<Enemy>, Without<Grenade>),
+    >,
+) {
+    let system_span = span!(Level::INFO, "ggrs", f = frame.frame, s = "lure_enemies");
+    let _enter = system_span.enter();
+
+    // GGRS CRITICAL: lures sorted by net id, the first one in range wins
+    let lures: Vec<(fixed_math::FixedVec2, fixed_math::Fixed)> = order_iter!(grenade_query)
+        .into_iter()
+        .filter_map(|(_, transform, grenade)| match grenade.kind {
+            ThrowableKind::Lure { lure_radius, .. } => {
+                Some((transform.translation.truncate(), lure_radius))
+            }
+            ThrowableKind::Grenade { .. } => None,
+        })
+        .collect();
+
+    if lures.is_empty() {
+        return;
+    }
+
+    for (_g_id, transform, mut target, mut state) in order_mut_iter!(enemy_query) {
+        // Enemies breaching or climbing through a window finish it first
+        if matches!(
+            *state,
+            MonsterState::Dead | MonsterState::Breaching { .. } | MonsterState::Vaulting { .. }
+        ) {
+            continue;
+        }
+
+        let enemy_pos = transform.translation.truncate();
+        let Some((lure_pos, _)) = lures
+            .iter()
+            .find(|(lure_pos, lure_radius)| enemy_pos.distance(lure_pos) <= *lure_radius)
+        else {
+            continue;
+        };
+
+        target.target = None;
+        target.target_type = TargetType::Lure;
+        target.last_known_position = Some(*lure_pos);
+        *state = MonsterState::Chasing;
+    }
+}
@@ -0,0 +1,90 @@
+//! Throwable HUD and projectile visuals.
+//!
+//! Derived every frame from the rollback `Throwables` of the local player
+//! and the `Grenade` entities.
+
+use bevy::prelude::*;
+use bevy_fixed::fixed_math;
+
+use crate::{character::player::LocalPlayer, global_asset::GlobalAsset};
+
+use super::{
+    config::ThrowableConfig,
+    state::{Grenade, Throwables},
+    systems::get_throwable_config,
+};
+
+/// Extra sprite size per pixel of height, fakes the arc from above
+const HEIGHT_SCALE: f32 = 0.15;
+
+/// Component marker for the throwables HUD text
+#[derive(Component)]
+pub struct ThrowableHudText;
+
+pub fn setup_throwable_ui(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let font = asset_server.load("fonts/FiraMono-Medium.ttf");
+
+    commands.spawn((
+        ThrowableHudText,
+        Text::new(""),
+        TextFont {
+            font,
+            font_size: 14.0,
+            ..Default::default()
+        },
+        TextColor(Color::srgb(0.8, 1.0, 0.6)),
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(105.0),
+            left: Val::Px(5.0),
+            ..default()
+        },
+    ));
+}
+
+/// List the throwables of the local player, the active one is marked
+pub fn update_throwable_hud(
+    global_assets: Res<GlobalAsset>,
+    config_assets: Res<Assets<ThrowableConfig>>,
+    player_query: Query<&Throwables, With<LocalPlayer>>,
+    mut text_query: Query<&mut Text, With<ThrowableHudText>>,
+) {
+    let Ok(mut text) = text_query.single_mut() else {
+        return;
+    };
+
+    let Some(throwables) = player_query.iter().next() else {
+        text.0.clear();
+        return;
+    };
+
+    let config = get_throwable_config(&global_assets, &config_assets);
+
+    text.0 = throwables
+        .slots
+        .iter()
+        .enumerate()
+        .map(|(index, slot)| {
+            let name = config
+                .and_then(|config| config.throwables.get(&slot.id))
+                .map_or(slot.id.as_str(), |definition| definition.display_name.as_str());
+            let marker = if index == throwables.active_slot { ">" } else { " " };
+            format!("{} {} x{}", marker, name, slot.count)
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+}
+
+/// Grow the projectile sprites with their height above the ground
+pub fn update_grenade_sprites(mut grenade_query: Query<(&Grenade, &mut Sprite)>) {
+    for (grenade, mut sprite) in grenade_query.iter_mut() {
+        let Some(size) = sprite.custom_size else {
+            continue;
+        };
+        let base_size = super::systems::GRENADE_SPRITE_SIZE;
+        let scaled = base_size + fixed_math::to_f32(grenade.height) * HEIGHT_SCALE;
+        if size.x != scaled {
+            sprite.custom_size = Some(Vec2::splat(scaled));
+        }
+    }
+}
@@ -1,12 +1,12 @@
 use bevy::prelude::*;
-use crate::character::health::PlayerDiedEvent;
 use crate::core::AppState;
+use crate::game_mode::GameOutcome;
 
 pub struct GameOverUiPlugin;
 
 impl Plugin for GameOverUiPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, handle_player_death.run_if(in_state(AppState::InGame)));
+        app.add_systems(Update, handle_game_outcome.run_if(in_state(AppState::InGame)));
         app.add_systems(Update, button_system.run_if(in_state(AppState::InGame)));
     }
 }
@@ -17,22 +17,34 @@ struct GameOverUiRoot;
 #[derive(Component)]
 struct ReloadButton;
 
-fn handle_player_death(
+// Show the end screen once the game mode decided the outcome of the match
+fn handle_game_outcome(
     mut commands: Commands,
-    mut events: EventReader<PlayerDiedEvent>,
+    outcome: Res<GameOutcome>,
     q_existing_ui: Query<Entity, With<GameOverUiRoot>>,
 ) {
-    if !q_existing_ui.is_empty() {
-        return;
-    }
-
-    for _event in events.read() {
-        spawn_game_over_ui(&mut commands);
-        break; 
+    match *outcome {
+        GameOutcome::InProgress => {
+            // A rollback can revert the outcome
+            for entity in q_existing_ui.iter() {
+                commands.entity(entity).despawn();
+            }
+        }
+        GameOutcome::Victory | GameOutcome::Defeat => {
+            if q_existing_ui.is_empty() {
+                spawn_game_over_ui(&mut commands, *outcome);
+            }
+        }
     }
 }
 
-fn spawn_game_over_ui(commands: &mut Commands) {
+fn spawn_game_over_ui(commands: &mut Commands, outcome: GameOutcome) {
+    let (title, background) = if outcome == GameOutcome::Victory {
+        ("VICTORY", Color::srgba(0.0, 0.4, 0.0, 0.5)) // Green tint
+    } else {
+        ("GAME OVER", Color::srgba(0.5, 0.0, 0.0, 0.5)) // Red tint
+    };
+
     commands.spawn((
         Node {
             width: Val::Percent(100.0),
@@ -43,11 +55,11 @@ fn spawn_game_over_ui(commands: &mut Commands) {
             flex_direction: FlexDirection::Column,
             ..default()
         },
-        BackgroundColor(Color::srgba(0.5, 0.0, 0.0, 0.5)), // Red tint
+        BackgroundColor(background),
         GameOverUiRoot,
     )).with_children(|parent| {
         parent.spawn((
-            Text::new("GAME OVER"),
+            Text::new(title),
             TextFont { font_size: 60.0, ..default() },
             TextColor(Color::WHITE),
         ));
@@ -2,6 +2,7 @@ use bevy::prelude::*;
 use bevy_matchbox::prelude::MatchboxSocket;
 use bevy_ggrs::ggrs::PlayerType;
 use crate::core::AppState;
+use crate::game_mode::ActiveGameMode;
 use crate::jjrs::GggrsSessionConfiguration;
 
 pub struct LobbyUiPlugin;
@@ -28,7 +29,7 @@ fn despawn_lobby_ui(mut commands: Commands, q_root: Query<Entity, With<LobbyUiRo
     }
 }
 
-fn spawn_lobby_ui(mut commands: Commands) {
+fn spawn_lobby_ui(mut commands: Commands, game_mode: Res<ActiveGameMode>) {
     // Root node
     commands.spawn((
         Node {
@@ -53,6 +54,15 @@ fn spawn_lobby_ui(mut commands: Commands) {
             TextColor(Color::WHITE),
         ));
 
+        parent.spawn((
+            Text::new(format!("Mode: {}", game_mode.display_name)),
+            TextFont {
+                font_size: 20.0,
+                ..default()
+            },
+            TextColor(Color::srgb(0.7, 0.7, 0.7)),
+        ));
+
         // Player List Container
         parent.spawn((
             Node {
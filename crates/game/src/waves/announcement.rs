@@ -0,0 +1,242 @@
+//! Special wave announcements, boss health bar and fog.
+//!
+//! Everything here is visual only, derived every frame from the rollback
+//! `WaveState`, `PowerState`, the `WaveBoss` entity and `FrameCount`.
+
+use bevy::prelude::*;
+use bevy_fixed::fixed_math;
+use utils::frame::FrameCount;
+
+#[cfg(feature = "lighting")]
+use bevy_light_2d::light::AmbientLight2d;
+
+#[cfg(feature = "lighting")]
+use crate::{camera::GameCamera, power::PowerState};
+use crate::{character::health::Health, global_asset::GlobalAsset};
+
+use super::{
+    config::{SpecialWave, SpecialWaveKind, WaveConfig},
+    state::{WavePhase, WaveState},
+    tracking::WaveBoss,
+};
+
+/// Frames the announcement stays visible once the wave started spawning
+const ANNOUNCEMENT_SPAWNING_FRAMES: u32 = 120;
+/// Frames the reward stays visible once the special wave is cleared
+const REWARD_FRAMES: u32 = 180;
+
+/// Component marker for the special wave announcement text
+#[derive(Component)]
+pub struct WaveAnnouncementText;
+
+/// Component marker for the boss health bar container
+#[derive(Component)]
+pub struct BossHealthBar;
+
+/// Component marker for the boss name above the health bar
+#[derive(Component)]
+pub struct BossHealthBarName;
+
+/// Component marker for the filled part of the boss health bar
+#[derive(Component)]
+pub struct BossHealthBarFill;
+
+/// Component marker for the fog overlay used when lighting is disabled
+#[derive(Component)]
+pub struct FogOverlay;
+
+/// Get the special wave being played, if any
+fn current_special_wave<'a>(
+    wave_state: &WaveState,
+    global_assets: &GlobalAsset,
+    config_assets: &'a Assets<WaveConfig>,
+) -> Option<&'a SpecialWave> {
+    let config = global_assets
+        .wave_config
+        .as_ref()
+        .and_then(|h| config_assets.get(h))?;
+    wave_state
+        .special_wave
+        .and_then(|i| config.special_waves.get(i))
+}
+
+pub fn setup_wave_announcement_ui(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let font = asset_server.load("fonts/FiraMono-Medium.ttf");
+
+    commands.spawn((
+        FogOverlay,
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            ..default()
+        },
+        BackgroundColor(Color::NONE),
+    ));
+
+    commands.spawn((
+        WaveAnnouncementText,
+        Text::new(""),
+        TextFont {
+            font: font.clone(),
+            font_size: 32.0,
+            ..Default::default()
+        },
+        TextColor(Color::WHITE),
+        TextLayout::new_with_justify(Justify::Center),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Percent(30.0),
+            width: Val::Percent(100.0),
+            ..default()
+        },
+    ));
+
+    commands
+        .spawn((
+            BossHealthBar,
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(70.0),
+                left: Val::Percent(30.0),
+                width: Val::Percent(40.0),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            Visibility::Hidden,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                BossHealthBarName,
+                Text::new(""),
+                TextFont {
+                    font,
+                    font_size: 14.0,
+                    ..Default::default()
+                },
+                TextColor(Color::srgb(0.9, 0.1, 0.1)),
+            ));
+            parent
+                .spawn((
+                    Node {
+                        width: Val::Percent(100.0),
+                        height: Val::Px(10.0),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgba(0.1, 0.1, 0.1, 0.8)),
+                ))
+                .with_children(|bar| {
+                    bar.spawn((
+                        BossHealthBarFill,
+                        Node {
+                            width: Val::Percent(100.0),
+                            height: Val::Percent(100.0),
+                            ..default()
+                        },
+                        BackgroundColor(Color::srgb(0.8, 0.1, 0.1)),
+                    ));
+                });
+        });
+}
+
+/// Announce special waves when they start and their reward when they are cleared
+pub fn update_wave_announcement(
+    frame: Res<FrameCount>,
+    wave_state: Res<WaveState>,
+    global_assets: Res<GlobalAsset>,
+    config_assets: Res<Assets<WaveConfig>>,
+    mut text_query: Query<(&mut Text, &mut TextColor), With<WaveAnnouncementText>>,
+) {
+    let Ok((mut text, mut color)) = text_query.single_mut() else {
+        return;
+    };
+
+    let Some(special) = current_special_wave(&wave_state, &global_assets, &config_assets) else {
+        text.0.clear();
+        return;
+    };
+
+    let since_phase_start = frame.frame.saturating_sub(wave_state.phase_start_frame);
+    let since_wave_start = frame.frame.saturating_sub(wave_state.wave_start_frame);
+
+    text.0 = match wave_state.phase {
+        WavePhase::GracePeriod => format!("WAVE {}\n{}", wave_state.current_wave, special.name),
+        WavePhase::Spawning | WavePhase::InProgress if since_wave_start < ANNOUNCEMENT_SPAWNING_FRAMES => {
+            format!("WAVE {}\n{}", wave_state.current_wave, special.name)
+        }
+        WavePhase::WaveComplete if since_phase_start < REWARD_FRAMES && special.reward_points > 0 => {
+            format!("{} CLEARED\n+{} points", special.name, special.reward_points)
+        }
+        _ => String::new(),
+    };
+    color.0 = special.kind.color();
+}
+
+/// Show the health of the boss of a boss wave
+pub fn update_boss_health_bar(
+    boss_query: Query<(&WaveBoss, &Health)>,
+    mut bar_query: Query<&mut Visibility, With<BossHealthBar>>,
+    mut name_query: Query<&mut Text, With<BossHealthBarName>>,
+    mut fill_query: Query<&mut Node, With<BossHealthBarFill>>,
+) {
+    let Ok(mut visibility) = bar_query.single_mut() else {
+        return;
+    };
+
+    let Some((boss, health)) = boss_query.iter().next() else {
+        visibility.set_if_neq(Visibility::Hidden);
+        return;
+    };
+    visibility.set_if_neq(Visibility::Inherited);
+
+    if let Ok(mut name) = name_query.single_mut() {
+        if name.0 != boss.name {
+            name.0 = boss.name.clone();
+        }
+    }
+
+    if let Ok(mut fill) = fill_query.single_mut() {
+        let ratio = if health.max > fixed_math::FIXED_ZERO {
+            (health.current / health.max).to_num::<f32>().clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        fill.width = Val::Percent(ratio * 100.0);
+    }
+}
+
+/// Darken the lighting during fog waves, on top of the darkness of the map
+/// while the power is off
+pub fn update_fog(
+    wave_state: Res<WaveState>,
+    #[cfg(feature = "lighting")] power: Res<PowerState>,
+    global_assets: Res<GlobalAsset>,
+    config_assets: Res<Assets<WaveConfig>>,
+    #[cfg(feature = "lighting")] mut light_query: Query<&mut AmbientLight2d, With<GameCamera>>,
+    #[cfg(not(feature = "lighting"))] mut overlay_query: Query<&mut BackgroundColor, With<FogOverlay>>,
+) {
+    let fog_active = matches!(
+        wave_state.phase,
+        WavePhase::GracePeriod | WavePhase::Spawning | WavePhase::InProgress
+    );
+    let darkness = match current_special_wave(&wave_state, &global_assets, &config_assets) {
+        Some(SpecialWave { kind: SpecialWaveKind::Fog { darkness }, .. }) if fog_active => {
+            darkness.clamp(0.0, 1.0)
+        }
+        _ => 0.0,
+    };
+
+    #[cfg(feature = "lighting")]
+    for mut light in light_query.iter_mut() {
+        let brightness = power.ambient_brightness() * (1.0 - darkness);
+        if light.brightness != brightness {
+            light.brightness = brightness;
+        }
+    }
+
+    #[cfg(not(feature = "lighting"))]
+    for mut background in overlay_query.iter_mut() {
+        background.set_if_neq(BackgroundColor(Color::srgba(0.0, 0.0, 0.0, darkness * 0.8)));
+    }
+}
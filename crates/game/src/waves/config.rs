@@ -16,6 +16,79 @@ pub struct WaveTier {
     pub enemy_probabilities: HashMap<String, u32>,
 }
 
+/// Variation of a special wave
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum SpecialWaveKind {
+    /// Every enemy of the wave is of a single (fast) type
+    Fast { enemy_type: String },
+    /// A single boss enemy with a boss health bar
+    Boss {
+        enemy_type: String,
+        /// Multiplier applied to the base health of the enemy type
+        health_multiplier: fixed_math::Fixed,
+    },
+    /// Enemies spawn around the players instead of at the spawners
+    Hellhound {
+        enemy_type: String,
+        /// Distance from the targeted player
+        spawn_distance: fixed_math::Fixed,
+    },
+    /// Lighting goes dark for the whole wave (visual only)
+    Fog {
+        /// 0.0 = normal lighting, 1.0 = pitch black
+        darkness: f32,
+    },
+}
+
+impl SpecialWaveKind {
+    /// Enemy type replacing the tier probabilities, if any
+    pub fn enemy_type(&self) -> Option<&str> {
+        match self {
+            SpecialWaveKind::Fast { enemy_type }
+            | SpecialWaveKind::Boss { enemy_type, .. }
+            | SpecialWaveKind::Hellhound { enemy_type, .. } => Some(enemy_type),
+            SpecialWaveKind::Fog { .. } => None,
+        }
+    }
+
+    /// Color of the announcement (visual only)
+    pub fn color(&self) -> Color {
+        match self {
+            SpecialWaveKind::Fast { .. } => Color::srgb(1.0, 0.85, 0.2),
+            SpecialWaveKind::Boss { .. } => Color::srgb(0.9, 0.1, 0.1),
+            SpecialWaveKind::Hellhound { .. } => Color::srgb(1.0, 0.5, 0.0),
+            SpecialWaveKind::Fog { .. } => Color::srgb(0.6, 0.6, 0.8),
+        }
+    }
+}
+
+/// Special wave replacing a regular wave on a schedule
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpecialWave {
+    /// Name shown in the announcement
+    pub name: String,
+    pub kind: SpecialWaveKind,
+    /// Explicit wave numbers, checked before `every`
+    #[serde(default)]
+    pub waves: Vec<u32>,
+    /// Every N waves (0 = only the explicit waves)
+    #[serde(default)]
+    pub every: u32,
+    /// Points given to every player when the wave is cleared
+    #[serde(default)]
+    pub reward_points: u32,
+}
+
+impl SpecialWave {
+    /// Enemy count replacing the formula, if any
+    pub fn enemy_count(&self) -> Option<u32> {
+        match self.kind {
+            SpecialWaveKind::Boss { .. } => Some(1),
+            _ => None,
+        }
+    }
+}
+
 /// Main wave configuration loaded from RON file
 #[derive(Asset, TypePath, Debug, Clone, Serialize, Deserialize)]
 pub struct WaveConfig {
@@ -63,6 +136,12 @@ pub struct WaveConfig {
     pub health_multiplier_per_wave: fixed_math::Fixed,
     /// Damage multiplier increase per wave (e.g., 0.03 = +3% per wave)
     pub damage_multiplier_per_wave: fixed_math::Fixed,
+
+    // === Special Waves ===
+
+    /// Scheduled special waves, the first match wins
+    #[serde(default)]
+    pub special_waves: Vec<SpecialWave>,
 }
 
 impl Default for WaveConfig {
@@ -119,6 +198,8 @@ impl Default for WaveConfig {
             // Scaling
             health_multiplier_per_wave: fixed_math::new(0.05),
             damage_multiplier_per_wave: fixed_math::new(0.03),
+
+            special_waves: Vec::new(),
         }
     }
 }
@@ -129,6 +210,20 @@ impl WaveConfig {
         self.wave_tiers.iter().find(|t| wave <= t.max_wave)
     }
 
+    /// Get the index of the special wave scheduled for a wave number.
+    ///
+    /// Explicit wave numbers win over the `every` schedules.
+    pub fn special_wave_index(&self, wave: u32) -> Option<usize> {
+        self.special_waves
+            .iter()
+            .position(|s| s.waves.contains(&wave))
+            .or_else(|| {
+                self.special_waves
+                    .iter()
+                    .position(|s| s.every > 0 && wave % s.every == 0)
+            })
+    }
+
     /// Calculate the total enemy count for a wave
     pub fn calculate_enemy_count(&self, wave: u32, variance: u32) -> u32 {
         let base = self.base_enemies;
@@ -62,7 +62,7 @@ fn update_wave_debug_text(
         // Compact single-line format matching frame counter style
         // Both Spawned and Kills use wave_enemy_count as denominator for consistency
         text.0 = format!(
-            "Wave {:>2} | {} | Spawned: {:>2}/{:<2} | Kills: {:>2}/{:<2} | Total: {:>4}",
+            "Wave {:>2} | {} | Spawned: {:>2}/{:<2} | Kills: {:>2}/{:<2} | Total: {:>4}{}",
             wave_state.current_wave,
             phase_str,
             wave_state.enemies_spawned_this_wave,
@@ -70,6 +70,10 @@ fn update_wave_debug_text(
             wave_state.wave_enemies_killed,
             wave_state.wave_enemy_count,
             wave_state.total_enemies_killed,
+            wave_state
+                .special_wave
+                .map(|i| format!(" | Special #{}", i))
+                .unwrap_or_default(),
         );
     }
 }
@@ -18,17 +18,16 @@
 //!
 //! # Usage
 //!
-//! Add the `WaveSystemPlugin` to your app and enable wave mode:
-//!
-//! ```rust,ignore
-//! app.add_plugins(WaveSystemPlugin);
-//! app.insert_resource(WaveModeEnabled(true));
-//! ```
+//! Add the `WaveSystemPlugin` to your app and select a game mode spawning
+//! waves (see `game_mode`). Without breaks (endless horde) the grace period
+//! and the delay between waves are skipped and a wave is complete as soon as
+//! all its enemies are spawned.
 //!
 //! # Configuration
 //!
 //! Configure via `assets/waves/wave_config.ron`. See `config::WaveConfig` for options.
 
+pub mod announcement;
 pub mod config;
 pub mod debug;
 pub mod state;
@@ -41,17 +40,18 @@ use bevy_ggrs::{GgrsSchedule, RollbackApp};
 
 use crate::character::enemy::spawning::enemy_spawn_from_spawners_system;
 use crate::character::health::{rollback_apply_accumulated_damage, rollback_apply_death};
+use crate::core::AppState;
 use crate::system_set::RollbackSystemSet;
 
-pub use config::WaveConfig;
+pub use config::{SpecialWave, SpecialWaveKind, WaveConfig};
 pub use debug::{WaveDebugEnabled, WaveDebugPlugin};
 pub use state::{WavePhase, WaveState};
-pub use tracking::WaveEnemy;
+pub use tracking::{WaveBoss, WaveEnemy};
 
 /// Resource to enable/disable wave spawning mode.
 ///
 /// When enabled, the wave system handles all enemy spawning.
-/// When disabled, the original spawner system is used.
+/// Set from the rules of the `ActiveGameMode` when the game mode is selected.
 #[derive(Resource, Debug, Clone, Copy, Default)]
 pub struct WaveModeEnabled(pub bool);
 
@@ -77,6 +77,7 @@ impl Plugin for WaveSystemPlugin {
         // Rollback registration
         app.rollback_resource_with_clone::<WaveState>();
         app.rollback_component_with_clone::<WaveEnemy>();
+        app.rollback_component_with_clone::<WaveBoss>();
 
         // Wave state and spawning systems run in EnemySpawning set
         // Use ambiguous_with to mark intentional ambiguity with old spawner
@@ -105,6 +106,18 @@ impl Plugin for WaveSystemPlugin {
                 .before(rollback_apply_death)
                 .in_set(RollbackSystemSet::DeathManagement),
         );
+
+        // Special wave visuals (outside GGRS schedule), derived from the rollback state
+        app.add_systems(OnEnter(AppState::InGame), announcement::setup_wave_announcement_ui);
+        app.add_systems(
+            Update,
+            (
+                announcement::update_wave_announcement,
+                announcement::update_boss_health_bar,
+                announcement::update_fog,
+            )
+                .run_if(in_state(AppState::InGame)),
+        );
     }
 }
 
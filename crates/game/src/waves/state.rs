@@ -68,6 +68,8 @@ pub struct WaveState {
     pub current_health_multiplier: fixed_math::Fixed,
     /// Damage multiplier for current wave (1.0 = 100%)
     pub current_damage_multiplier: fixed_math::Fixed,
+    /// Index in `WaveConfig::special_waves` of the current wave, if special
+    pub special_wave: Option<usize>,
 }
 
 impl Default for WaveState {
@@ -86,6 +88,7 @@ impl Default for WaveState {
             phase_start_frame: 0,
             current_health_multiplier: fixed_math::FIXED_ONE,
             current_damage_multiplier: fixed_math::FIXED_ONE,
+            special_wave: None,
         }
     }
 }
@@ -118,12 +121,19 @@ impl WaveState {
     }
 
     /// Prepare for next wave
-    pub fn prepare_next_wave(&mut self, enemy_count: u32, health_mult: fixed_math::Fixed, damage_mult: fixed_math::Fixed) {
+    pub fn prepare_next_wave(
+        &mut self,
+        enemy_count: u32,
+        health_mult: fixed_math::Fixed,
+        damage_mult: fixed_math::Fixed,
+        special_wave: Option<usize>,
+    ) {
         self.wave_enemy_count = enemy_count;
         self.enemies_to_spawn = enemy_count;
         self.enemies_spawned_this_wave = 0;
         self.wave_enemies_killed = 0;
         self.current_health_multiplier = health_mult;
         self.current_damage_multiplier = damage_mult;
+        self.special_wave = special_wave;
     }
 }
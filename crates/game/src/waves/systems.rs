@@ -3,53 +3,49 @@
 //! GGRS CRITICAL: All systems must be deterministic.
 //! See CLAUDE.md for GGRS rules.
 
-use animation::SpriteSheetConfig;
-use bevy::{ecs::system::SystemParam, prelude::*};
+use bevy::prelude::*;
 use bevy_fixed::{fixed_math, rng::RollbackRng};
-use bevy_ggrs::AddRollbackCommandExtension;
-use map::game::entity::map::enemy_spawn::EnemySpawnerComponent;
-use utils::{frame::FrameCount, net_id::{GgrsNetId, GgrsNetIdFactory}};
+use bevy_ggrs::{AddRollbackCommandExtension, Rollback};
+use map::game::entity::map::{enemy_spawn::EnemySpawnerComponent, level_id::LevelId};
+use utils::{frame::FrameCount, net_id::{GgrsNetId, GgrsNetIdFactory}, order_iter, order_mut_iter};
 
 use crate::{
     character::{
-        config::CharacterConfig,
-        enemy::{create::spawn_enemy, Enemy},
-        health::Death,
+        enemy::{
+            ai::navigation::{FlowFieldCache, GridPos, NavProfile},
+            create::{spawn_enemy, SpawnAssets},
+            Enemy,
+        },
+        health::{Death, Health},
         player::Player,
     },
-    collider::CollisionSettings,
+    economy::Points,
+    game_mode::ActiveGameMode,
     global_asset::GlobalAsset,
-    weapons::{melee::MeleeWeaponsConfig, WeaponsConfig},
+    room::SpawnerRooms,
 };
 
-/// Bundled parameters for enemy spawning (reduces parameter count)
-#[derive(SystemParam)]
-pub struct SpawnAssets<'w> {
-    pub collision_settings: Res<'w, CollisionSettings>,
-    pub weapons_asset: Res<'w, Assets<WeaponsConfig>>,
-    pub melee_weapons_asset: Res<'w, Assets<MeleeWeaponsConfig>>,
-    pub characters_asset: Res<'w, Assets<CharacterConfig>>,
-    pub asset_server: Res<'w, AssetServer>,
-    pub texture_atlas_layouts: ResMut<'w, Assets<TextureAtlasLayout>>,
-    pub spritesheet_assets: Res<'w, Assets<SpriteSheetConfig>>,
-}
-
 use super::{
-    config::WaveConfig,
+    config::{SpecialWave, SpecialWaveKind, WaveConfig},
     state::{WavePhase, WaveState},
-    tracking::WaveEnemy,
+    tracking::{WaveBoss, WaveEnemy},
 };
 
 /// System that manages wave state transitions.
 ///
 /// Runs every frame to check conditions and advance the state machine.
+/// Game modes without wave breaks skip the grace period and the delay between
+/// waves, and don't wait for the wave enemies to be killed (except for special
+/// waves, which must be cleared to get their reward).
 pub fn wave_state_machine_system(
     frame: Res<FrameCount>,
     mut wave_state: ResMut<WaveState>,
     mut rng: ResMut<RollbackRng>,
+    game_mode: Res<ActiveGameMode>,
     wave_config_assets: Res<Assets<WaveConfig>>,
     global_assets: Res<GlobalAsset>,
     wave_enemy_query: Query<Entity, (With<Enemy>, With<WaveEnemy>)>,
+    mut player_query: Query<(&GgrsNetId, &mut Points), (With<Player>, With<Rollback>)>,
 ) {
     // Get config (handle may not be loaded yet)
     let Some(config) = global_assets
@@ -63,6 +59,10 @@ pub fn wave_state_machine_system(
     let current_frame = frame.frame;
     let alive_wave_enemies = wave_enemy_query.iter().count() as u32;
 
+    let wave_breaks = game_mode.rules.wave_breaks;
+    let grace_period_frames = if wave_breaks { config.grace_period_frames } else { 0 };
+    let min_wave_delay_frames = if wave_breaks { config.min_wave_delay_frames } else { 0 };
+
     match wave_state.phase {
         WavePhase::NotStarted => {
             // Start first wave - transition to grace period
@@ -71,25 +71,17 @@ pub fn wave_state_machine_system(
             wave_state.current_wave = 1;
 
             // Calculate wave 1 enemies
-            let variance = if config.max_random_variance > 0 {
-                rng.next_u32_range(0, config.max_random_variance + 1)
-            } else {
-                0
-            };
-            let enemy_count = config.calculate_enemy_count(1, variance);
-            let health_mult = config.calculate_health_multiplier(1);
-            let damage_mult = config.calculate_damage_multiplier(1);
-            wave_state.prepare_next_wave(enemy_count, health_mult, damage_mult);
+            prepare_wave(&mut wave_state, config, &mut rng);
 
             info!(
-                "ggrs{{f={} wave_system phase=GracePeriod wave={} enemies={}}}",
-                current_frame, wave_state.current_wave, enemy_count
+                "ggrs{{f={} wave_system phase=GracePeriod wave={} enemies={} special={:?}}}",
+                current_frame, wave_state.current_wave, wave_state.wave_enemy_count, wave_state.special_wave
             );
         }
 
         WavePhase::GracePeriod => {
             let elapsed = current_frame.saturating_sub(wave_state.phase_start_frame);
-            if elapsed >= config.grace_period_frames {
+            if elapsed >= grace_period_frames {
                 // Grace period over, start spawning
                 wave_state.phase = WavePhase::Spawning;
                 wave_state.phase_start_frame = current_frame;
@@ -117,11 +109,23 @@ pub fn wave_state_machine_system(
         }
 
         WavePhase::InProgress => {
-            // Check if all wave enemies are dead
-            if alive_wave_enemies == 0 && wave_state.enemies_spawned_this_wave > 0 {
+            // Check if all wave enemies are dead (or just spawned without breaks)
+            let wait_for_kills = wave_breaks || wave_state.special_wave.is_some();
+            if (alive_wave_enemies == 0 || !wait_for_kills) && wave_state.enemies_spawned_this_wave > 0 {
                 wave_state.phase = WavePhase::WaveComplete;
                 wave_state.phase_start_frame = current_frame;
 
+                // Special wave reward for every player
+                if let Some(special) = wave_state.special_wave.and_then(|i| config.special_waves.get(i)) {
+                    for (net_id, mut points) in order_mut_iter!(player_query) {
+                        points.earn(special.reward_points);
+                        info!(
+                            "ggrs{{f={} wave_system special_reward {} earned={} total={}}}",
+                            current_frame, net_id, special.reward_points, points.current
+                        );
+                    }
+                }
+
                 // If we haven't recorded any kills frame, use current
                 if wave_state.last_enemy_killed_frame == 0 {
                     wave_state.last_enemy_killed_frame = current_frame;
@@ -136,32 +140,46 @@ pub fn wave_state_machine_system(
 
         WavePhase::WaveComplete => {
             let elapsed = current_frame.saturating_sub(wave_state.last_enemy_killed_frame);
-            if elapsed >= config.min_wave_delay_frames {
+            if elapsed >= min_wave_delay_frames {
                 // Advance to next wave
                 wave_state.current_wave += 1;
                 wave_state.phase = WavePhase::GracePeriod;
                 wave_state.phase_start_frame = current_frame;
 
                 // Calculate next wave enemies
-                let variance = if config.max_random_variance > 0 {
-                    rng.next_u32_range(0, config.max_random_variance + 1)
-                } else {
-                    0
-                };
-                let enemy_count = config.calculate_enemy_count(wave_state.current_wave, variance);
-                let health_mult = config.calculate_health_multiplier(wave_state.current_wave);
-                let damage_mult = config.calculate_damage_multiplier(wave_state.current_wave);
-                wave_state.prepare_next_wave(enemy_count, health_mult, damage_mult);
+                prepare_wave(&mut wave_state, config, &mut rng);
 
                 info!(
-                    "ggrs{{f={} wave_system phase=GracePeriod wave={} enemies={}}}",
-                    current_frame, wave_state.current_wave, enemy_count
+                    "ggrs{{f={} wave_system phase=GracePeriod wave={} enemies={} special={:?}}}",
+                    current_frame, wave_state.current_wave, wave_state.wave_enemy_count, wave_state.special_wave
                 );
             }
         }
     }
 }
 
+/// Compute the enemies and modifiers of `wave_state.current_wave`.
+///
+/// Special waves can replace the enemy count from the formula.
+fn prepare_wave(wave_state: &mut WaveState, config: &WaveConfig, rng: &mut RollbackRng) {
+    let wave = wave_state.current_wave;
+
+    // GGRS CRITICAL: always consume the variance roll, even if a special wave overrides the count
+    let variance = if config.max_random_variance > 0 {
+        rng.next_u32_range(0, config.max_random_variance + 1)
+    } else {
+        0
+    };
+
+    let special_wave = config.special_wave_index(wave);
+    let enemy_count = special_wave
+        .and_then(|i| config.special_waves[i].enemy_count())
+        .unwrap_or_else(|| config.calculate_enemy_count(wave, variance));
+    let health_mult = config.calculate_health_multiplier(wave);
+    let damage_mult = config.calculate_damage_multiplier(wave);
+    wave_state.prepare_next_wave(enemy_count, health_mult, damage_mult, special_wave);
+}
+
 /// System that spawns enemies during the Spawning phase.
 ///
 /// Uses existing LDTK spawner positions for spawn locations.
@@ -175,11 +193,15 @@ pub fn wave_spawning_system(
 
     // Spawner query (from LDTK map)
     // GGRS CRITICAL: GgrsNetId must be first for deterministic sorting
-    spawner_query: Query<(&GgrsNetId, Entity, &EnemySpawnerComponent, &fixed_math::FixedTransform3D)>,
-    // Player positions for spawner selection
-    player_query: Query<&fixed_math::FixedTransform3D, With<Player>>,
+    spawner_query: Query<(&GgrsNetId, Entity, &EnemySpawnerComponent, &fixed_math::FixedTransform3D, Option<&LevelId>)>,
+    // Only spawners in the rooms unlocked by the players are used
+    spawner_rooms: SpawnerRooms,
+    // Player positions for spawner selection and hellhound spawns
+    player_query: Query<(&GgrsNetId, &fixed_math::FixedTransform3D), With<Player>>,
     // Current enemy count
     enemy_query: Query<&fixed_math::FixedTransform3D, With<Enemy>>,
+    // Blocked cells and level bounds, hellhounds must not spawn in a wall
+    flow_field_cache: Res<FlowFieldCache>,
 
     // Bundled asset dependencies for spawn_enemy
     mut spawn_assets: SpawnAssets,
@@ -220,19 +242,32 @@ pub fn wave_spawning_system(
     }
 
     // Get player positions
-    let player_positions: Vec<fixed_math::FixedVec2> = player_query
+    // GGRS CRITICAL: sorted by net id, hellhounds pick a player with the rng
+    let player_translations: Vec<fixed_math::FixedVec3> = order_iter!(player_query)
+        .into_iter()
+        .map(|(_, t)| t.translation)
+        .collect();
+    let player_positions: Vec<fixed_math::FixedVec2> = player_translations
         .iter()
-        .map(|t| t.translation.truncate())
+        .map(|t| t.truncate())
         .collect();
 
     if player_positions.is_empty() {
         return;
     }
 
-    // Select valid spawners based on distance
-    let valid_spawners = select_valid_spawners(&spawner_query, &player_positions, config);
+    let special = wave_state
+        .special_wave
+        .and_then(|i| config.special_waves.get(i));
+    let hellhound_distance = match special.map(|s| &s.kind) {
+        Some(SpecialWaveKind::Hellhound { spawn_distance, .. }) => Some(*spawn_distance),
+        _ => None,
+    };
+
+    // Select valid spawners based on unlocked rooms and distance
+    let valid_spawners = select_valid_spawners(&spawner_query, &spawner_rooms, &player_positions, config);
 
-    if valid_spawners.is_empty() {
+    if valid_spawners.is_empty() && hellhound_distance.is_none() {
         // No valid spawners - try again next frame
         return;
     }
@@ -249,37 +284,57 @@ pub fn wave_spawning_system(
             break;
         }
 
-        // Select spawner (random from valid spawners)
-        let spawner_idx = if valid_spawners.len() == 1 {
-            0
+        // Hellhounds spawn around a random player instead of at the spawners,
+        // or at a spawner when there is no free spot around the player
+        let ring_pos = hellhound_distance.and_then(|spawn_distance| {
+            let player_idx = if player_translations.len() == 1 {
+                0
+            } else {
+                rng.next_u32_range(0, player_translations.len() as u32) as usize
+            };
+            find_ring_position(
+                player_translations[player_idx],
+                spawn_distance,
+                &flow_field_cache,
+                &mut rng,
+            )
+        });
+
+        let spawn_pos = if let Some(ring_pos) = ring_pos {
+            ring_pos
+        } else if valid_spawners.is_empty() {
+            // Nowhere to spawn - try again next frame
+            break;
         } else {
-            rng.next_u32_range(0, valid_spawners.len() as u32) as usize
+            // Select spawner (random from valid spawners)
+            let spawner_idx = if valid_spawners.len() == 1 {
+                0
+            } else {
+                rng.next_u32_range(0, valid_spawners.len() as u32) as usize
+            };
+            let (_, _, spawner_config, spawner_transform) = &valid_spawners[spawner_idx];
+
+            // Calculate spawn position with offset
+            calculate_spawn_position(
+                spawner_transform.translation,
+                spawner_config.spawn_radius,
+                &mut rng,
+            )
         };
-        let (_, _, spawner_config, spawner_transform) = &valid_spawners[spawner_idx];
-
-        // Calculate spawn position with offset
-        let spawn_pos = calculate_spawn_position(
-            spawner_transform.translation,
-            spawner_config.spawn_radius,
-            &mut rng,
-        );
 
-        // Select enemy type based on current wave tier
-        let enemy_type = select_enemy_type(&wave_state, config, &mut rng);
+        // Special waves force the enemy type, otherwise select it based on current wave tier
+        let enemy_type = match special.and_then(|s| s.kind.enemy_type()) {
+            Some(enemy_type) => enemy_type.to_string(),
+            None => select_enemy_type(&wave_state, config, &mut rng),
+        };
 
         // Spawn the enemy and get the entity
         let enemy_entity = spawn_enemy(
             enemy_type.clone(),
             spawn_pos,
             &mut commands,
-            &spawn_assets.weapons_asset,
-            &spawn_assets.melee_weapons_asset,
-            &spawn_assets.characters_asset,
-            &spawn_assets.asset_server,
-            &mut spawn_assets.texture_atlas_layouts,
-            &spawn_assets.spritesheet_assets,
+            &mut spawn_assets,
             &global_assets,
-            &spawn_assets.collision_settings,
             &mut id_factory,
         );
 
@@ -288,6 +343,27 @@ pub fn wave_spawning_system(
             spawned_wave: wave_state.current_wave,
         });
 
+        // Boss: scale the base health of its type and mark it for the boss health bar
+        if let Some(SpecialWave {
+            name,
+            kind: SpecialWaveKind::Boss { health_multiplier, .. },
+            ..
+        }) = special
+        {
+            if let Some(character_config) = global_assets
+                .character_configs
+                .get(&enemy_type)
+                .and_then(|h| spawn_assets.characters_asset.get(h))
+            {
+                let max = character_config.base_health.max.saturating_mul(*health_multiplier);
+                commands.entity(enemy_entity).insert(Health {
+                    current: max,
+                    max,
+                });
+            }
+            commands.entity(enemy_entity).insert(WaveBoss { name: name.clone() });
+        }
+
         wave_state.enemies_to_spawn -= 1;
         wave_state.enemies_spawned_this_wave += 1;
     }
@@ -302,20 +378,28 @@ pub fn wave_spawning_system(
     );
 }
 
-/// Select spawners that are within valid distance range from players.
+/// Select spawners in unlocked rooms that are within valid distance range from players.
 fn select_valid_spawners<'a>(
-    spawner_query: &'a Query<(&GgrsNetId, Entity, &EnemySpawnerComponent, &fixed_math::FixedTransform3D)>,
+    spawner_query: &'a Query<(&GgrsNetId, Entity, &EnemySpawnerComponent, &fixed_math::FixedTransform3D, Option<&LevelId>)>,
+    spawner_rooms: &SpawnerRooms,
     player_positions: &[fixed_math::FixedVec2],
     config: &WaveConfig,
 ) -> Vec<(&'a GgrsNetId, Entity, &'a EnemySpawnerComponent, &'a fixed_math::FixedTransform3D)> {
     let mut spawners: Vec<_> = spawner_query.iter().collect();
-    spawners.sort_unstable_by_key(|(net_id, _, _, _)| net_id.0);
+    spawners.sort_unstable_by_key(|(net_id, _, _, _, _)| net_id.0);
 
     let mut valid = Vec::new();
 
-    for (net_id, entity, spawner_config, transform) in spawners {
+    for (net_id, entity, spawner_config, transform, level_id) in spawners {
         let spawner_pos = transform.translation.truncate();
 
+        // Spawners outside of the map rooms are always usable
+        if let Some(level_id) = level_id {
+            if spawner_rooms.active_room(level_id, spawner_pos).is_none() {
+                continue;
+            }
+        }
+
         // Find minimum distance to any player
         let min_distance = player_positions
             .iter()
@@ -358,6 +442,48 @@ fn calculate_spawn_position(
     )
 }
 
+/// Random directions tried around a player before falling back to the spawners
+const RING_SPAWN_ATTEMPTS: u32 = 8;
+
+/// Find a free position at a fixed distance from a center, in a random direction.
+///
+/// Positions in a blocked cell, outside the levels or not reached by the flow
+/// field (walled off from the players) are retried with another direction.
+/// Returns `None` when every attempt failed.
+fn find_ring_position(
+    center: fixed_math::FixedVec3,
+    distance: fixed_math::Fixed,
+    flow_field_cache: &FlowFieldCache,
+    rng: &mut RollbackRng,
+) -> Option<fixed_math::FixedVec3> {
+    let flow_field = flow_field_cache.get_flow_field(NavProfile::GroundBreaker);
+    (0..RING_SPAWN_ATTEMPTS)
+        .map(|_| calculate_ring_position(center, distance, rng))
+        .find(|pos| {
+            let cell = GridPos::from_fixed(pos.truncate());
+            flow_field_cache
+                .level_bounds
+                .is_none_or(|bounds| bounds.contains(cell))
+                && !flow_field_cache.is_blocked(&cell, NavProfile::Ground)
+                && flow_field.is_none_or(|flow_field| flow_field.is_reached(cell))
+        })
+}
+
+/// Calculate a position at a fixed distance from a center, in a random direction.
+fn calculate_ring_position(
+    center: fixed_math::FixedVec3,
+    distance: fixed_math::Fixed,
+    rng: &mut RollbackRng,
+) -> fixed_math::FixedVec3 {
+    let angle = rng.next_fixed() * fixed_math::FIXED_TAU;
+
+    fixed_math::FixedVec3::new(
+        center.x.saturating_add(fixed_math::cos_fixed(angle) * distance),
+        center.y.saturating_add(fixed_math::sin_fixed(angle) * distance),
+        center.z,
+    )
+}
+
 /// Select enemy type based on wave tier probabilities.
 ///
 /// GGRS CRITICAL: Sorts probability keys for deterministic weighted selection.
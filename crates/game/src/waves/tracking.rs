@@ -16,3 +16,10 @@ pub struct WaveEnemy {
     /// Wave number when this enemy was spawned
     pub spawned_wave: u32,
 }
+
+/// Marker component for the boss of a boss wave, shown with a boss health bar.
+#[derive(Component, Debug, Clone, Serialize, Deserialize, Default)]
+pub struct WaveBoss {
+    /// Name of the special wave, shown above the health bar
+    pub name: String,
+}
@@ -0,0 +1,239 @@
+use bevy::{log::{tracing::span, Level}, prelude::*};
+use bevy_fixed::fixed_math;
+use bevy_ggrs::{AddRollbackCommandExtension, Rollback};
+use ggrs::PlayerHandle;
+use serde::{Deserialize, Serialize};
+use utils::{
+    frame::FrameCount,
+    net_id::{GgrsNetId, GgrsNetIdFactory},
+    order_iter, order_mut_iter,
+};
+
+use crate::character::{
+    enemy::Enemy,
+    health::{DamageAccumulator, HitBy},
+    status_effect::{StatusEffect, StatusEffectKind, StatusEffects, STATUS_TICK_FRAMES},
+};
+
+use super::{BulletType, EffectType};
+
+// FIRE ZONE
+// Left on the ground by incendiary bullets, burns the enemies standing in it
+#[derive(Component, Clone, Debug, Serialize, Deserialize)]
+pub struct FireZone {
+    pub radius: fixed_math::Fixed,
+    pub damage_per_second: fixed_math::Fixed,
+    pub expires_at_frame: u32,
+    pub player_handle: PlayerHandle,
+}
+
+// Apply the status effects of an elemental bullet to the hit target,
+// returns the visual effect to show (none for non elemental bullets)
+pub fn apply_elemental_hit(
+    bullet_type: &BulletType,
+    status_effects: &mut StatusEffects,
+    current_frame: u32,
+    source: Vec<HitBy>,
+) -> Option<EffectType> {
+    match *bullet_type {
+        BulletType::Incendiary {
+            burn_damage_per_second,
+            burn_duration_frames,
+            ..
+        } => {
+            status_effects.apply(
+                current_frame,
+                StatusEffect::new(
+                    StatusEffectKind::Burning,
+                    burn_damage_per_second,
+                    current_frame,
+                    burn_duration_frames,
+                )
+                .with_source(source),
+            );
+            Some(EffectType::Incendiary)
+        }
+        BulletType::Cryo {
+            slow_multiplier,
+            slow_duration_frames,
+            freeze_after_hits,
+            freeze_duration_frames,
+            ..
+        } => {
+            status_effects.apply(
+                current_frame,
+                StatusEffect::new(
+                    StatusEffectKind::Slowed,
+                    slow_multiplier,
+                    current_frame,
+                    slow_duration_frames,
+                )
+                .with_source(source),
+            );
+
+            // Each hit during the slow adds a stack, enough of them freeze the target solid
+            let hits = status_effects
+                .get(StatusEffectKind::Slowed, current_frame)
+                .map_or(0, |slowed| slowed.stacks);
+            if freeze_after_hits > 0 && hits >= freeze_after_hits {
+                status_effects.remove(StatusEffectKind::Slowed);
+                status_effects.apply(
+                    current_frame,
+                    StatusEffect::new(
+                        StatusEffectKind::Stunned,
+                        fixed_math::FIXED_ZERO,
+                        current_frame,
+                        freeze_duration_frames,
+                    ),
+                );
+                return Some(EffectType::Freeze);
+            }
+
+            Some(EffectType::Cryo)
+        }
+        _ => None,
+    }
+}
+
+// Spawn the fire zone of an incendiary bullet, if it leaves one. A hit inside a
+// zone of the same shooter refreshes that zone instead, so automatic fire
+// doesn't pile them up
+pub fn spawn_fire_zone(
+    commands: &mut Commands,
+    bullet_type: &BulletType,
+    position: fixed_math::FixedVec2,
+    player_handle: PlayerHandle,
+    current_frame: u32,
+    zone_query: &mut Query<
+        (&GgrsNetId, &fixed_math::FixedTransform3D, &mut FireZone),
+        With<Rollback>,
+    >,
+    id_factory: &mut ResMut<GgrsNetIdFactory>,
+) {
+    let BulletType::Incendiary {
+        burn_damage_per_second,
+        fire_zone_radius,
+        fire_zone_duration_frames,
+        ..
+    } = *bullet_type
+    else {
+        return;
+    };
+
+    if fire_zone_radius <= fixed_math::FIXED_ZERO || fire_zone_duration_frames == 0 {
+        return;
+    }
+
+    let expires_at_frame = current_frame + fire_zone_duration_frames;
+    let existing_zone = order_mut_iter!(zone_query)
+        .into_iter()
+        .find(|(_, transform, zone)| {
+            zone.player_handle == player_handle
+                && current_frame < zone.expires_at_frame
+                && transform.translation.truncate().distance(&position) <= zone.radius
+        });
+    if let Some((g_id, _, mut zone)) = existing_zone {
+        zone.expires_at_frame = zone.expires_at_frame.max(expires_at_frame);
+        zone.damage_per_second = zone.damage_per_second.max(burn_damage_per_second);
+        info!("{} refreshed by {}", g_id, player_handle);
+        return;
+    }
+
+    let transform = fixed_math::FixedTransform3D::new(
+        fixed_math::FixedVec3::new(position.x, position.y, fixed_math::FIXED_ZERO),
+        fixed_math::FixedMat3::IDENTITY,
+        fixed_math::FixedVec3::ONE,
+    );
+
+    let g_id = id_factory.next("fire_zone".to_string());
+    info!("{} spawn at {} by {}", g_id, transform.translation, player_handle);
+
+    let size = fire_zone_radius.to_num::<f32>() * 2.0;
+
+    commands
+        .spawn((
+            Sprite::from_color(Color::srgba(1.0, 0.4, 0.0, 0.35), Vec2::new(size, size)),
+            FireZone {
+                radius: fire_zone_radius,
+                damage_per_second: burn_damage_per_second,
+                expires_at_frame,
+                player_handle,
+            },
+            transform.to_bevy_transform(),
+            transform,
+            g_id,
+        ))
+        .add_rollback();
+}
+
+// SYSTEM: FIRE ZONES
+// Damage the enemies standing in a fire zone every status tick, despawn expired zones
+pub fn fire_zone_rollback_system(
+    frame: Res<FrameCount>,
+    mut commands: Commands,
+    zone_query: Query<(&GgrsNetId, Entity, &fixed_math::FixedTransform3D, &FireZone), With<Rollback>>,
+    mut enemy_query: Query<
+        (
+            &GgrsNetId,
+            Entity,
+            &fixed_math::FixedTransform3D,
+            Option<&mut DamageAccumulator>,
+        ),
+        (With<Enemy>, With<Rollback>, Without<FireZone>),
+    >,
+) {
+    let system_span = span!(Level::INFO, "ggrs", f = frame.frame, s = "fire_zones");
+    let _enter = system_span.enter();
+
+    let mut zones = Vec::new();
+    for (g_id, entity, transform, zone) in order_iter!(zone_query) {
+        if frame.frame >= zone.expires_at_frame {
+            info!("{} fire zone expired", g_id);
+            commands.entity(entity).despawn();
+            continue;
+        }
+        zones.push((g_id.clone(), transform.translation.truncate(), zone.clone()));
+    }
+
+    if zones.is_empty() || frame.frame % STATUS_TICK_FRAMES != 0 {
+        return;
+    }
+
+    let tick_seconds = fixed_math::Fixed::from_num(STATUS_TICK_FRAMES) / fixed_math::new(60.0);
+
+    for (enemy_g_id, enemy_entity, enemy_transform, opt_accumulator) in order_mut_iter!(enemy_query) {
+        let enemy_pos = enemy_transform.translation.truncate();
+
+        // Overlapping zones stack, the last one gets the credit
+        let mut damage = fixed_math::FIXED_ZERO;
+        let mut last_hit_by = None;
+        for (zone_g_id, zone_pos, zone) in zones.iter() {
+            if enemy_pos.distance(zone_pos) > zone.radius {
+                continue;
+            }
+            damage = damage.saturating_add(zone.damage_per_second.saturating_mul(tick_seconds));
+            last_hit_by = Some(vec![
+                HitBy::Player(zone.player_handle),
+                HitBy::Entity(zone_g_id.clone()),
+            ]);
+        }
+
+        if damage <= fixed_math::FIXED_ZERO {
+            continue;
+        }
+
+        info!("{} burns in fire zone for {} dmg", enemy_g_id, damage);
+
+        if let Some(mut accumulator) = opt_accumulator {
+            accumulator.total_damage = accumulator.total_damage.saturating_add(damage);
+            accumulator.hit_count += 1;
+            accumulator.last_hit_by = last_hit_by;
+        } else {
+            commands.entity(enemy_entity).insert(DamageAccumulator {
+                hit_count: 1,
+                total_damage: damage,
+                last_hit_by,
+            });
+        }
+    }
+}
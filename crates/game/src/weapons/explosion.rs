@@ -0,0 +1,198 @@
+use bevy::{log::{tracing::span, Level}, prelude::*};
+use bevy_fixed::fixed_math;
+use bevy_ggrs::{AddRollbackCommandExtension, Rollback};
+use ggrs::PlayerHandle;
+use utils::{
+    frame::FrameCount,
+    net_id::{GgrsNetId, GgrsNetIdFactory},
+    order_mut_iter,
+};
+
+use crate::{
+    character::{
+        enemy::{
+            ai::perception::{NoiseConfig, NoiseEvents},
+            Enemy,
+        },
+        health::{modifiers::DamageModifiers, DamageAccumulator, HitBy},
+    },
+    collider::{is_colliding, Collider, ColliderShape, Wall},
+};
+
+use super::{BulletKind, EffectType, ExplosionMarker, VisualEffectRequest};
+
+// Distance between two line of sight checks against the walls
+const LINE_OF_SIGHT_STEP: f32 = 8.0;
+
+// Spawn an explosion, it deals its damage on the next explosion system run
+pub fn spawn_explosion(
+    commands: &mut Commands,
+    position: fixed_math::FixedVec2,
+    radius: fixed_math::Fixed,
+    damage: fixed_math::Fixed,
+    player_handle: PlayerHandle,
+    id_factory: &mut ResMut<GgrsNetIdFactory>,
+) {
+    let transform = fixed_math::FixedTransform3D::new(
+        fixed_math::FixedVec3::new(position.x, position.y, fixed_math::FIXED_ZERO),
+        fixed_math::FixedMat3::IDENTITY,
+        fixed_math::FixedVec3::ONE,
+    );
+
+    let g_id = id_factory.next("explosion".to_string());
+    info!("{} spawn at {} by {}", g_id, transform.translation, player_handle);
+
+    commands
+        .spawn((
+            ExplosionMarker {
+                radius,
+                damage,
+                player_handle,
+                processed: false,
+            },
+            transform,
+            g_id,
+        ))
+        .add_rollback();
+}
+
+// Whether no wall stands between the two positions
+pub fn has_line_of_sight(
+    from: fixed_math::FixedVec2,
+    to: fixed_math::FixedVec2,
+    walls: &[(&fixed_math::FixedTransform3D, &Collider)],
+) -> bool {
+    let distance = from.distance(&to);
+    let steps = (distance / fixed_math::new(LINE_OF_SIGHT_STEP)).ceil().to_num::<u32>();
+    if steps == 0 {
+        return true;
+    }
+
+    let point_collider = Collider {
+        offset: fixed_math::FixedVec3::ZERO,
+        shape: ColliderShape::Circle {
+            radius: fixed_math::FIXED_ONE,
+        },
+    };
+
+    let step = (to - from) / fixed_math::Fixed::from_num(steps);
+    // The end points are skipped, the target collider may overlap a wall
+    for i in 1..steps {
+        let point = from + step * fixed_math::Fixed::from_num(i);
+        let point = fixed_math::FixedVec3::new(point.x, point.y, fixed_math::FIXED_ZERO);
+        for (wall_transform, wall_collider) in walls.iter() {
+            if is_colliding(&point, &point_collider, &wall_transform.translation, wall_collider) {
+                return false;
+            }
+        }
+    }
+
+    true
+}
+
+// SYSTEM: EXPLOSIONS
+// Damage the enemies in the blast radius, the damage falls off with the distance
+// and walls block the blast
+pub fn explosion_rollback_system(
+    frame: Res<FrameCount>,
+    mut commands: Commands,
+    mut explosion_query: Query<
+        (&GgrsNetId, Entity, &fixed_math::FixedTransform3D, &mut ExplosionMarker),
+        With<Rollback>,
+    >,
+    mut enemy_query: Query<
+        (
+            &GgrsNetId,
+            Entity,
+            &fixed_math::FixedTransform3D,
+            Option<&mut DamageAccumulator>,
+            Option<&DamageModifiers>,
+        ),
+        (With<Enemy>, With<Rollback>, Without<ExplosionMarker>),
+    >,
+    wall_query: Query<
+        (&fixed_math::FixedTransform3D, &Collider),
+        (With<Wall>, Without<Enemy>, Without<ExplosionMarker>),
+    >,
+    noise_config: Res<NoiseConfig>,
+    mut noises: ResMut<NoiseEvents>,
+) {
+    let system_span = span!(Level::INFO, "ggrs", f = frame.frame, s = "explosions");
+    let _enter = system_span.enter();
+
+    let mut explosions = Vec::new();
+    for (g_id, entity, transform, mut explosion) in order_mut_iter!(explosion_query) {
+        // The blast only lasts one frame
+        commands.entity(entity).despawn();
+        if explosion.processed {
+            continue;
+        }
+        explosion.processed = true;
+
+        let center = transform.translation.truncate();
+        commands.spawn(VisualEffectRequest {
+            effect_type: EffectType::Explosion,
+            position: center,
+            scale: explosion.radius / fixed_math::new(EffectType::Explosion.size() / 2.0),
+        });
+        noises.emit(center, noise_config.explosion_radius);
+
+        // No blast radius, nothing to damage (and no falloff to divide by)
+        if explosion.radius <= fixed_math::FIXED_ZERO {
+            continue;
+        }
+        explosions.push((g_id.clone(), center, explosion.clone()));
+    }
+
+    if explosions.is_empty() {
+        return;
+    }
+
+    let walls: Vec<_> = wall_query.iter().collect();
+
+    for (enemy_g_id, enemy_entity, enemy_transform, opt_accumulator, opt_damage_modifiers) in
+        order_mut_iter!(enemy_query)
+    {
+        let enemy_pos = enemy_transform.translation.truncate();
+
+        // Overlapping blasts add up, the last one gets the credit
+        let mut damage = fixed_math::FIXED_ZERO;
+        let mut last_hit_by = None;
+        for (g_id, center, explosion) in explosions.iter() {
+            let distance = center.distance(&enemy_pos);
+            if distance > explosion.radius || !has_line_of_sight(*center, enemy_pos, &walls) {
+                continue;
+            }
+
+            let falloff = fixed_math::FIXED_ONE - distance / explosion.radius;
+            damage = damage.saturating_add(explosion.damage.saturating_mul(falloff));
+            last_hit_by = Some(vec![
+                HitBy::Player(explosion.player_handle),
+                HitBy::Entity(g_id.clone()),
+            ]);
+        }
+
+        // Blasts can't be critical, only the explosive resistance applies
+        if let Some(damage_modifiers) = opt_damage_modifiers {
+            damage = damage_modifiers.apply(damage, BulletKind::Explosive, false);
+        }
+
+        if damage <= fixed_math::FIXED_ZERO {
+            continue;
+        }
+
+        info!("{} caught in explosion for {} dmg", enemy_g_id, damage);
+
+        if let Some(mut accumulator) = opt_accumulator {
+            accumulator.total_damage = accumulator.total_damage.saturating_add(damage);
+            accumulator.hit_count += 1;
+            accumulator.last_hit_by = last_hit_by;
+        } else {
+            commands.entity(enemy_entity).insert(DamageAccumulator {
+                hit_count: 1,
+                total_damage: damage,
+                last_hit_by,
+            });
+        }
+    }
+}
@@ -11,7 +11,8 @@ use crate::{
         health::{DamageAccumulator, Health, HitBy},
         movement::Velocity,
         enemy::Enemy,
-        player::{input::INPUT_MELEE_ATTACK, jjrs::PeerConfig, Player},
+        player::{downed::Downed, input::INPUT_MELEE_ATTACK, jjrs::PeerConfig, Player},
+        stamina::Stamina,
     },
     collider::{is_colliding, Collider, ColliderShape, CollisionLayer, CollisionSettings},
     global_asset::GlobalAsset,
@@ -563,6 +564,8 @@ pub fn player_melee_attack_system(
             &FacingDirection,
             &Children,
             &mut MeleeAttackState,
+            Option<&Downed>,
+            Option<&mut Stamina>,
         ),
         With<Rollback>,
     >,
@@ -571,13 +574,23 @@ pub fn player_melee_attack_system(
     let system_span = span!(Level::INFO, "ggrs", f = frame.frame, s = "player_melee_attack");
     let _enter = system_span.enter();
     
-    for (net_id, entity, player, transform, facing_direction, children, mut attack_state) in
-        order_mut_iter!(player_query)
+    for (
+        net_id,
+        entity,
+        player,
+        transform,
+        facing_direction,
+        children,
+        mut attack_state,
+        downed,
+        mut stamina,
+    ) in order_mut_iter!(player_query)
     {
         let (input, _status) = inputs[player.handle];
         
         // Check if melee attack button is pressed
-        let wants_melee_attack = input.buttons & INPUT_MELEE_ATTACK != 0;
+        // Downed players cannot melee
+        let wants_melee_attack = input.buttons & INPUT_MELEE_ATTACK != 0 && downed.is_none();
         
         // Find melee weapon in children
         let mut melee_weapon_opt: Option<&MeleeWeapon> = None;
@@ -600,7 +613,13 @@ pub fn player_melee_attack_system(
                         attack_state.end_attack(frame.frame);
                     }
                 }
-            } else if wants_melee_attack && attack_state.can_attack(frame.frame, config.cooldown_frames) {
+            } else if wants_melee_attack
+                && attack_state.can_attack(frame.frame, config.cooldown_frames)
+                // Attacks cost stamina, no attack when it is empty
+                && stamina
+                    .as_deref_mut()
+                    .is_none_or(|stamina| stamina.try_consume(config.stamina_cost, frame.frame))
+            {
                 // Start new attack
                 attack_state.start_attack(frame.frame);
                 
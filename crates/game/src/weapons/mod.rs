@@ -1,6 +1,10 @@
 #[cfg(feature = "debug_ui")]
 pub mod ui;
+pub mod elemental;
+pub mod explosion;
 pub mod melee;
+pub mod upgrade;
+pub mod visual_effect;
 
 use animation::{create_child_sprite, AnimationBundle, FacingDirection, SpriteSheetConfig};
 use bevy::{log::{tracing::span, Level}, platform::collections::{HashMap, HashSet}, prelude::*};
@@ -17,18 +21,22 @@ use utils::{
 use crate::{
     character::{
         dash::DashState,
-        health::{DamageAccumulator, Health, HitBy},
+        enemy::ai::perception::{NoiseConfig, NoiseEvents},
+        health::{modifiers::DamageModifiers, DamageAccumulator, Health, HitBy},
         movement::SprintState,
         player::{
+            downed::{Downed, DOWNED_WEAPON_NAME},
             input::{
                 CursorPosition, INPUT_DASH, INPUT_RELOAD, INPUT_SPRINT, INPUT_SWITCH_WEAPON_MODE,
             },
             jjrs::PeerConfig,
             Player,
         },
+        status_effect::StatusEffects,
     },
     collider::{is_colliding, Collider, ColliderShape, CollisionLayer, CollisionSettings, Wall},
     global_asset::GlobalAsset,
+    perk::Perks,
     system_set::RollbackSystemSet,
     GAME_SPEED,
 };
@@ -74,6 +82,28 @@ pub enum BulletType {
         speed: fixed_math::Fixed,
         penetration: u8,
     },
+    // Sets the target on fire, can leave a fire zone where it hits (radius 0 for none)
+    Incendiary {
+        damage: fixed_math::Fixed,
+        speed: fixed_math::Fixed,
+        burn_damage_per_second: fixed_math::Fixed,
+        burn_duration_frames: u32,
+        #[serde(default)]
+        fire_zone_radius: fixed_math::Fixed,
+        #[serde(default)]
+        fire_zone_duration_frames: u32,
+    },
+    // Slows the target, freezes it solid after enough hits during the slow (0 for never)
+    Cryo {
+        damage: fixed_math::Fixed,
+        speed: fixed_math::Fixed,
+        slow_multiplier: fixed_math::Fixed,
+        slow_duration_frames: u32,
+        #[serde(default)]
+        freeze_after_hits: u32,
+        #[serde(default)]
+        freeze_duration_frames: u32,
+    },
 }
 
 impl fmt::Display for BulletType {
@@ -82,10 +112,117 @@ impl fmt::Display for BulletType {
             BulletType::Standard { .. } => write!(f, "Standard"),
             BulletType::Explosive { .. } => write!(f, "Explosive"),
             BulletType::Piercing { .. } => write!(f, "Piercing"),
+            BulletType::Incendiary { .. } => write!(f, "Incendiary"),
+            BulletType::Cryo { .. } => write!(f, "Cryo"),
+        }
+    }
+}
+
+// Bullet type without its stats, used to key the enemy resistances
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum BulletKind {
+    Standard,
+    Explosive,
+    Piercing,
+    Incendiary,
+    Cryo,
+}
+
+impl BulletType {
+    pub fn kind(&self) -> BulletKind {
+        match self {
+            BulletType::Standard { .. } => BulletKind::Standard,
+            BulletType::Explosive { .. } => BulletKind::Explosive,
+            BulletType::Piercing { .. } => BulletKind::Piercing,
+            BulletType::Incendiary { .. } => BulletKind::Incendiary,
+            BulletType::Cryo { .. } => BulletKind::Cryo,
+        }
+    }
+
+    // Same bullet type with its direct damage scaled
+    pub fn with_damage_multiplier(self, multiplier: fixed_math::Fixed) -> Self {
+        match self {
+            BulletType::Standard { damage, speed } => BulletType::Standard {
+                damage: damage.saturating_mul(multiplier),
+                speed,
+            },
+            BulletType::Explosive {
+                damage,
+                speed,
+                blast_radius,
+                explosive_damage_multiplier,
+            } => BulletType::Explosive {
+                damage: damage.saturating_mul(multiplier),
+                speed,
+                blast_radius,
+                explosive_damage_multiplier,
+            },
+            BulletType::Piercing {
+                damage,
+                speed,
+                penetration,
+            } => BulletType::Piercing {
+                damage: damage.saturating_mul(multiplier),
+                speed,
+                penetration,
+            },
+            BulletType::Incendiary {
+                damage,
+                speed,
+                burn_damage_per_second,
+                burn_duration_frames,
+                fire_zone_radius,
+                fire_zone_duration_frames,
+            } => BulletType::Incendiary {
+                damage: damage.saturating_mul(multiplier),
+                speed,
+                burn_damage_per_second: burn_damage_per_second.saturating_mul(multiplier),
+                burn_duration_frames,
+                fire_zone_radius,
+                fire_zone_duration_frames,
+            },
+            BulletType::Cryo {
+                damage,
+                speed,
+                slow_multiplier,
+                slow_duration_frames,
+                freeze_after_hits,
+                freeze_duration_frames,
+            } => BulletType::Cryo {
+                damage: damage.saturating_mul(multiplier),
+                speed,
+                slow_multiplier,
+                slow_duration_frames,
+                freeze_after_hits,
+                freeze_duration_frames,
+            },
         }
     }
 }
 
+// Damage multiplier by distance traveled: full damage until `start_distance`,
+// then linear down to `min_multiplier` at `end_distance` and beyond
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct DamageFalloff {
+    pub start_distance: fixed_math::Fixed,
+    pub end_distance: fixed_math::Fixed,
+    pub min_multiplier: fixed_math::Fixed,
+}
+
+impl DamageFalloff {
+    pub fn multiplier(&self, distance: fixed_math::Fixed) -> fixed_math::Fixed {
+        if distance <= self.start_distance {
+            return fixed_math::FIXED_ONE;
+        }
+        if distance >= self.end_distance {
+            return self.min_multiplier;
+        }
+
+        let progress = (distance - self.start_distance) / (self.end_distance - self.start_distance);
+        fixed_math::FIXED_ONE - (fixed_math::FIXED_ONE - self.min_multiplier) * progress
+    }
+}
+
 #[derive(Component)]
 pub struct ExplosiveTag;
 
@@ -100,6 +237,9 @@ pub struct FiringModeConfig {
     pub recoil: fixed_math::Fixed,
     pub bullet_type: BulletType,
     pub range: fixed_math::Fixed,
+    // Full damage over the whole range when missing
+    #[serde(default)]
+    pub damage_falloff: Option<DamageFalloff>,
 
     pub reload_time_seconds: fixed_math::Fixed,
     pub mag: MagBulletConfig,
@@ -108,10 +248,19 @@ pub struct FiringModeConfig {
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct WeaponConfig {
     pub name: String,
+    // Name shown to the player, `name` stays the key used to look up configs
+    #[serde(default)]
+    pub display_name: Option<String>,
     pub default_firing_mode: String,
     pub firing_modes: HashMap<String, FiringModeConfig>,
 }
 
+impl WeaponConfig {
+    pub fn display_name(&self) -> &str {
+        self.display_name.as_deref().unwrap_or(&self.name)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct WeaponSpriteConfig {
     pub name: String,
@@ -158,11 +307,15 @@ pub struct VisualEffectRequest {
     pub scale: fixed_math::Fixed,
 }
 
-#[derive(Clone)]
+#[derive(Clone, Copy, Debug)]
 pub enum EffectType {
     BulletHit,
     Explosion,
     Piercing,
+    Incendiary,
+    Cryo,
+    Freeze,
+    CriticalHit,
 }
 
 #[derive(Component, Clone, Serialize, Deserialize)]
@@ -185,10 +338,22 @@ pub struct Bullet {
     pub damage: fixed_math::Fixed,
     pub range: fixed_math::Fixed,
     pub distance_traveled: fixed_math::Fixed,
-    pub player_handle: PlayerHandle,
+    pub falloff: Option<DamageFalloff>,
+    // Player or enemy that fired the bullet
+    pub source: HitBy,
     pub created_at: u32,
 }
 
+impl Bullet {
+    // Player that fired the bullet, none for enemy projectiles
+    pub fn player_handle(&self) -> Option<PlayerHandle> {
+        match self.source {
+            HitBy::Player(handle) => Some(handle),
+            _ => None,
+        }
+    }
+}
+
 /// Component to track the player's weapon inventory
 #[derive(Component, Debug, Clone, Default)]
 pub struct WeaponInventory {
@@ -235,6 +400,23 @@ pub struct FireWeaponEvent {
     pub player_entity: Entity,
 }
 
+// Sent by the bullet collision system for every bullet hitting a character,
+// read outside the GGRS schedule by the visuals. A rollback sends the hits of
+// the resimulated frames again, readers skip the (frame, bullet, target) seen
+#[derive(Event, Message, Clone, Debug)]
+pub struct BulletHitEvent {
+    pub frame: u32,
+    pub bullet: GgrsNetId,
+    pub target: GgrsNetId,
+    pub position: fixed_math::FixedVec2,
+    pub damage: fixed_math::Fixed,
+    pub is_critical: bool,
+    // Visual of the elemental status applied by the hit
+    pub elemental_effect: Option<EffectType>,
+    // None when an enemy projectile hit a player
+    pub player_handle: Option<PlayerHandle>,
+}
+
 // ASSETS
 
 #[derive(Asset, TypePath, Serialize, Deserialize)]
@@ -243,6 +425,25 @@ pub struct WeaponsConfig(pub HashMap<String, WeaponAsset>);
 // UTILITY FUNCTION
 
 impl WeaponModeState {
+    // Create a full ammo state for a firing mode from its magazine configuration
+    pub fn from_mag_config(mag: &MagBulletConfig) -> Self {
+        let mut weapon_mode_state = WeaponModeState::default();
+        match *mag {
+            MagBulletConfig::Mag {
+                mag_size,
+                mag_limit,
+            } => {
+                weapon_mode_state.mag_ammo = mag_size;
+                weapon_mode_state.mag_quantity = mag_limit;
+                weapon_mode_state.mag_size = mag_size;
+            }
+            MagBulletConfig::Magless { bullet_limit } => {
+                weapon_mode_state.mag_ammo = bullet_limit;
+            }
+        };
+        weapon_mode_state
+    }
+
     // Do the reloading of the ammo when the reloading process is over or some other event
     pub fn reload(&mut self) {
         if self.mag_quantity > 0 {
@@ -262,6 +463,14 @@ impl WeaponModesState {
             mode.reload();
         }
     }
+
+    // Refill every firing mode to the full ammo defined by the weapon config
+    pub fn refill(&mut self, config: &WeaponConfig) {
+        for (k, v) in config.firing_modes.iter() {
+            self.modes
+                .insert(k.clone(), WeaponModeState::from_mag_config(&v.mag));
+        }
+    }
 }
 
 impl WeaponInventory {
@@ -341,24 +550,9 @@ pub fn spawn_weapon_for_player(
     let mut weapon_modes_state = WeaponModesState::default();
     weapon_state.active_mode = weapon.config.default_firing_mode.clone();
     for (k, v) in weapon.config.firing_modes.iter() {
-        let mut weapon_mode_state = WeaponModeState::default();
-        match v.mag {
-            MagBulletConfig::Mag {
-                mag_size,
-                mag_limit,
-            } => {
-                weapon_mode_state.mag_ammo = mag_size;
-                weapon_mode_state.mag_quantity = mag_limit;
-                weapon_mode_state.mag_size = mag_size;
-            }
-            MagBulletConfig::Magless { bullet_limit } => {
-                weapon_mode_state.mag_ammo = bullet_limit;
-            }
-        };
-
         weapon_modes_state
             .modes
-            .insert(k.clone(), weapon_mode_state);
+            .insert(k.clone(), WeaponModeState::from_mag_config(&v.mag));
     }
 
     let weapon: Weapon = weapon.into();
@@ -419,49 +613,12 @@ fn spawn_bullet_rollback(
     direction: fixed_math::FixedVec2,
     bullet_type: BulletType,
     range: fixed_math::Fixed,
+    falloff: Option<DamageFalloff>,
     player_handle: PlayerHandle,
     current_frame: u32,
     collision_settings: &Res<CollisionSettings>,
     id_factory: &mut ResMut<GgrsNetIdFactory>,
 ) -> Entity {
-    let (velocity, damage, range, radius) = match &bullet_type {
-        BulletType::Standard {
-            speed,
-            damage: damage_bullet,
-        } => (
-            direction * (*speed / *GAME_SPEED),
-            *damage_bullet,
-            range,
-            fixed_math::new(5.0),
-        ),
-        BulletType::Explosive {
-            speed,
-            damage: damage_bullet,
-            ..
-        } => (
-            direction * (*speed / *GAME_SPEED),
-            *damage_bullet,
-            range,
-            fixed_math::new(8.0),
-        ),
-        BulletType::Piercing {
-            speed,
-            damage: damage_bullet,
-            ..
-        } => (
-            direction * (*speed / *GAME_SPEED),
-            *damage_bullet,
-            range,
-            fixed_math::new(5.0),
-        ),
-    };
-
-    let color = match &bullet_type {
-        BulletType::Standard { .. } => Color::BLACK,
-        BulletType::Explosive { .. } => Color::WHITE,
-        BulletType::Piercing { .. } => Color::BLACK,
-    };
-
     let local_muzzle_offset_v2 = if !facing_direction.should_flip_x() {
         weapon.sprite_config.bullet_offset_right
     } else {
@@ -503,11 +660,120 @@ fn spawn_bullet_rollback(
         fixed_math::FixedVec3::ONE,
     );
 
+    spawn_bullet(
+        commands,
+        new_projectile_fixed_transform,
+        direction,
+        bullet_type,
+        range,
+        falloff,
+        HitBy::Player(player_handle),
+        collision_settings.bullet_layer,
+        current_frame,
+        id_factory,
+    )
+}
+
+// Spawn a projectile fired by an enemy, it only collides with players and walls
+pub fn spawn_enemy_projectile(
+    commands: &mut Commands,
+    owner: &GgrsNetId,
+    position: fixed_math::FixedVec2,
+    direction: fixed_math::FixedVec2,
+    bullet_type: BulletType,
+    range: fixed_math::Fixed,
+    current_frame: u32,
+    collision_settings: &Res<CollisionSettings>,
+    id_factory: &mut ResMut<GgrsNetIdFactory>,
+) -> Entity {
+    let transform = fixed_math::FixedTransform3D::new(
+        fixed_math::FixedVec3::new(position.x, position.y, fixed_math::FIXED_ZERO),
+        fixed_math::FixedMat3::IDENTITY,
+        fixed_math::FixedVec3::ONE,
+    );
+
+    spawn_bullet(
+        commands,
+        transform,
+        direction,
+        bullet_type,
+        range,
+        None,
+        HitBy::Enemy(owner.clone()),
+        collision_settings.enemy_bullet_layer,
+        current_frame,
+        id_factory,
+    )
+}
+
+// Spawn the bullet entity, shared by the player weapons and the enemy projectiles
+fn spawn_bullet(
+    commands: &mut Commands,
+    new_projectile_fixed_transform: fixed_math::FixedTransform3D,
+    direction: fixed_math::FixedVec2,
+    bullet_type: BulletType,
+    range: fixed_math::Fixed,
+    falloff: Option<DamageFalloff>,
+    source: HitBy,
+    layer: usize,
+    current_frame: u32,
+    id_factory: &mut ResMut<GgrsNetIdFactory>,
+) -> Entity {
+    let (velocity, damage, range, radius) = match &bullet_type {
+        BulletType::Standard {
+            speed,
+            damage: damage_bullet,
+        } => (
+            direction * (*speed / *GAME_SPEED),
+            *damage_bullet,
+            range,
+            fixed_math::new(5.0),
+        ),
+        BulletType::Explosive {
+            speed,
+            damage: damage_bullet,
+            ..
+        } => (
+            direction * (*speed / *GAME_SPEED),
+            *damage_bullet,
+            range,
+            fixed_math::new(8.0),
+        ),
+        BulletType::Piercing {
+            speed,
+            damage: damage_bullet,
+            ..
+        }
+        | BulletType::Incendiary {
+            speed,
+            damage: damage_bullet,
+            ..
+        }
+        | BulletType::Cryo {
+            speed,
+            damage: damage_bullet,
+            ..
+        } => (
+            direction * (*speed / *GAME_SPEED),
+            *damage_bullet,
+            range,
+            fixed_math::new(5.0),
+        ),
+    };
+
+    let color = match &bullet_type {
+        BulletType::Standard { .. } => Color::BLACK,
+        BulletType::Explosive { .. } => Color::WHITE,
+        BulletType::Piercing { .. } => Color::BLACK,
+        BulletType::Incendiary { .. } => EffectType::Incendiary.color(),
+        BulletType::Cryo { .. } => EffectType::Cryo.color(),
+    };
+
     let g_id = id_factory.next(format!("{}", bullet_type));
 
     info!(
         "{} spawn at {} by {}",
-        g_id, new_projectile_fixed_transform.translation, player_handle
+        g_id, new_projectile_fixed_transform.translation, source
     );
 
     let mut entity_commands = commands.spawn((
@@ -518,14 +784,15 @@ fn spawn_bullet_rollback(
             damage,
             range,
             distance_traveled: fixed_math::Fixed::ZERO,
-            player_handle,
+            falloff,
+            source,
             created_at: current_frame,
         },
         Collider {
             offset: fixed_math::FixedVec3::ZERO,
             shape: ColliderShape::Circle { radius },
         },
-        CollisionLayer(collision_settings.bullet_layer),
+        CollisionLayer(layer),
         new_projectile_fixed_transform.to_bevy_transform(),
         new_projectile_fixed_transform,
         g_id,
@@ -587,6 +854,9 @@ pub fn weapon_rollback_system(
         &MeleeAttackState,
         &fixed_math::FixedTransform3D,
         &Player,
+        Option<&Downed>,
+        Option<&Perks>,
+        Option<&StatusEffects>,
     )>,
     mut weapon_query: Query<(
         &mut Weapon,
@@ -601,12 +871,15 @@ pub fn weapon_rollback_system(
     collision_settings: Res<CollisionSettings>,
 
     mut id_factory: ResMut<GgrsNetIdFactory>,
+
+    noise_config: Res<NoiseConfig>,
+    mut noises: ResMut<NoiseEvents>,
 ) {
     let system_span = span!(Level::INFO, "ggrs", f = frame.frame, s = "weapon");
     let _enter = system_span.enter(); // Enter the span
 
     // Process weapon firing for all players
-    for (_entity, mut inventory, sprint_state, dash_state, melee_attack_state, transform, player) in
+    for (_entity, mut inventory, sprint_state, dash_state, melee_attack_state, transform, player, downed, perks, status_effects) in
         inventory_query.iter_mut()
     {
         let (input, _input_status) = inputs[player.handle];
@@ -616,6 +889,11 @@ pub fn weapon_rollback_system(
             continue;
         }
 
+        // Stunned players can't fire
+        if status_effects.is_some_and(|status| status.is_stunned(frame.frame)) {
+            continue;
+        }
+
         // Don't allow weapon firing during melee attacks
         if melee_attack_state.is_attacking {
             continue;
@@ -638,8 +916,20 @@ pub fn weapon_rollback_system(
         if let Ok((weapon, mut weapon_state, mut weapon_modes_state, weapon_transform, child_of)) =
             weapon_query.get_mut(weapon_entity)
         {
+            // Downed players can only use the pistol
+            if downed.is_some() && weapon.config.name != DOWNED_WEAPON_NAME {
+                continue;
+            }
+
             let active_mode = weapon_state.active_mode.clone();
             let weapon_config = weapon.config.firing_modes.get(&active_mode).unwrap();
+            // Perks speed up the reload
+            let reload_time_seconds = perks
+                .map_or(weapon_config.reload_time_seconds, |perks| perks.reload_time(weapon_config.reload_time_seconds));
+            // Damage boost scales the bullets damage
+            let bullet_type = status_effects.map_or(weapon_config.bullet_type, |status| {
+                weapon_config.bullet_type.with_damage_multiplier(status.damage_multiplier(frame.frame))
+            });
 
             if input.buttons & INPUT_SWITCH_WEAPON_MODE != 0 {
                 if let Some(new_mode) = weapon_modes_state
@@ -669,12 +959,12 @@ pub fn weapon_rollback_system(
                     continue;
                 }
             } else if input.buttons & INPUT_RELOAD != 0 && !weapon_mode_state.is_mag_full() {
-                inventory.start_reload(frame.frame, weapon_config.reload_time_seconds);
+                inventory.start_reload(frame.frame, reload_time_seconds);
                 continue;
             }
 
             // Handle switching of weapons, will start firing on the next frame
-            if input.switch_weapon && !inventory.weapons.is_empty() {
+            if input.switch_weapon && !inventory.weapons.is_empty() && downed.is_none() {
                 let new_index = (inventory.active_weapon_index + 1) % inventory.weapons.len();
 
                 if new_index != inventory.active_weapon_index
@@ -749,7 +1039,7 @@ pub fn weapon_rollback_system(
                 };
 
                 if empty {
-                    inventory.start_reload(frame.frame, weapon_config.reload_time_seconds);
+                    inventory.start_reload(frame.frame, reload_time_seconds);
                     continue;
                 }
 
@@ -793,8 +1083,9 @@ pub fn weapon_rollback_system(
                                         weapon_transform,
                                         facing_direction,
                                         direction,
-                                        weapon_config.bullet_type,
+                                        bullet_type,
                                         weapon_config.range,
+                                        weapon_config.damage_falloff,
                                         player.handle,
                                         frame.frame,
                                         &collision_settings,
@@ -803,7 +1094,7 @@ pub fn weapon_rollback_system(
                                 }
                                 weapon_mode_state.mag_ammo -= 1; // Shotgun uses one ammo for all pellets
                                 inventory
-                                    .start_reload(frame.frame, weapon_config.reload_time_seconds);
+                                    .start_reload(frame.frame, reload_time_seconds);
                             }
                             _ => {
                                 let random_fixed_val = rng.next_fixed();
@@ -823,8 +1114,9 @@ pub fn weapon_rollback_system(
                                     weapon_transform,
                                     facing_direction,
                                     direction,
-                                    weapon_config.bullet_type,
+                                    bullet_type,
                                     weapon_config.range,
+                                    weapon_config.damage_falloff,
                                     player.handle,
                                     frame.frame,
                                     &collision_settings,
@@ -845,6 +1137,9 @@ pub fn weapon_rollback_system(
                             }
                         }
                         weapon_state.last_fire_frame = frame.frame;
+
+                        // Enemies with perception hear the gunshot
+                        noises.emit(transform.translation.truncate(), noise_config.gunshot_radius);
                     }
                 }
             } else {
@@ -912,9 +1207,17 @@ pub fn bullet_rollback_collision_system(
             Option<&Wall>,
             Option<&Health>,
             Option<&mut DamageAccumulator>,
+            Option<&mut StatusEffects>,
+            Option<&DamageModifiers>,
         ),
         (Without<Bullet>, With<Rollback>),
     >,
+    mut fire_zone_query: Query<
+        (&GgrsNetId, &fixed_math::FixedTransform3D, &mut elemental::FireZone),
+        With<Rollback>,
+    >,
+    mut id_factory: ResMut<GgrsNetIdFactory>,
+    mut hit_writer: MessageWriter<BulletHitEvent>,
 ) {
     let system_span = span!(
         Level::INFO,
@@ -945,6 +1248,8 @@ pub fn bullet_rollback_collision_system(
             _opt_wall,
             _opt_health,
             /* no mut here */ _,
+            _,
+            _,
         ) in collider_query.iter()
         {
             if !settings.layer_matrix[bullet_layer.0][target_layer.0] {
@@ -978,23 +1283,63 @@ pub fn bullet_rollback_collision_system(
         // Now get mutable components for this specific, deterministically chosen target
         if let Ok((
             _target_entity_refetch,
-            _target_transform,
+            target_transform,
             _target_collider,
             _target_layer,
             _,
             opt_wall,
             opt_health,
             opt_accumulator_mut,
+            opt_status_effects_mut,
+            opt_damage_modifiers,
         )) = collider_query.get_mut(deterministic_target_entity)
         {
             if opt_health.is_some() {
-                let last_hit_by = Some(vec![
-                    HitBy::Player(bullet.player_handle),
-                    HitBy::Entity(ggrs_net_id.clone()),
-                ]);
+                let hit_by = vec![bullet.source.clone(), HitBy::Entity(ggrs_net_id.clone())];
+
+                // Incendiary and cryo bullets apply their status effects
+                let elemental_effect = opt_status_effects_mut.and_then(|mut status_effects| {
+                    elemental::apply_elemental_hit(
+                        &bullet.bullet_type,
+                        &mut status_effects,
+                        frame.frame,
+                        hit_by.clone(),
+                    )
+                });
+
+                // Damage falls off with the distance traveled, then the target
+                // resistance and critical zone apply
+                let hit_position = bullet_transform.translation.truncate();
+                let mut damage = bullet.falloff.map_or(bullet.damage, |falloff| {
+                    bullet
+                        .damage
+                        .saturating_mul(falloff.multiplier(bullet.distance_traveled))
+                });
+                let mut is_critical = false;
+                if let Some(damage_modifiers) = opt_damage_modifiers {
+                    is_critical = damage_modifiers
+                        .is_critical_hit(hit_position, target_transform.translation.truncate());
+                    damage = damage_modifiers.apply(damage, bullet.bullet_type.kind(), is_critical);
+                }
+
+                if is_critical {
+                    info!("{} critical hit on {} for {} dmg", ggrs_net_id, deterministic_target_g_id, damage);
+                }
+
+                hit_writer.write(BulletHitEvent {
+                    frame: frame.frame,
+                    bullet: ggrs_net_id.clone(),
+                    target: deterministic_target_g_id.clone(),
+                    position: hit_position,
+                    damage,
+                    is_critical,
+                    elemental_effect,
+                    player_handle: bullet.player_handle(),
+                });
+
+                let last_hit_by = Some(hit_by);
                 if let Some(mut accumulator) = opt_accumulator_mut {
-                    accumulator.total_damage =
-                        accumulator.total_damage.saturating_add(bullet.damage);
+                    accumulator.total_damage = accumulator.total_damage.saturating_add(damage);
                     accumulator.hit_count += 1;
                     accumulator.last_hit_by = last_hit_by;
                 } else {
@@ -1002,18 +1347,51 @@ pub fn bullet_rollback_collision_system(
                         .entity(deterministic_target_entity)
                         .insert(DamageAccumulator {
                             hit_count: 1,
-                            total_damage: bullet.damage,
+                            total_damage: damage,
                             last_hit_by,
                         });
                 }
             }
 
+            // Incendiary bullets can leave a fire zone where they hit, the fire
+            // zones and explosions only hurt enemies so enemy projectiles don't
+            // leave any
+            if let Some(player_handle) = bullet.player_handle() {
+                elemental::spawn_fire_zone(
+                    &mut commands,
+                    &bullet.bullet_type,
+                    bullet_transform.translation.truncate(),
+                    player_handle,
+                    frame.frame,
+                    &mut fire_zone_query,
+                    &mut id_factory,
+                );
+            }
+
             let mut should_bullet_despawn_now = false;
             match bullet.bullet_type {
-                BulletType::Standard { .. } => {
+                BulletType::Standard { .. }
+                | BulletType::Incendiary { .. }
+                | BulletType::Cryo { .. } => {
                     should_bullet_despawn_now = true;
                 }
-                BulletType::Explosive { .. } => {
+                BulletType::Explosive {
+                    damage,
+                    blast_radius,
+                    explosive_damage_multiplier,
+                    ..
+                } => {
+                    // Explosive bullets blast around the impact point
+                    if let Some(player_handle) = bullet.player_handle() {
+                        explosion::spawn_explosion(
+                            &mut commands,
+                            bullet_transform.translation.truncate(),
+                            blast_radius,
+                            damage.saturating_mul(explosive_damage_multiplier),
+                            player_handle,
+                            &mut id_factory,
+                        );
+                    }
                     should_bullet_despawn_now = true;
                 }
                 BulletType::Piercing { .. } => {
@@ -1103,11 +1481,12 @@ pub fn weapons_config_update_system(
 
     mut ev_asset: MessageReader<AssetEvent<WeaponsConfig>>,
 
-    mut query_weapons: Query<(&Children, Entity, &mut Weapon)>,
+    mut query_weapons: Query<(&Children, Entity, &mut Weapon), Without<upgrade::UpgradedWeapon>>,
 ) {
     for event in ev_asset.read() {
         if let AssetEvent::Modified { id } = event {
             if let Some(weapons_config) = weapons_config.get(*id) {
+                // Upgraded weapons keep their rewritten config
                 for (_childs, _entity, mut weapon) in query_weapons.iter_mut() {
                     if let Some(config) = weapons_config.0.get(&weapon.config.name) {
                         weapon.config = config.config.clone();
@@ -1131,18 +1510,28 @@ impl Plugin for BaseWeaponGamePlugin {
         // Add RON asset plugins for weapons and melee weapons
         app.add_plugins(RonAssetPlugin::<WeaponsConfig>::new(&["ron"]));
         app.add_plugins(RonAssetPlugin::<melee::MeleeWeaponsConfig>::new(&["ron"]));
+        app.add_plugins(RonAssetPlugin::<upgrade::WeaponUpgradesConfig>::new(&["ron"]));
 
         // Rollback components for ranged weapons
         app.rollback_component_with_clone::<WeaponInventory>()
+            .rollback_component_with_clone::<Weapon>()
             .rollback_component_with_clone::<WeaponModesState>()
             .rollback_component_with_clone::<WeaponState>()
-            .rollback_component_with_clone::<Bullet>();
+            .rollback_component_with_clone::<Bullet>()
+            .rollback_component_with_clone::<elemental::FireZone>()
+            .rollback_component_with_clone::<ExplosionMarker>();
+
+        app.add_message::<BulletHitEvent>();
 
         // Rollback components for melee weapons
         app.rollback_component_with_clone::<melee::MeleeWeapon>()
             .rollback_component_with_clone::<melee::MeleeAttackState>()
             .rollback_component_with_clone::<melee::MeleeHitbox>();
 
+        // Rollback components for the upgrade station
+        app.rollback_component_with_clone::<upgrade::UpgradeStation>()
+            .rollback_component_with_clone::<upgrade::UpgradedWeapon>();
+
         app.add_systems(
             Update,
             (
@@ -1150,9 +1539,25 @@ impl Plugin for BaseWeaponGamePlugin {
                 weapon_inventory_system,
                 weapons_config_update_system,
                 melee::update_slash_effects, // Add slash effect animation system
+                visual_effect::spawn_hit_effects,
+                visual_effect::spawn_visual_effects,
+                visual_effect::update_visual_effects,
+                upgrade::update_upgraded_weapon_tint,
             ),
         );
 
+        // Upgrade station interactions run with the other interactables
+        app.add_systems(
+            GgrsSchedule,
+            (
+                upgrade::handle_upgrade_station_interaction,
+                upgrade::upgrade_station_phase_system,
+            )
+                .chain()
+                .after(crate::mystery_box::systems::mystery_box_phase_system)
+                .in_set(RollbackSystemSet::Interaction),
+        );
+
         app.add_systems(
             GgrsSchedule,
             (
@@ -1161,6 +1566,8 @@ impl Plugin for BaseWeaponGamePlugin {
                 weapon_rollback_system.after(system_weapon_position),
                 bullet_rollback_system.after(weapon_rollback_system),
                 bullet_rollback_collision_system.after(bullet_rollback_system),
+                elemental::fire_zone_rollback_system.after(bullet_rollback_collision_system),
+                explosion::explosion_rollback_system.after(elemental::fire_zone_rollback_system),
                 // Melee weapon systems
                 melee::player_melee_attack_system.after(bullet_rollback_collision_system),
                 melee::enemy_melee_attack_system.after(melee::player_melee_attack_system),
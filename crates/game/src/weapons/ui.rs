@@ -92,7 +92,7 @@ fn update_weapons_text(
             if let Ok(mut text) = q_weapon.single_mut() {
                 text.0 = format!(
                     "Weapon: {} - {}",
-                    active_weapon.1.config.name, state.active_mode
+                    active_weapon.1.config.display_name(), state.active_mode
                 );
             }
             if let Ok(mut text) = q_ammo.single_mut() {
@@ -0,0 +1,370 @@
+use animation::{ColoredLayer, LayerName};
+use bevy::{log::{tracing::span, Level}, platform::collections::HashMap, prelude::*};
+use bevy_fixed::fixed_math;
+use bevy_ggrs::Rollback;
+use ggrs::PlayerHandle;
+use serde::{Deserialize, Serialize};
+use utils::{frame::FrameCount, net_id::GgrsNetId, order_mut_iter};
+
+use crate::{
+    character::player::{input::InteractionInput, Player},
+    economy::Points,
+    global_asset::GlobalAsset,
+    interaction::{InteractionEvent, InteractionType},
+    power::PowerState,
+};
+
+use super::{BulletType, MagBulletConfig, Weapon, WeaponConfig, WeaponInventory, WeaponModesState};
+
+fn default_multiplier() -> fixed_math::Fixed {
+    fixed_math::FIXED_ONE
+}
+
+// WEAPON UPGRADE CONFIG
+// Multipliers are applied to every firing mode of the weapon, overrides replace the value
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WeaponUpgradeConfig {
+    #[serde(default)]
+    pub display_name: Option<String>,
+    #[serde(default = "default_multiplier")]
+    pub damage_multiplier: fixed_math::Fixed,
+    #[serde(default = "default_multiplier")]
+    pub firing_rate_multiplier: fixed_math::Fixed,
+    #[serde(default = "default_multiplier")]
+    pub mag_size_multiplier: fixed_math::Fixed,
+    #[serde(default)]
+    pub mag: Option<MagBulletConfig>,
+    #[serde(default)]
+    pub bullet_type: Option<BulletType>,
+}
+
+impl WeaponUpgradeConfig {
+    // Create the upgraded version of a weapon config
+    pub fn apply(&self, config: &WeaponConfig) -> WeaponConfig {
+        let mut upgraded = config.clone();
+        if let Some(display_name) = &self.display_name {
+            upgraded.display_name = Some(display_name.clone());
+        }
+
+        for mode in upgraded.firing_modes.values_mut() {
+            // The overridden bullet already has its final damage
+            mode.bullet_type = match self.bullet_type {
+                Some(bullet_type) => bullet_type,
+                None => mode.bullet_type.with_damage_multiplier(self.damage_multiplier),
+            };
+            mode.firing_rate = mode.firing_rate.saturating_mul(self.firing_rate_multiplier);
+            mode.mag = match &self.mag {
+                Some(mag) => mag.clone(),
+                None => scale_mag(&mode.mag, self.mag_size_multiplier),
+            };
+        }
+
+        upgraded
+    }
+}
+
+fn scale_mag(mag: &MagBulletConfig, multiplier: fixed_math::Fixed) -> MagBulletConfig {
+    let scale = |value: u32| -> u32 {
+        fixed_math::Fixed::from_num(value)
+            .saturating_mul(multiplier)
+            .to_num::<u32>()
+            .max(1)
+    };
+    match *mag {
+        MagBulletConfig::Mag {
+            mag_size,
+            mag_limit,
+        } => MagBulletConfig::Mag {
+            mag_size: scale(mag_size),
+            mag_limit,
+        },
+        MagBulletConfig::Magless { bullet_limit } => MagBulletConfig::Magless {
+            bullet_limit: scale(bullet_limit),
+        },
+    }
+}
+
+// WEAPON UPGRADES ASSET
+#[derive(Asset, TypePath, Debug, Clone, Serialize, Deserialize)]
+pub struct WeaponUpgradesConfig {
+    pub cost: u32,
+    // Frames the station works before the weapon can be collected
+    pub upgrade_frames: u32,
+    // Tint of the upgraded weapon sprite (visual only)
+    pub tint: (f32, f32, f32),
+    // WeaponConfig.name -> upgrade
+    pub upgrades: HashMap<String, WeaponUpgradeConfig>,
+}
+
+// UPGRADE STATION STATE
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum UpgradeStationPhase {
+    #[default]
+    Idle,
+    // Paid, the weapon will be ready at the given frame
+    Upgrading {
+        owner: PlayerHandle,
+        weapon: Entity,
+        ready_at_frame: u32,
+    },
+    // Waiting for the owner to collect the upgraded weapon
+    Ready { owner: PlayerHandle, weapon: Entity },
+}
+
+#[derive(Component, Debug, Clone, Default)]
+pub struct UpgradeStation {
+    pub phase: UpgradeStationPhase,
+}
+
+// Marker for a weapon that went through the upgrade station
+#[derive(Component, Debug, Clone, Copy)]
+pub struct UpgradedWeapon {
+    pub tint: (f32, f32, f32),
+}
+
+// SYSTEMS
+
+pub fn handle_upgrade_station_interaction(
+    frame: Res<FrameCount>,
+    mut commands: Commands,
+    mut event_reader: MessageReader<InteractionEvent>,
+    upgrades_assets: Res<Assets<WeaponUpgradesConfig>>,
+    global_assets: Res<GlobalAsset>,
+    power: Res<PowerState>,
+    mut station_query: Query<(&GgrsNetId, &mut UpgradeStation), With<Rollback>>,
+    mut player_query: Query<
+        (&Player, &InteractionInput, &mut Points, &mut WeaponInventory),
+        With<Rollback>,
+    >,
+    mut weapon_query: Query<(&mut Weapon, &mut WeaponModesState, Option<&UpgradedWeapon>)>,
+) {
+    let system_span = span!(Level::INFO, "ggrs", f = frame.frame, s = "handle_upgrade_station_interaction");
+    let _enter = system_span.enter();
+
+    let Some(upgrades_config) = upgrades_assets.get(&global_assets.weapon_upgrades) else {
+        return;
+    };
+
+    // Events are delivered in deterministic order from interaction_detection_system
+    for event in event_reader.read() {
+        if event.interaction_type != InteractionType::UpgradeStation {
+            continue;
+        }
+
+        let Ok((station_net_id, mut station)) = station_query.get_mut(event.interactable) else {
+            continue;
+        };
+        let Ok((player, interaction_input, mut points, mut inventory)) =
+            player_query.get_mut(event.interactor)
+        else {
+            continue;
+        };
+
+        if !interaction_input.just_pressed || inventory.weapons.is_empty() {
+            continue;
+        }
+
+        // The station stays inactive until the power is on
+        if !power.on {
+            info!(
+                "{} upgrade station {}: requires power",
+                frame.as_ref(), station_net_id
+            );
+            continue;
+        }
+
+        match station.phase.clone() {
+            UpgradeStationPhase::Idle => {
+                let (weapon_entity, weapon) = inventory.active_weapon().clone();
+
+                if let Ok((_, _, Some(_))) = weapon_query.get(weapon_entity) {
+                    info!(
+                        "{} upgrade station {}: {} is already upgraded",
+                        frame.as_ref(), station_net_id, weapon.config.name
+                    );
+                    continue;
+                }
+
+                if !upgrades_config.upgrades.contains_key(&weapon.config.name) {
+                    info!(
+                        "{} upgrade station {}: no upgrade for {}",
+                        frame.as_ref(), station_net_id, weapon.config.name
+                    );
+                    continue;
+                }
+
+                if !points.try_spend(upgrades_config.cost) {
+                    info!(
+                        "{} upgrade station {}: {} cannot afford upgrade ({} < {})",
+                        frame.as_ref(), station_net_id, event.interactor_net_id, points.current, upgrades_config.cost
+                    );
+                    continue;
+                }
+
+                info!(
+                    "{} upgrade station {}: {} paid {} to upgrade {}",
+                    frame.as_ref(), station_net_id, event.interactor_net_id, upgrades_config.cost, weapon.config.name
+                );
+
+                station.phase = UpgradeStationPhase::Upgrading {
+                    owner: player.handle,
+                    weapon: weapon_entity,
+                    ready_at_frame: frame.frame + upgrades_config.upgrade_frames,
+                };
+            }
+            UpgradeStationPhase::Ready { owner, weapon } if owner == player.handle => {
+                station.phase = UpgradeStationPhase::Idle;
+
+                // The weapon may have been lost while upgrading
+                let Some(index) = inventory.weapons.iter().position(|(e, _)| *e == weapon) else {
+                    warn!(
+                        "{} upgrade station {}: upgraded weapon is no longer owned",
+                        frame.as_ref(), station_net_id
+                    );
+                    continue;
+                };
+                let Ok((mut weapon_component, mut modes_state, _)) = weapon_query.get_mut(weapon) else {
+                    continue;
+                };
+                let Some(upgrade) = upgrades_config.upgrades.get(&weapon_component.config.name) else {
+                    continue;
+                };
+
+                let upgraded_config = upgrade.apply(&weapon_component.config);
+                modes_state.refill(&upgraded_config);
+                weapon_component.config = upgraded_config.clone();
+                inventory.weapons[index].1.config = upgraded_config;
+                inventory.active_weapon_index = index;
+                inventory.frame_switched = frame.frame;
+                inventory.clear_reloading();
+
+                commands.entity(weapon).insert(UpgradedWeapon {
+                    tint: upgrades_config.tint,
+                });
+
+                info!(
+                    "{} upgrade station {}: {} collected {}",
+                    frame.as_ref(), station_net_id, event.interactor_net_id, weapon_component.config.display_name()
+                );
+            }
+            _ => {}
+        }
+    }
+}
+
+pub fn upgrade_station_phase_system(
+    frame: Res<FrameCount>,
+    mut station_query: Query<(&GgrsNetId, &mut UpgradeStation), With<Rollback>>,
+) {
+    for (net_id, mut station) in order_mut_iter!(station_query) {
+        if let UpgradeStationPhase::Upgrading {
+            owner,
+            weapon,
+            ready_at_frame,
+        } = station.phase
+        {
+            if frame.frame >= ready_at_frame {
+                info!("{} upgrade station {}: weapon ready for player {}", frame.as_ref(), net_id, owner);
+                station.phase = UpgradeStationPhase::Ready { owner, weapon };
+            }
+        }
+    }
+}
+
+// Non rollback system to tint the sprite layers of upgraded weapons
+pub fn update_upgraded_weapon_tint(
+    mut commands: Commands,
+    weapon_query: Query<(&Children, Option<&UpgradedWeapon>), With<Weapon>>,
+    layer_query: Query<Option<&ColoredLayer>, With<LayerName>>,
+) {
+    for (childs, upgraded) in weapon_query.iter() {
+        for child in childs.iter() {
+            let Ok(colored_layer) = layer_query.get(child) else {
+                continue;
+            };
+            match (upgraded, colored_layer) {
+                (Some(upgraded), _) => {
+                    let color = Color::srgb(upgraded.tint.0, upgraded.tint.1, upgraded.tint.2);
+                    if colored_layer.map(|c| c.color) != Some(color) {
+                        commands.entity(child).insert(ColoredLayer { color });
+                    }
+                }
+                (None, Some(_)) => {
+                    commands.entity(child).remove::<ColoredLayer>();
+                }
+                (None, None) => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::weapons::{FiringMode, FiringModeConfig};
+
+    fn pistol() -> WeaponConfig {
+        let mut firing_modes = HashMap::new();
+        firing_modes.insert(
+            "single".to_string(),
+            FiringModeConfig {
+                firing_rate: fixed_math::new(4.0),
+                firing_mode: FiringMode::Manual {},
+                spread: fixed_math::FIXED_ZERO,
+                recoil: fixed_math::FIXED_ZERO,
+                bullet_type: BulletType::Standard {
+                    damage: fixed_math::new(10.0),
+                    speed: fixed_math::new(400.0),
+                },
+                range: fixed_math::new(300.0),
+                damage_falloff: None,
+                reload_time_seconds: fixed_math::FIXED_ONE,
+                mag: MagBulletConfig::Mag {
+                    mag_size: 8,
+                    mag_limit: 64,
+                },
+            },
+        );
+        WeaponConfig {
+            name: "pistol".to_string(),
+            display_name: None,
+            default_firing_mode: "single".to_string(),
+            firing_modes,
+        }
+    }
+
+    fn upgrade(bullet_type: Option<BulletType>) -> WeaponUpgradeConfig {
+        WeaponUpgradeConfig {
+            display_name: None,
+            damage_multiplier: fixed_math::new(3.0),
+            firing_rate_multiplier: fixed_math::FIXED_ONE,
+            mag_size_multiplier: fixed_math::FIXED_ONE,
+            mag: None,
+            bullet_type,
+        }
+    }
+
+    #[test]
+    fn test_damage_multiplier_scales_the_weapon_bullet() {
+        let upgraded = upgrade(None).apply(&pistol());
+        assert_eq!(
+            upgraded.firing_modes["single"].bullet_type,
+            BulletType::Standard {
+                damage: fixed_math::new(30.0),
+                speed: fixed_math::new(400.0),
+            }
+        );
+    }
+
+    #[test]
+    fn test_bullet_type_override_keeps_its_damage() {
+        let explosive = BulletType::Explosive {
+            damage: fixed_math::new(15.0),
+            speed: fixed_math::new(400.0),
+            blast_radius: fixed_math::new(30.0),
+            explosive_damage_multiplier: fixed_math::new(1.5),
+        };
+        let upgraded = upgrade(Some(explosive)).apply(&pistol());
+        assert_eq!(upgraded.firing_modes["single"].bullet_type, explosive);
+    }
+}
@@ -0,0 +1,126 @@
+use std::collections::HashSet;
+
+use bevy::prelude::*;
+use bevy_fixed::fixed_math;
+use utils::{frame::FrameCount, net_id::StableIdType};
+
+use super::{BulletHitEvent, EffectType, VisualEffectRequest};
+
+impl EffectType {
+    pub fn color(&self) -> Color {
+        match self {
+            EffectType::BulletHit => Color::srgb(1.0, 1.0, 1.0),
+            EffectType::Explosion => Color::srgb(1.0, 0.6, 0.1),
+            EffectType::Piercing => Color::srgb(0.8, 0.8, 1.0),
+            EffectType::Incendiary => Color::srgb(1.0, 0.35, 0.0),
+            EffectType::Cryo => Color::srgb(0.5, 0.85, 1.0),
+            EffectType::Freeze => Color::srgb(0.75, 0.95, 1.0),
+            EffectType::CriticalHit => Color::srgb(1.0, 0.9, 0.0),
+        }
+    }
+
+    // Size in pixels of the effect at scale 1
+    pub fn size(&self) -> f32 {
+        match self {
+            EffectType::Explosion => 24.0,
+            EffectType::Freeze => 20.0,
+            EffectType::CriticalHit => 12.0,
+            _ => 8.0,
+        }
+    }
+
+    pub fn duration_frames(&self) -> u32 {
+        match self {
+            EffectType::Explosion | EffectType::Freeze => 20,
+            _ => 10,
+        }
+    }
+}
+
+// Hits are remembered this many frames to skip their resimulations, longer
+// than any rollback
+const HIT_MEMORY_FRAMES: u32 = 120;
+
+// VISUAL EFFECT
+// Non rollback visual spawned from a VisualEffectRequest, fades out then despawns
+#[derive(Component)]
+pub struct VisualEffect {
+    pub start_frame: u32,
+    pub duration_frames: u32,
+    pub color: Color,
+}
+
+// SYSTEM: SPAWN HIT EFFECTS
+// Flash on the critical and elemental hits reported by the bullet collision
+// system, once per hit even when a rollback resimulates it
+pub fn spawn_hit_effects(
+    mut commands: Commands,
+    frame: Res<FrameCount>,
+    mut hit_events: MessageReader<BulletHitEvent>,
+    mut seen_hits: Local<HashSet<(u32, StableIdType, StableIdType)>>,
+) {
+    seen_hits.retain(|(hit_frame, ..)| hit_frame + HIT_MEMORY_FRAMES > frame.frame);
+
+    for event in hit_events.read() {
+        if !seen_hits.insert((event.frame, event.bullet.0, event.target.0)) {
+            continue;
+        }
+
+        let critical = event.is_critical.then_some(EffectType::CriticalHit);
+        for effect_type in critical.into_iter().chain(event.elemental_effect) {
+            commands.spawn(VisualEffectRequest {
+                effect_type,
+                position: event.position,
+                scale: fixed_math::FIXED_ONE,
+            });
+        }
+    }
+}
+
+// SYSTEM: SPAWN VISUAL EFFECTS
+// Turn the requests from the rollback systems into sprites
+pub fn spawn_visual_effects(
+    mut commands: Commands,
+    frame: Res<FrameCount>,
+    request_query: Query<(Entity, &VisualEffectRequest)>,
+) {
+    for (entity, request) in request_query.iter() {
+        let color = request.effect_type.color();
+        let size = request.effect_type.size() * fixed_math::to_f32(request.scale);
+
+        commands
+            .entity(entity)
+            .remove::<VisualEffectRequest>()
+            .insert((
+                VisualEffect {
+                    start_frame: frame.frame,
+                    duration_frames: request.effect_type.duration_frames(),
+                    color,
+                },
+                Sprite::from_color(color, Vec2::new(size, size)),
+                Transform::from_translation(
+                    fixed_math::fixed_to_vec2(request.position).extend(1.0),
+                ),
+            ));
+    }
+}
+
+// SYSTEM: UPDATE VISUAL EFFECTS
+pub fn update_visual_effects(
+    mut commands: Commands,
+    frame: Res<FrameCount>,
+    mut effect_query: Query<(Entity, &VisualEffect, &mut Sprite)>,
+) {
+    for (entity, effect, mut sprite) in effect_query.iter_mut() {
+        let frames_alive = frame.frame.saturating_sub(effect.start_frame);
+
+        // Despawn when the effect is over
+        if frames_alive >= effect.duration_frames {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        let alpha = 1.0 - frames_alive as f32 / effect.duration_frames as f32;
+        sprite.color = effect.color.with_alpha(alpha);
+    }
+}
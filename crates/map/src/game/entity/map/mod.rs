@@ -4,4 +4,8 @@ pub mod player_spawn;
 pub mod window;
 pub mod map_rollback;
 pub mod room;
-pub mod level_id;
\ No newline at end of file
+pub mod level_id;
+pub mod mystery_box;
+pub mod upgrade_station;
+pub mod power_switch;
+pub mod perk_machine;
@@ -0,0 +1,5 @@
+use bevy::prelude::*;
+
+/// Marker for a location where the mystery box can appear
+#[derive(Default, Component, Reflect)]
+pub struct MysteryBoxLocationComponent;
@@ -0,0 +1,7 @@
+use bevy::prelude::*;
+
+/// Perk machine, selling the perk with the given id
+#[derive(Default, Component, Clone, Debug, Reflect)]
+pub struct PerkMachineComponent {
+    pub perk: String,
+}
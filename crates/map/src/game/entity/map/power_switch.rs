@@ -0,0 +1,5 @@
+use bevy::prelude::*;
+
+/// Marker for the switch turning on the power of the map
+#[derive(Default, Component, Reflect)]
+pub struct PowerSwitchComponent;
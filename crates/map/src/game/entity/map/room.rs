@@ -19,4 +19,10 @@ impl RoomBounds {
         point.x >= self.position.x && point.x <= self.position.x + self.size.x &&
         point.y >= self.position.y && point.y <= self.position.y + self.size.y
     }
-}
\ No newline at end of file
+
+    /// Same as `contains` but with the bounds grown by `margin` on every side
+    pub fn contains_with_margin(&self, point: FixedVec2, margin: fixed_math::Fixed) -> bool {
+        point.x >= self.position.x - margin && point.x <= self.position.x + self.size.x + margin &&
+        point.y >= self.position.y - margin && point.y <= self.position.y + self.size.y + margin
+    }
+}
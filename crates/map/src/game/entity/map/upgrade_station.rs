@@ -0,0 +1,5 @@
+use bevy::prelude::*;
+
+/// Marker for the weapon upgrade station
+#[derive(Default, Component, Reflect)]
+pub struct UpgradeStationComponent;
@@ -1,4 +1,8 @@
 pub mod door;
 pub mod enemy_spawn;
+pub mod mystery_box;
 pub mod player_spawn;
 pub mod window;
+pub mod upgrade_station;
+pub mod power_switch;
+pub mod perk_machine;
@@ -0,0 +1,25 @@
+use bevy::prelude::*;
+use bevy_ecs_ldtk::prelude::*;
+
+use map::game::entity::map::{map_rollback::MapRollbackMarker, mystery_box::MysteryBoxLocationComponent};
+
+#[derive(Bundle, LdtkEntity)]
+pub struct MysteryBoxBundle {
+    location: MysteryBoxLocationComponent,
+    rollback_marker: MapRollbackMarker,
+    #[sprite_sheet]
+    sprite_sheet: Sprite,
+    visibility: Visibility,
+}
+
+impl Default for MysteryBoxBundle {
+    fn default() -> Self {
+        Self {
+            rollback_marker: MapRollbackMarker("mystery_box".into()),
+            location: MysteryBoxLocationComponent,
+            sprite_sheet: Sprite::default(),
+            // Hidden until the box is placed on this location
+            visibility: Visibility::Hidden,
+        }
+    }
+}
@@ -0,0 +1,34 @@
+use bevy::prelude::*;
+use bevy_ecs_ldtk::prelude::*;
+
+use map::game::entity::map::{map_rollback::MapRollbackMarker, perk_machine::PerkMachineComponent};
+
+use crate::map_const;
+
+pub fn perk_machine_component_from_field(entity_instance: &EntityInstance) -> PerkMachineComponent {
+    PerkMachineComponent {
+        perk: entity_instance
+            .get_string_field(map_const::FIELD_PERK_NAME)
+            .cloned()
+            .unwrap_or_default(),
+    }
+}
+
+#[derive(Bundle, LdtkEntity)]
+pub struct PerkMachineBundle {
+    #[with(perk_machine_component_from_field)]
+    machine: PerkMachineComponent,
+    rollback_marker: MapRollbackMarker,
+    #[sprite_sheet]
+    sprite_sheet: Sprite,
+}
+
+impl Default for PerkMachineBundle {
+    fn default() -> Self {
+        Self {
+            rollback_marker: MapRollbackMarker("perk_machine".into()),
+            machine: PerkMachineComponent::default(),
+            sprite_sheet: Sprite::default(),
+        }
+    }
+}
@@ -0,0 +1,22 @@
+use bevy::prelude::*;
+use bevy_ecs_ldtk::prelude::*;
+
+use map::game::entity::map::{map_rollback::MapRollbackMarker, power_switch::PowerSwitchComponent};
+
+#[derive(Bundle, LdtkEntity)]
+pub struct PowerSwitchBundle {
+    power_switch: PowerSwitchComponent,
+    rollback_marker: MapRollbackMarker,
+    #[sprite_sheet]
+    sprite_sheet: Sprite,
+}
+
+impl Default for PowerSwitchBundle {
+    fn default() -> Self {
+        Self {
+            rollback_marker: MapRollbackMarker("power_switch".into()),
+            power_switch: PowerSwitchComponent,
+            sprite_sheet: Sprite::default(),
+        }
+    }
+}
@@ -0,0 +1,22 @@
+use bevy::prelude::*;
+use bevy_ecs_ldtk::prelude::*;
+
+use map::game::entity::map::{map_rollback::MapRollbackMarker, upgrade_station::UpgradeStationComponent};
+
+#[derive(Bundle, LdtkEntity)]
+pub struct UpgradeStationBundle {
+    station: UpgradeStationComponent,
+    rollback_marker: MapRollbackMarker,
+    #[sprite_sheet]
+    sprite_sheet: Sprite,
+}
+
+impl Default for UpgradeStationBundle {
+    fn default() -> Self {
+        Self {
+            rollback_marker: MapRollbackMarker("upgrade_station".into()),
+            station: UpgradeStationComponent,
+            sprite_sheet: Sprite::default(),
+        }
+    }
+}
@@ -3,7 +3,7 @@ use bevy::ecs::relationship::Relationship;
 use bevy_fixed::fixed_math;
 use bevy_ecs_ldtk::prelude::LevelIid;
 use game::{character::enemy::ai::Obstacle, character::enemy::spawning::EnemySpawnerState, collider::{Collider, CollisionLayer, CollisionSettings, Wall, Window}, core::AppState};
-use map::game::entity::{map::{door::{DoorComponent, DoorGridPosition}, enemy_spawn::EnemySpawnerComponent, level_id::LevelId, map_rollback::MapRollbackMarker}, MapRollbackItem};
+use map::game::entity::{map::{door::{DoorComponent, DoorGridPosition}, enemy_spawn::EnemySpawnerComponent, level_id::LevelId, map_rollback::MapRollbackMarker, perk_machine::PerkMachineComponent}, MapRollbackItem};
 use map::generation::entity::door::DoorConfig;
 use bevy_ggrs::AddRollbackCommandExtension;
 use utils::net_id::GgrsNetIdFactory;
@@ -23,6 +23,7 @@ pub struct LdtkMapEntityLoading {
     pub door_config: Option<DoorConfig>,
     pub door_grid_position: Option<DoorGridPosition>,
     pub spawner_config: Option<EnemySpawnerComponent>,
+    pub perk_machine: Option<PerkMachineComponent>,
     pub level_id: Option<LevelId>,
 }
 
@@ -110,7 +111,7 @@ fn wait_for_all_map_rollback_entity(
     mut entity_registery: ResMut<LdtkMapEntityLoadingRegistry>,
     mut ev_loading_map: MessageWriter<LdtkMapLoadingEvent>,
 
-    query_map_entity: Query<(Entity, &GlobalTransform, &MapRollbackMarker, Option<&LdtkEntitySize>, Option<&DoorComponent>, Option<&DoorGridPosition>, Option<&EnemySpawnerComponent>, Option<&ChildOf>), With<MapRollbackMarker>>,
+    query_map_entity: Query<(Entity, &GlobalTransform, &MapRollbackMarker, Option<&LdtkEntitySize>, Option<&DoorComponent>, Option<&DoorGridPosition>, Option<&EnemySpawnerComponent>, Option<&PerkMachineComponent>, Option<&ChildOf>), With<MapRollbackMarker>>,
 
     collision_settings: Res<CollisionSettings>,
 
@@ -129,7 +130,7 @@ fn wait_for_all_map_rollback_entity(
 
     // Collect and sort entities by their marker name and position for deterministic order
     let mut entities_to_process: Vec<_> = query_map_entity.iter()
-        .filter(|(e, _, _, _, _, _, _, _)| !entity_registery.registered_entities.contains(e))
+        .filter(|(e, _, _, _, _, _, _, _, _)| !entity_registery.registered_entities.contains(e))
         .collect();
     
     // Sort by marker name first, then by position (x, y) for determinism
@@ -146,7 +147,7 @@ fn wait_for_all_map_rollback_entity(
             .then_with(|| pos_a.y.partial_cmp(&pos_b.y).unwrap_or(std::cmp::Ordering::Equal))
     });
 
-    for (e, global_transform, rollback_marker, ldtk_size, door_component, door_grid_pos, spawner_component, parent) in entities_to_process {
+    for (e, global_transform, rollback_marker, ldtk_size, door_component, door_grid_pos, spawner_component, perk_machine, parent) in entities_to_process {
         // Skip if already registered (should not happen due to filter above, but keeping for safety)
         if entity_registery.registered_entities.contains(&e) {
             continue;
@@ -169,6 +170,7 @@ fn wait_for_all_map_rollback_entity(
             let door_config = door_component.map(|dc| dc.config.clone());
             let door_grid_position = door_grid_pos.cloned();
             let spawner_config = spawner_component.cloned();
+            let perk_machine = perk_machine.cloned();
             info!("Found {} entity {:?} at position {} with LDTK size {:?} and door config {:?}",
                   rollback_marker.0, e, translation, sprite_size, door_config);
 
@@ -181,6 +183,7 @@ fn wait_for_all_map_rollback_entity(
                 door_config,
                 door_grid_position,
                 spawner_config,
+                perk_machine,
                 level_id: entity_level_id,
             });
             entity_registery.registered_entities.insert(e);
@@ -331,6 +334,57 @@ fn wait_for_all_map_rollback_entity(
                     ));
                     info!("adding enemy spawner at {:?}", world_position);
                 },
+                "mystery_box" => {
+                    // The Interactable is only added by the game on the location the box sits on
+                    cmd.insert(game::mystery_box::MysteryBoxLocation);
+                    info!("adding mystery box location at {:?}", world_position);
+                },
+                "upgrade_station" => {
+                    let interaction_range = item
+                        .sprite_size
+                        .map(|size| size.x.max(size.y))
+                        .unwrap_or(32.0);
+
+                    cmd.insert((
+                        game::weapons::upgrade::UpgradeStation::default(),
+                        game::interaction::Interactable {
+                            interaction_range: fixed_math::new(interaction_range),
+                            interaction_type: game::interaction::InteractionType::UpgradeStation,
+                        },
+                    ));
+                    info!("adding upgrade station at {:?}, interaction range {}", world_position, interaction_range);
+                },
+                "power_switch" => {
+                    let interaction_range = item
+                        .sprite_size
+                        .map(|size| size.x.max(size.y))
+                        .unwrap_or(32.0);
+
+                    cmd.insert((
+                        game::power::PowerSwitch,
+                        game::interaction::Interactable {
+                            interaction_range: fixed_math::new(interaction_range),
+                            interaction_type: game::interaction::InteractionType::PowerSwitch,
+                        },
+                    ));
+                    info!("adding power switch at {:?}, interaction range {}", world_position, interaction_range);
+                },
+                "perk_machine" => {
+                    let interaction_range = item
+                        .sprite_size
+                        .map(|size| size.x.max(size.y))
+                        .unwrap_or(32.0);
+                    let perk = item.perk_machine.clone().unwrap_or_default().perk;
+
+                    cmd.insert((
+                        game::perk::PerkMachine { perk: perk.clone() },
+                        game::interaction::Interactable {
+                            interaction_range: fixed_math::new(interaction_range),
+                            interaction_type: game::interaction::InteractionType::Perk,
+                        },
+                    ));
+                    info!("adding perk machine {} at {:?}, interaction range {}", perk, world_position, interaction_range);
+                },
                 _ => {}
             }
 
@@ -12,6 +12,8 @@ pub const ENTITY_WEAPON_LOCATION: &str = "WeaponLocation";
 pub const ENTITY_WINDOW_VERTICAL_LOCATION: &str = "WindowVertical";
 pub const ENTITY_WINDOW_HORIZONTAL_LOCATION: &str = "WindowHorizontal";
 pub const ENTITY_SODA_LOCATION: &str = "SodaLocation";
+pub const ENTITY_UPGRADE_STATION_LOCATION: &str = "UpgradeStation";
+pub const ENTITY_POWER_SWITCH_LOCATION: &str = "PowerSwitch";
 
 // pub const FIELD_BOOL_TYPE: &str = "Bool";
 // pub const FIELD_INT_TYPE: &str = "Int";
@@ -27,3 +29,5 @@ pub const FIELD_PAIRED_DOOR_LEVEL_NAME: &str = "paired_door_level";
 
 
 pub const FIELD_PLAYER_SPAWN_INDEX_NAME: &str = "index";
+
+pub const FIELD_PERK_NAME: &str = "perk";
@@ -5,7 +5,7 @@ use crate::game::{collider::create_wall_colliders_from_ldtk, plugin::LdtkMapLoad
 
 use super::{
     game::{
-        entity::{door::DoorBundle, enemy_spawn::EnemySpawnBundle, player_spawn::PlayerSpawnBundle, window::WindowBundle},
+        entity::{door::DoorBundle, enemy_spawn::EnemySpawnBundle, mystery_box::MysteryBoxBundle, perk_machine::PerkMachineBundle, player_spawn::PlayerSpawnBundle, power_switch::PowerSwitchBundle, upgrade_station::UpgradeStationBundle, window::WindowBundle},
         system::add_level_components::add_room_component_to_ldtk_level,
     },
     map_const,
@@ -24,7 +24,11 @@ impl Plugin for EntityPlugin {
         .register_ldtk_entity::<WindowBundle>(map_const::ENTITY_WINDOW_HORIZONTAL_LOCATION)
         .register_ldtk_entity::<WindowBundle>(map_const::ENTITY_WINDOW_VERTICAL_LOCATION)
         .register_ldtk_entity::<DoorBundle>(map_const::ENTITY_DOOR_HORIZONTAL_LOCATION)
-        .register_ldtk_entity::<DoorBundle>(map_const::ENTITY_DOOR_VERTICAL_LOCATION);
+        .register_ldtk_entity::<DoorBundle>(map_const::ENTITY_DOOR_VERTICAL_LOCATION)
+        .register_ldtk_entity::<MysteryBoxBundle>(map_const::ENTITY_CRATE_LOCATION)
+        .register_ldtk_entity::<UpgradeStationBundle>(map_const::ENTITY_UPGRADE_STATION_LOCATION)
+        .register_ldtk_entity::<PowerSwitchBundle>(map_const::ENTITY_POWER_SWITCH_LOCATION)
+        .register_ldtk_entity::<PerkMachineBundle>(map_const::ENTITY_SODA_LOCATION);
     }
 }
 
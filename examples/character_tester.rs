@@ -2,7 +2,7 @@ use animation::SpriteSheetConfig;
 use bevy::{color::palettes::{css::TURQUOISE, tailwind::{ORANGE_300, PURPLE_300}}, prelude::*};
 use bevy_fixed::fixed_math;
 use game::{
-    args::BaseArgsPlugin, character::{config::CharacterConfig, enemy::spawning::EnemySpawnerState, player::create::create_player}, collider::{spawn_test_wall, CollisionSettings}, core::{AppState, CoreSetupConfig, CoreSetupPlugin}, global_asset::GlobalAsset, jjrs::{GggrsSessionConfiguration, GggrsSessionConfigurationState, GgrsSessionBuilding}, waves::{WaveDebugEnabled, WaveModeEnabled}, weapons::{melee::MeleeWeaponsConfig, WeaponsConfig}
+    args::BaseArgsPlugin, character::{config::CharacterConfig, enemy::spawning::EnemySpawnerState, player::create::create_player}, collider::{spawn_test_wall, CollisionSettings}, core::{AppState, CoreSetupConfig, CoreSetupPlugin}, game_mode::ActiveGameMode, global_asset::GlobalAsset, jjrs::{GggrsSessionConfiguration, GggrsSessionConfigurationState, GgrsSessionBuilding}, waves::WaveDebugEnabled, weapons::{melee::MeleeWeaponsConfig, WeaponsConfig}
 };
 use map::game::entity::map::enemy_spawn::EnemySpawnerComponent;
 use utils::net_id::GgrsNetIdFactory;
@@ -23,8 +23,6 @@ fn main() {
 
         // Because i don't have extra configuration yet we can directly start
         .insert_resource(GggrsSessionConfigurationState::ready())
-        // Enable wave-based spawning mode (CoD Zombies style)
-        .insert_resource(WaveModeEnabled(true))
         // Enable wave debug UI (toggle with F3)
         .insert_resource(WaveDebugEnabled(true))
         .add_systems(OnEnter(AppState::GameLoading), (
@@ -56,6 +54,9 @@ fn system_game_loading(
 
     mut id_provider: ResMut<GgrsNetIdFactory>,
 
+    // Starting loadout and points of the selected game mode
+    game_mode: Res<ActiveGameMode>,
+
     ggrs_session_building: Res<GgrsSessionBuilding>,
 ) {
 
@@ -86,6 +87,7 @@ fn system_game_loading(
             i,
             name,
             pubkey,
+            &game_mode.rules,
             &mut id_provider,
         );
     }
@@ -4,7 +4,7 @@ use animation::SpriteSheetConfig;
 use bevy::{color::palettes::{css::TURQUOISE, tailwind::{ORANGE_300, PURPLE_300}}, platform::collections::HashMap, prelude::*};
 use bevy_fixed::fixed_math;
 use game::{
-    args::BaseArgsPlugin, character::{config::CharacterConfig, enemy::spawning::EnemySpawnerState, player::create::create_player}, collider::{spawn_test_wall, CollisionSettings}, core::{AppState, CoreSetupConfig, CoreSetupPlugin}, global_asset::GlobalAsset, jjrs::{GggrsSessionConfiguration, GggrsSessionConfigurationState, GgrsSessionBuilding}, waves::{WaveDebugEnabled, WaveModeEnabled}, weapons::{melee::MeleeWeaponsConfig, WeaponsConfig}
+    args::BaseArgsPlugin, character::{config::CharacterConfig, enemy::spawning::EnemySpawnerState, player::create::create_player}, collider::{spawn_test_wall, CollisionSettings}, core::{AppState, CoreSetupConfig, CoreSetupPlugin}, game_mode::ActiveGameMode, global_asset::GlobalAsset, jjrs::{GggrsSessionConfiguration, GggrsSessionConfigurationState, GgrsSessionBuilding}, waves::WaveDebugEnabled, weapons::{melee::MeleeWeaponsConfig, WeaponsConfig}
 };
 use map::{game::entity::map::{enemy_spawn::EnemySpawnerComponent, player_spawn::PlayerSpawnConfig}, generation::{config::MapGenerationConfig, position}};
 use map_ldtk::{game::plugin::LdtkMapLoadingEvent, plugins::LdtkRoguePlugin};
@@ -28,8 +28,6 @@ fn main() {
         .add_plugins(core_plugin)
         // Plugins for rogue like map with ldtk
         .add_plugins(LdtkRoguePlugin)
-        // Enable wave-based spawning mode (CoD Zombies style)
-        .insert_resource(WaveModeEnabled(true))
         // Enable wave debug UI (toggle with F3)
         .insert_resource(WaveDebugEnabled(true))
         .add_systems(OnEnter(AppState::LobbyLocal), system_configure_map)
@@ -67,6 +65,9 @@ fn system_wait_for_map_loaded(
 
     mut id_provider: ResMut<GgrsNetIdFactory>,
 
+    // Starting loadout and points of the selected game mode
+    game_mode: Res<ActiveGameMode>,
+
     // Get my players
     ggrs_session_building: Res<GgrsSessionBuilding>,
 
@@ -111,6 +112,7 @@ fn system_wait_for_map_loaded(
             i,
             name,
             pubkey,
+            &game_mode.rules,
             &mut id_provider,
         );
     }
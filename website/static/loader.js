@@ -34,6 +34,12 @@ document.addEventListener('DOMContentLoaded', function() {
       console.log("MATCHBOX " + matchbox + " NUMBER " + lobby_size + " LOBBY " + lobby + " PLAYERS " + players);
     }
 
+    const game_mode = urlParams.get("game_mode");
+    if (game_mode) {
+      let canvas = document.getElementById("bevy-canvas");
+      canvas.setAttribute("data-game-mode", game_mode);
+    }
+
     const telemetry = urlParams.get("telemetry");
     if (telemetry === "true") {
       let canvas = document.getElementById("bevy-canvas");